@@ -270,7 +270,11 @@ impl<'op> PushrebaseOntoBookmarkOp<'op> {
                 // Marking the pushrebased changeset as public.
                 if kind.is_public() {
                     repo.phases()
-                        .add_reachable_as_public(ctx, vec![outcome.head.clone()])
+                        .add_reachable_as_public_with_bookmark(
+                            ctx,
+                            vec![outcome.head.clone()],
+                            self.bookmark.clone(),
+                        )
                         .await?;
                 }
             }
@@ -18,6 +18,7 @@ use anyhow::Error;
 use async_trait::async_trait;
 use basename_suffix_skeleton_manifest_v3::RootBssmV3DirectoryId;
 use blame::RootBlameV2;
+use blame::RootBlameV3;
 use bookmarks::ArcBookmarkUpdateLog;
 use bookmarks::ArcBookmarks;
 use bookmarks::BookmarkCategory;
@@ -87,6 +88,10 @@ define_stats! {
     bookmark_discover_failures: timeseries(Rate, Sum),
     bookmark_update_failures: timeseries(Rate, Sum),
     max_staleness_secs: dynamic_singleton_counter("{}.max_staleness_secs", (reponame: String)),
+    derived_data_lag_secs: dynamic_singleton_counter(
+        "{}.derived_data_lag_secs.{}",
+        (reponame: String, derived_data_type: String)
+    ),
 }
 
 pub struct WarmBookmarksCache {
@@ -229,6 +234,10 @@ impl WarmBookmarksCacheBuilder {
                 &self.ctx,
                 repo_derived_data.clone(),
             )),
+            DerivableType::BlameV3 => Some(create_derived_data_warmer::<RootBlameV3>(
+                &self.ctx,
+                repo_derived_data.clone(),
+            )),
             DerivableType::FileNodes => {
                 // TODO: add warmer for filenodes
                 None
@@ -283,6 +292,7 @@ impl WarmBookmarksCacheBuilder {
             )),
             DerivableType::TestManifests => None,
             DerivableType::TestShardedManifests => None,
+            DerivableType::DirectoryChurn => None,
         }
     }
 
@@ -570,6 +580,31 @@ async fn is_warm(ctx: &CoreContext, cs_id: ChangesetId, warmers: &[Warmer]) -> b
         .await
 }
 
+/// Report, for each warmer that isn't warm yet for `cs_id`, how stale (in seconds) the oldest
+/// underived bookmark entry is. This lets dashboards tell which derived data type is actually
+/// holding up a bookmark (e.g. a slow fsnodes backfill) rather than just the aggregate staleness.
+async fn report_per_warmer_lag(
+    ctx: &CoreContext,
+    reponame: &str,
+    cs_id: ChangesetId,
+    ts: Timestamp,
+    warmers: &[Warmer],
+) {
+    let lag_secs = ts.since_seconds();
+    stream::iter(warmers.iter())
+        .for_each_concurrent(100, |warmer| async move {
+            let is_warm = (*warmer.is_warm)(ctx, cs_id).await.unwrap_or(false);
+            if !is_warm {
+                STATS::derived_data_lag_secs.set_value(
+                    ctx.fb,
+                    lag_secs,
+                    (reponame.to_owned(), warmer.name.clone()),
+                );
+            }
+        })
+        .await;
+}
+
 async fn warm_all(ctx: &CoreContext, cs_id: ChangesetId, warmers: &[Warmer]) -> Result<(), Error> {
     stream::iter(warmers.iter().map(Ok))
         .try_for_each_concurrent(100, |warmer| async {
@@ -1051,7 +1086,7 @@ impl BookmarkUpdaterState {
 
 async fn single_bookmark_updater(
     ctx: &CoreContext,
-    repo: &(impl BookmarksRef + BookmarkUpdateLogRef),
+    repo: &(impl BookmarksRef + BookmarkUpdateLogRef + RepoIdentityRef),
     bookmark: &Bookmark,
     bookmarks: &Arc<RwLock<HashMap<BookmarkKey, (ChangesetId, BookmarkKind)>>>,
     warmers: &Arc<Vec<Warmer>>,
@@ -1106,6 +1141,17 @@ async fn single_bookmark_updater(
         let bookmark_log_id = maybe_id_ts.as_ref().map(|(id, _)| u64::from(*id));
         let maybe_ts = maybe_id_ts.map(|(_, ts)| ts);
 
+        if let Some(ts) = maybe_ts {
+            report_per_warmer_lag(
+                ctx,
+                repo.repo_identity().name(),
+                underived_cs_id,
+                ts,
+                warmers,
+            )
+            .await;
+        }
+
         let ctx = ctx.clone().with_mutated_scuba(|mut scuba| {
             scuba.add("bookmark", bookmark.key().to_string());
             scuba.add("bookmark_log_id", bookmark_log_id);
@@ -42,6 +42,7 @@ use cmdlib_x_repo::create_commit_syncers_from_matches;
 use commit_graph::CommitGraph;
 use commit_graph::CommitGraphWriter;
 use context::CoreContext;
+use cross_repo_sync::check_bookmark_consistency;
 use cross_repo_sync::create_commit_syncer_lease;
 use cross_repo_sync::create_commit_syncers;
 use cross_repo_sync::find_bookmark_diff;
@@ -1199,9 +1200,11 @@ async fn subcommand_verify_bookmarks(
             Ok(())
         }
         VerifyRunMode::JustVerify => {
-            for d in &diff {
+            let divergences =
+                check_bookmark_consistency(ctx.clone(), &syncers.large_to_small).await?;
+            for divergence in &divergences {
                 use BookmarkDiff::*;
-                match d {
+                match &divergence.diff {
                     InconsistentValue {
                         target_bookmark,
                         target_cs_id,
@@ -1209,12 +1212,13 @@ async fn subcommand_verify_bookmarks(
                     } => {
                         warn!(
                             ctx.logger(),
-                            "inconsistent value of {}: '{}' has {}, but '{}' bookmark points to {:?}",
+                            "inconsistent value of {}: '{}' has {}, but '{}' bookmark points to {:?} ({:?} sync is responsible for reconciling this)",
                             target_bookmark,
                             target_repo.repo_identity().name(),
                             target_cs_id,
                             source_repo.repo_identity().name(),
                             source_cs_id,
+                            divergence.responsible_direction,
                         );
                     }
                     MissingInTarget {
@@ -1223,11 +1227,12 @@ async fn subcommand_verify_bookmarks(
                     } => {
                         warn!(
                             ctx.logger(),
-                            "'{}' doesn't have bookmark {} but '{}' has it and it points to {}",
+                            "'{}' doesn't have bookmark {} but '{}' has it and it points to {} ({:?} sync is responsible for reconciling this)",
                             target_repo.repo_identity().name(),
                             target_bookmark,
                             source_repo.repo_identity().name(),
                             source_cs_id,
+                            divergence.responsible_direction,
                         );
                     }
                     NoSyncOutcome { target_bookmark } => {
@@ -1243,7 +1248,7 @@ async fn subcommand_verify_bookmarks(
                     }
                 }
             }
-            Err(format_err!("found {} inconsistencies", diff.len()).into())
+            Err(format_err!("found {} inconsistencies", divergences.len()).into())
         }
     }
 }
@@ -1792,6 +1797,8 @@ mod test {
                     small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                         bookmark_prefix: Default::default(),
                         common_pushrebase_bookmarks_map: Default::default(),
+                        bookmark_redirection_namespaces: Vec::new(),
+                        pushvar_passthrough_policy: Default::default(),
                     },
                 },
                 large_repo_id: large_repo.repo_identity().id(),
@@ -1884,6 +1891,8 @@ mod test {
                 small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::new(),
                     common_pushrebase_bookmarks_map: Default::default(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 }
             },
             large_repo_id: large_repo.repo_identity().id(),
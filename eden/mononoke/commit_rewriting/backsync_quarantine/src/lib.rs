@@ -0,0 +1,73 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Backsync quarantine queue
+//!
+//! When backsyncing a bookmark update log entry fails (for example because
+//! the rewritten commit conflicts with a path already present in the target
+//! repo), the backsyncer records the failing entry here instead of blocking
+//! the whole source repo from making further progress. Operators can later
+//! list the quarantined entries, retry them once the underlying problem has
+//! been fixed, or skip them for good.
+
+mod sql_queries;
+#[cfg(test)]
+mod test;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bookmarks::BookmarkKey;
+use context::CoreContext;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
+pub use sql_queries::SqlBacksyncQuarantine;
+pub use sql_queries::SqlBacksyncQuarantineConnections;
+
+/// A bookmark update log entry that failed to backsync.
+#[derive(Clone, Debug)]
+pub struct QuarantinedEntry {
+    /// Id of this quarantine record - not to be confused with `log_id`.
+    pub id: u64,
+    /// Id of the source repo the failing entry came from.
+    pub source_repo_id: RepositoryId,
+    /// Id of the bookmark update log entry that failed to backsync.
+    pub log_id: u64,
+    pub bookmark_name: BookmarkKey,
+    pub from_changeset_id: Option<ChangesetId>,
+    pub to_changeset_id: Option<ChangesetId>,
+    /// Human-readable description of why backsyncing this entry failed.
+    pub error: String,
+    pub quarantined_at: Timestamp,
+}
+
+/// Quarantine queue for a single target repo's backsyncer. Scoped to the
+/// target repo the entries were meant to be backsynced into.
+#[facet::facet]
+#[async_trait]
+pub trait BacksyncQuarantine: Send + Sync {
+    /// Record a backsync failure so it can be inspected, retried or skipped
+    /// later, without blocking the backsync of other bookmarks.
+    #[allow(clippy::too_many_arguments)]
+    async fn quarantine(
+        &self,
+        ctx: &CoreContext,
+        source_repo_id: RepositoryId,
+        log_id: u64,
+        bookmark_name: &BookmarkKey,
+        from_changeset_id: Option<ChangesetId>,
+        to_changeset_id: Option<ChangesetId>,
+        error: &str,
+    ) -> Result<()>;
+
+    /// List all entries currently quarantined, oldest first.
+    async fn list(&self, ctx: &CoreContext) -> Result<Vec<QuarantinedEntry>>;
+
+    /// Remove a quarantined entry, either because a retry of it succeeded or
+    /// because an operator chose to skip it for good.
+    async fn remove(&self, ctx: &CoreContext, id: u64) -> Result<()>;
+}
@@ -0,0 +1,175 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bookmarks::BookmarkKey;
+use context::CoreContext;
+use context::PerfCounterType;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
+use sql::Connection;
+use sql_construct::SqlConstruct;
+use sql_construct::SqlConstructFromMetadataDatabaseConfig;
+use sql_ext::SqlConnections;
+use sql_ext::mononoke_queries;
+
+use crate::BacksyncQuarantine;
+use crate::QuarantinedEntry;
+
+mononoke_queries! {
+    write InsertQuarantineEntry(
+        repo_id: RepositoryId,
+        source_repo_id: RepositoryId,
+        log_id: u64,
+        bookmark_name: String,
+        from_changeset_id: Option<ChangesetId>,
+        to_changeset_id: Option<ChangesetId>,
+        error: String,
+        quarantined_at: Timestamp,
+    ) {
+        none,
+        "INSERT INTO backsync_quarantine
+         (repo_id, source_repo_id, log_id, bookmark_name, from_changeset_id, to_changeset_id, error, quarantined_at)
+         VALUES ({repo_id}, {source_repo_id}, {log_id}, {bookmark_name}, {from_changeset_id}, {to_changeset_id}, {error}, {quarantined_at})"
+    }
+
+    read SelectQuarantineEntries(repo_id: RepositoryId) -> (
+        u64,
+        RepositoryId,
+        u64,
+        String,
+        Option<ChangesetId>,
+        Option<ChangesetId>,
+        String,
+        Timestamp,
+    ) {
+        "SELECT id, source_repo_id, log_id, bookmark_name, from_changeset_id, to_changeset_id, error, quarantined_at
+         FROM backsync_quarantine
+         WHERE repo_id = {repo_id}
+         ORDER BY id ASC"
+    }
+
+    write DeleteQuarantineEntry(repo_id: RepositoryId, id: u64) {
+        "DELETE FROM backsync_quarantine WHERE repo_id = {repo_id} AND id = {id}"
+    }
+}
+
+pub struct SqlBacksyncQuarantine {
+    repo_id: RepositoryId,
+    connections: SqlBacksyncQuarantineConnections,
+}
+
+impl SqlBacksyncQuarantine {
+    pub fn new(repo_id: RepositoryId, connections: SqlBacksyncQuarantineConnections) -> Self {
+        Self {
+            repo_id,
+            connections,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SqlBacksyncQuarantineConnections {
+    #[allow(dead_code)]
+    write_connection: Connection,
+    read_connection: Connection,
+    read_master_connection: Connection,
+}
+
+impl SqlBacksyncQuarantineConnections {
+    pub fn with_repo_id(self, repo_id: RepositoryId) -> SqlBacksyncQuarantine {
+        SqlBacksyncQuarantine::new(repo_id, self)
+    }
+}
+
+impl SqlConstruct for SqlBacksyncQuarantineConnections {
+    const LABEL: &'static str = "backsync_quarantine";
+
+    const CREATION_QUERY: &'static str = include_str!("../schemas/sqlite-backsync-quarantine.sql");
+
+    fn from_sql_connections(connections: SqlConnections) -> Self {
+        Self {
+            write_connection: connections.write_connection,
+            read_connection: connections.read_connection,
+            read_master_connection: connections.read_master_connection,
+        }
+    }
+}
+
+impl SqlConstructFromMetadataDatabaseConfig for SqlBacksyncQuarantineConnections {}
+
+#[async_trait]
+impl BacksyncQuarantine for SqlBacksyncQuarantine {
+    async fn quarantine(
+        &self,
+        ctx: &CoreContext,
+        source_repo_id: RepositoryId,
+        log_id: u64,
+        bookmark_name: &BookmarkKey,
+        from_changeset_id: Option<ChangesetId>,
+        to_changeset_id: Option<ChangesetId>,
+        error: &str,
+    ) -> Result<()> {
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlWrites);
+        InsertQuarantineEntry::query(
+            &self.connections.write_connection,
+            &self.repo_id,
+            &source_repo_id,
+            &log_id,
+            &bookmark_name.to_string(),
+            &from_changeset_id,
+            &to_changeset_id,
+            &error.to_string(),
+            &Timestamp::now(),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn list(&self, ctx: &CoreContext) -> Result<Vec<QuarantinedEntry>> {
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlReadsReplica);
+        let rows = SelectQuarantineEntries::query(&self.connections.read_connection, &self.repo_id)
+            .await?;
+        rows.into_iter()
+            .map(
+                |(
+                    id,
+                    source_repo_id,
+                    log_id,
+                    bookmark_name,
+                    from_changeset_id,
+                    to_changeset_id,
+                    error,
+                    quarantined_at,
+                )| {
+                    Ok(QuarantinedEntry {
+                        id,
+                        source_repo_id,
+                        log_id,
+                        bookmark_name: BookmarkKey::new(bookmark_name)?,
+                        from_changeset_id,
+                        to_changeset_id,
+                        error,
+                        quarantined_at,
+                    })
+                },
+            )
+            .collect()
+    }
+
+    async fn remove(&self, ctx: &CoreContext, id: u64) -> Result<()> {
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlWrites);
+        DeleteQuarantineEntry::query(&self.connections.write_connection, &self.repo_id, &id)
+            .await?;
+        Ok(())
+    }
+}
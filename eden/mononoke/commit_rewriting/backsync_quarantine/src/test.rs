@@ -0,0 +1,52 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use fbinit::FacebookInit;
+use mononoke_types_mocks::changesetid;
+use mononoke_types_mocks::repo;
+use sql_construct::SqlConstruct;
+
+use crate::BacksyncQuarantine;
+use crate::SqlBacksyncQuarantineConnections;
+
+#[fbinit::test]
+async fn test_quarantine_list_remove(fb: FacebookInit) -> Result<()> {
+    let ctx = context::CoreContext::test_mock(fb);
+
+    let quarantine =
+        SqlBacksyncQuarantineConnections::with_sqlite_in_memory()?.with_repo_id(repo::REPO_ZERO);
+
+    assert!(quarantine.list(&ctx).await?.is_empty());
+
+    quarantine
+        .quarantine(
+            &ctx,
+            repo::REPO_ONE,
+            1,
+            &bookmarks::BookmarkKey::new("master")?,
+            Some(changesetid::ONES_CSID),
+            Some(changesetid::TWOS_CSID),
+            "path conflict",
+        )
+        .await?;
+
+    let entries = quarantine.list(&ctx).await?;
+    assert_eq!(entries.len(), 1);
+    let entry = &entries[0];
+    assert_eq!(entry.source_repo_id, repo::REPO_ONE);
+    assert_eq!(entry.log_id, 1);
+    assert_eq!(entry.bookmark_name, bookmarks::BookmarkKey::new("master")?);
+    assert_eq!(entry.from_changeset_id, Some(changesetid::ONES_CSID));
+    assert_eq!(entry.to_changeset_id, Some(changesetid::TWOS_CSID));
+    assert_eq!(entry.error, "path conflict");
+
+    quarantine.remove(&ctx, entry.id).await?;
+    assert!(quarantine.list(&ctx).await?.is_empty());
+
+    Ok(())
+}
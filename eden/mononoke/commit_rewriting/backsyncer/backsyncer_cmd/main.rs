@@ -339,6 +339,7 @@ where
                     CommitSyncContext::Backsyncer,
                     false,
                     commit_only_backsync_future,
+                    None,
                 )
                 .await?
             }
@@ -545,6 +546,7 @@ async fn run(
                 CommitSyncContext::Backsyncer,
                 false,
                 Box::new(future::ready(())),
+                None,
             )
             .boxed()
             .await?
@@ -23,6 +23,7 @@
 //! 3) In the same transaction try to update a bookmark in the source repo AND latest backsynced
 //!    log id.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::iter::once;
 use std::iter::repeat;
@@ -35,11 +36,13 @@ use std::time::Instant;
 use anyhow::bail;
 use anyhow::format_err;
 use anyhow::Error;
+use backsync_quarantine::BacksyncQuarantine;
 use blobstore::Loadable;
 use bonsai_git_mapping::BonsaiGitMapping;
 use bonsai_globalrev_mapping::BonsaiGlobalrevMapping;
 use bonsai_globalrev_mapping::BonsaiGlobalrevMappingEntry;
 use bonsai_hg_mapping::BonsaiHgMapping;
+use bookmarks::BookmarkKey;
 use bookmarks::BookmarkKind;
 use bookmarks::BookmarkTransactionError;
 use bookmarks::BookmarkUpdateLog;
@@ -75,6 +78,7 @@ use metaconfig_types::RepoConfigRef;
 use mononoke_types::ChangesetId;
 use mononoke_types::Globalrev;
 use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
 use mutable_counters::MutableCounters;
 use mutable_counters::MutableCountersArc;
 use mutable_counters::SqlMutableCounters;
@@ -132,10 +136,35 @@ mod tests;
 pub enum BacksyncError {
     #[error("BacksyncError::LogEntryNotFound: {latest_log_id} not found")]
     LogEntryNotFound { latest_log_id: u64 },
+    #[error(
+        "BacksyncError::Timeout: backsync of log id {log_id} did not complete within {waited:?}"
+    )]
+    Timeout {
+        log_id: BookmarkUpdateLogId,
+        waited: Duration,
+    },
     #[error("BacksyncError::Other")]
     Other(#[from] Error),
 }
 
+/// A snapshot of how far behind the backsyncer is for a given source repo,
+/// for use by clients and dashboards that want to know why an operation
+/// waiting on `ensure_backsynced` is taking a long time.
+#[derive(Debug, Clone)]
+pub struct BacksyncStatus {
+    /// The last log id that has been confirmed backsynced.
+    pub synced_log_id: BookmarkUpdateLogId,
+    /// The number of bookmark update log entries that are still waiting to
+    /// be backsynced.
+    pub queue_depth: u64,
+    /// The number of not-yet-backsynced log entries, broken down by the
+    /// bookmark they update.
+    pub lag_per_bookmark: HashMap<BookmarkKey, u64>,
+    /// When the backsyncer last timed out waiting for a bookmark to catch
+    /// up, if ever.
+    pub last_timeout_at: Option<Timestamp>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BacksyncLimit {
     NoLimit,
@@ -155,23 +184,29 @@ pub enum BacksyncLimit {
 ///
 /// We also use a hard-coded timeout to avoid being stuck forever waiting for the backsync if it is
 /// lagging. Not having this timeout has caused SEVs in the past, blocking lands.
+///
+/// `timeout_override` can be used to replace the default justknob-controlled timeout with a
+/// caller-chosen one, e.g. for callers that would rather fail fast than wait the default 60s.
 pub async fn ensure_backsynced<M, R>(
     ctx: CoreContext,
     commit_syncer: CommitSyncer<M, R>,
     target_repo_dbs: Arc<TargetRepoDbs>,
     log_id: BookmarkUpdateLogId,
-) -> Result<(), Error>
+    timeout_override: Option<Duration>,
+) -> Result<(), BacksyncError>
 where
     M: SyncedCommitMapping + Clone + 'static,
     R: RepoLike + Send + Sync + Clone + 'static,
 {
-    let timeout = Duration::from_secs(
-        justknobs::get_as::<u64>(
-            "scm/mononoke:defer_to_backsyncer_for_backsync_timeout_seconds",
-            None,
+    let timeout = timeout_override.unwrap_or_else(|| {
+        Duration::from_secs(
+            justknobs::get_as::<u64>(
+                "scm/mononoke:defer_to_backsyncer_for_backsync_timeout_seconds",
+                None,
+            )
+            .unwrap_or(60),
         )
-        .unwrap_or(60),
-    );
+    });
 
     let source_repo_id = commit_syncer.get_source_repo().repo_identity().id();
     let counter_name = format_counter(&source_repo_id);
@@ -198,7 +233,69 @@ where
                 .expect("sleep_times is an unbounded iterator"),
         )
     }
-    bail!("Timeout expired while waiting for backsyncing")
+
+    let waited = start_instant.elapsed();
+    let _ = target_repo_dbs
+        .counters
+        .set_counter(
+            &ctx,
+            &format_last_timeout_counter(&source_repo_id),
+            Timestamp::now().timestamp_seconds(),
+            None,
+        )
+        .await;
+    Err(BacksyncError::Timeout { log_id, waited })
+}
+
+/// Compute the current backsync queue depth and per-bookmark lag for a source repo, so that
+/// clients and dashboards can see why `ensure_backsynced` is taking a long time instead of just
+/// seeing it hang.
+pub async fn backsync_status<M, R>(
+    ctx: &CoreContext,
+    commit_syncer: &CommitSyncer<M, R>,
+    target_repo_dbs: &TargetRepoDbs,
+) -> Result<BacksyncStatus, Error>
+where
+    M: SyncedCommitMapping + Clone + 'static,
+    R: RepoLike + Send + Sync + Clone + 'static,
+{
+    let source_repo_id = commit_syncer.get_source_repo().repo_identity().id();
+    let counter_name = format_counter(&source_repo_id);
+
+    let synced_log_id: BookmarkUpdateLogId = target_repo_dbs
+        .counters
+        .get_counter(ctx, &counter_name)
+        .await?
+        .unwrap_or(0)
+        .try_into()?;
+
+    let pending_entries: Vec<_> = commit_syncer
+        .get_source_repo()
+        .bookmark_update_log()
+        .read_next_bookmark_log_entries(ctx.clone(), synced_log_id, u64::MAX, Freshness::MostRecent)
+        .boxed()
+        .try_collect()
+        .await?;
+
+    let mut lag_per_bookmark = HashMap::new();
+    for entry in &pending_entries {
+        *lag_per_bookmark
+            .entry(entry.bookmark_name.clone())
+            .or_insert(0u64) += 1;
+    }
+
+    let last_timeout_at = target_repo_dbs
+        .counters
+        .get_counter(ctx, &format_last_timeout_counter(&source_repo_id))
+        .await?
+        .map(Timestamp::from_timestamp_secs);
+
+    Ok(BacksyncStatus {
+        synced_log_id,
+        queue_depth: pending_entries.len().try_into()?,
+        lag_per_bookmark,
+        last_timeout_at,
+    })
 }
 
 pub async fn backsync_latest<M, R>(
@@ -210,6 +307,7 @@ pub async fn backsync_latest<M, R>(
     sync_context: CommitSyncContext,
     disable_lease: bool,
     commit_only_backsync_future: Box<dyn Future<Output = ()> + Send + Unpin>,
+    quarantine: Option<Arc<dyn BacksyncQuarantine>>,
 ) -> Result<Box<dyn Future<Output = ()> + Send + Unpin>, Error>
 where
     M: SyncedCommitMapping + Clone + 'static,
@@ -269,6 +367,7 @@ where
             sync_context,
             disable_lease,
             commit_only_backsync_future,
+            quarantine,
         )
         .boxed()
         .await
@@ -285,6 +384,7 @@ async fn sync_entries<M, R>(
     sync_context: CommitSyncContext,
     disable_lease: bool,
     mut commit_only_backsync_future: Box<dyn Future<Output = ()> + Send + Unpin>,
+    quarantine: Option<Arc<dyn BacksyncQuarantine>>,
 ) -> Result<Box<dyn Future<Output = ()> + Send + Unpin>, Error>
 where
     M: SyncedCommitMapping + Clone + 'static,
@@ -300,7 +400,11 @@ where
         let mut scuba_sample = ctx.scuba().clone();
         let pc = ctx.fork_perf_counters();
         let mut scuba_log_tag = "Backsyncing".to_string();
-        let (stats, new_commit_only_backsync_future) = do_sync_entry(
+        let entry_id = entry.id;
+        let bookmark_name = entry.bookmark_name.clone();
+        let from_changeset_id = entry.from_changeset_id;
+        let to_changeset_id = entry.to_changeset_id;
+        let result = do_sync_entry(
             ctx.clone(),
             commit_syncer,
             &target_repo_dbs,
@@ -313,7 +417,32 @@ where
             &mut scuba_log_tag,
         )
         .try_timed()
-        .await?;
+        .await;
+        let (stats, new_commit_only_backsync_future) = match (result, &quarantine) {
+            (Ok(result), _) => result,
+            (Err(err), Some(quarantine)) => {
+                warn!(
+                    ctx.logger(),
+                    "quarantining {} for bookmark {} after backsync failure: {}",
+                    entry_id,
+                    bookmark_name,
+                    err
+                );
+                quarantine
+                    .quarantine(
+                        &ctx,
+                        commit_syncer.get_source_repo().repo_identity().id(),
+                        entry_id.into(),
+                        &bookmark_name,
+                        from_changeset_id,
+                        to_changeset_id,
+                        &format!("{:#}", err),
+                    )
+                    .await?;
+                continue;
+            }
+            (Err(err), None) => return Err(err),
+        };
         commit_only_backsync_future = new_commit_only_backsync_future;
         pc.insert_perf_counters(&mut scuba_sample);
         scuba_sample
@@ -787,3 +916,7 @@ pub async fn open_backsyncer_dbs(repo: &impl RepoLike) -> Result<TargetRepoDbs,
 pub fn format_counter(repo_to_backsync_from: &RepositoryId) -> String {
     format!("backsync_from_{}", repo_to_backsync_from.id())
 }
+
+fn format_last_timeout_counter(repo_to_backsync_from: &RepositoryId) -> String {
+    format!("backsync_last_timeout_from_{}", repo_to_backsync_from.id())
+}
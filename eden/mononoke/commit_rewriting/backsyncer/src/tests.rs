@@ -184,6 +184,7 @@ fn test_sync_entries(fb: FacebookInit) -> Result<(), Error> {
             CommitSyncContext::Backsyncer,
             false,
             Box::new(future::ready(())),
+            None,
         )
         .map_err(Error::from)
         .await?;
@@ -213,6 +214,7 @@ fn test_sync_entries(fb: FacebookInit) -> Result<(), Error> {
             CommitSyncContext::Backsyncer,
             false,
             fut,
+            None,
         )
         .await?
         .await;
@@ -415,6 +417,7 @@ async fn backsync_two_small_repos(fb: FacebookInit) -> Result<(), Error> {
             CommitSyncContext::Backsyncer,
             false,
             Box::new(future::ready(())),
+            None,
         )
         .map_err(Error::from)
         .await?
@@ -649,6 +652,7 @@ async fn backsync_unrelated_branch(fb: FacebookInit) -> Result<(), Error> {
         CommitSyncContext::Backsyncer,
         false,
         Box::new(future::ready(())),
+        None,
     )
     .await?;
 
@@ -680,6 +684,7 @@ async fn backsync_unrelated_branch(fb: FacebookInit) -> Result<(), Error> {
         CommitSyncContext::Backsyncer,
         false,
         fut,
+        None,
     )
     .await?
     .await;
@@ -829,6 +834,7 @@ async fn backsync_change_mapping(fb: FacebookInit) -> Result<(), Error> {
         CommitSyncContext::Backsyncer,
         false,
         Box::new(future::ready(())),
+        None,
     );
     with_just_knobs_async(jk, f.boxed()).await?.await;
 
@@ -949,6 +955,7 @@ async fn backsync_and_verify_master_wc(
             CommitSyncContext::Backsyncer,
             false,
             Box::new(future::ready(())),
+            None,
         ))
         .flatten_err();
         futs.push(f);
@@ -1223,6 +1230,8 @@ impl BookmarkRenamerType {
                     small_repo_id => SmallRepoPermanentConfig {
                         bookmark_prefix: AsciiString::from_str(bookmark_prefix).unwrap(),
                         common_pushrebase_bookmarks_map: HashMap::new(),
+                        bookmark_redirection_namespaces: Vec::new(),
+                        pushvar_passthrough_policy: Default::default(),
                     }
                 },
                 large_repo_id,
@@ -1233,6 +1242,8 @@ impl BookmarkRenamerType {
                     small_repo_id => SmallRepoPermanentConfig {
                         bookmark_prefix: AsciiString::from_str("nonexistentprefix").unwrap(),
                         common_pushrebase_bookmarks_map: HashMap::new(),
+                        bookmark_redirection_namespaces: Vec::new(),
+                        pushvar_passthrough_policy: Default::default(),
                     }
                 },
                 large_repo_id,
@@ -1243,6 +1254,8 @@ impl BookmarkRenamerType {
                     small_repo_id => SmallRepoPermanentConfig {
                         bookmark_prefix: AsciiString::from_str("nonexistentprefix").unwrap(),
                         common_pushrebase_bookmarks_map: HashMap::new(),
+                        bookmark_redirection_namespaces: Vec::new(),
+                        pushvar_passthrough_policy: Default::default(),
                     }
                 },
                 large_repo_id,
@@ -1253,6 +1266,8 @@ impl BookmarkRenamerType {
                     small_repo_id => SmallRepoPermanentConfig {
                         bookmark_prefix: AsciiString::new(),
                         common_pushrebase_bookmarks_map: HashMap::new(),
+                        bookmark_redirection_namespaces: Vec::new(),
+                        pushvar_passthrough_policy: Default::default(),
                     }
                 },
                 large_repo_id,
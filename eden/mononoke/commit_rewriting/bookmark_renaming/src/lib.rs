@@ -170,14 +170,20 @@ mod test {
                 RepositoryId::new(1) => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::from_str("b1/").unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 },
                 RepositoryId::new(2) => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::from_str("b2/").unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 },
                 RepositoryId::new(3) => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::from_str("b3/").unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::from([(m1, heads_m1), (m2.clone(), m2)]),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 },
             },
             large_repo_id: RepositoryId::new(0),
@@ -70,8 +70,10 @@ pub use types::Small;
 pub use types::Source;
 pub use types::SubmoduleDeps;
 pub use types::Target;
+pub use validation::check_bookmark_consistency;
 pub use validation::find_bookmark_diff;
 pub use validation::report_different;
 pub use validation::verify_working_copy;
 pub use validation::verify_working_copy_with_version;
 pub use validation::BookmarkDiff;
+pub use validation::BookmarkDivergence;
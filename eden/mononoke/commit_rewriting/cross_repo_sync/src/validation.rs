@@ -49,6 +49,7 @@ use slog::error;
 use slog::info;
 use sorted_vector_map::SortedVectorMap;
 use synced_commit_mapping::SyncedCommitMapping;
+use synced_commit_mapping::SyncedCommitSourceRepo;
 
 use crate::commit_syncer::CommitSyncer;
 use crate::get_git_submodule_action_by_version;
@@ -1246,6 +1247,56 @@ pub async fn find_bookmark_diff<M: SyncedCommitMapping + Clone + 'static, R: Rep
     Ok(diff)
 }
 
+/// A [`BookmarkDiff`] together with the sync direction whose backlog is the
+/// likely cause of it, so that callers (chron jobs, admin tooling,
+/// dashboards) don't have to re-derive which side needs to catch up.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BookmarkDivergence {
+    pub diff: BookmarkDiff,
+    pub responsible_direction: CommitSyncDirection,
+}
+
+/// Compare bookmark positions between the two repos behind `commit_syncer`
+/// and, for each divergent bookmark found by [`find_bookmark_diff`], work out
+/// which sync direction is responsible for catching it up:
+///
+/// * a bookmark missing in the target repo (or with no sync outcome at all)
+///   means the sync job running in `commit_syncer`'s own direction (source to
+///   target) hasn't processed it yet;
+/// * a bookmark present in both but pointing at different commits means the
+///   target repo moved it independently of the source, so the opposite
+///   direction (target to source) is the one that owes a sync.
+pub async fn check_bookmark_consistency<M: SyncedCommitMapping + Clone + 'static, R: Repo>(
+    ctx: CoreContext,
+    commit_syncer: &CommitSyncer<M, R>,
+) -> Result<Vec<BookmarkDivergence>, Error> {
+    let direction = match commit_syncer.get_source_repo_type() {
+        SyncedCommitSourceRepo::Large => CommitSyncDirection::LargeToSmall,
+        SyncedCommitSourceRepo::Small => CommitSyncDirection::SmallToLarge,
+    };
+    let reverse_direction = match direction {
+        CommitSyncDirection::LargeToSmall => CommitSyncDirection::SmallToLarge,
+        CommitSyncDirection::SmallToLarge => CommitSyncDirection::LargeToSmall,
+    };
+
+    let diff = find_bookmark_diff(ctx, commit_syncer).await?;
+    Ok(diff
+        .into_iter()
+        .map(|diff| {
+            let responsible_direction = match diff {
+                BookmarkDiff::MissingInTarget { .. } | BookmarkDiff::NoSyncOutcome { .. } => {
+                    direction
+                }
+                BookmarkDiff::InconsistentValue { .. } => reverse_direction,
+            };
+            BookmarkDivergence {
+                diff,
+                responsible_direction,
+            }
+        })
+        .collect())
+}
+
 /// Given a list of differences of a given type (`T`)
 /// report them in the logs and return an appropriate result
 pub fn report_different<
@@ -1790,6 +1841,8 @@ mod test {
                 small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::from_str("prefix/").unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 }
             },
             large_repo_id: large_repo.repo_identity().id(),
@@ -421,6 +421,8 @@ pub(crate) fn create_small_repo_to_large_repo_commit_syncer(
             small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id: large_repo.repo_identity().id(),
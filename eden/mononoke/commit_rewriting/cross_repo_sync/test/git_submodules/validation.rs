@@ -11,10 +11,10 @@
 
 use anyhow::Result;
 use context::CoreContext;
-use cross_repo_sync::verify_working_copy;
-use cross_repo_sync::verify_working_copy_with_version;
 use cross_repo_sync::Source;
 use cross_repo_sync::Target;
+use cross_repo_sync::verify_working_copy;
+use cross_repo_sync::verify_working_copy_with_version;
 use fbinit::FacebookInit;
 use mononoke_types::NonRootMPath;
 use tests_utils::CreateCommitContext;
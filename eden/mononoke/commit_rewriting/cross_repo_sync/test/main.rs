@@ -309,6 +309,8 @@ fn populate_config(
             small_repo_id => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id: large_repo.repo_identity().id(),
@@ -775,6 +777,8 @@ async fn test_sync_implicit_deletes(fb: FacebookInit) -> Result<(), Error> {
             small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id: megarepo.repo_identity().id(),
@@ -1746,6 +1750,8 @@ async fn prepare_commit_syncer_with_mapping_change(
             small_repo.repo_identity().id() => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id,
@@ -1836,6 +1842,8 @@ fn get_merge_sync_live_commit_sync_config(
             small_repo_id => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id,
@@ -2168,6 +2176,8 @@ async fn test_no_accidental_preserved_roots(
                 commit_syncer.get_small_repo().repo_identity().id() => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::new(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 }
             },
             large_repo_id: commit_syncer.get_large_repo().repo_identity().id(),
@@ -2257,10 +2267,14 @@ async fn test_not_sync_candidate_if_mapping_does_not_have_small_repo(
             first_small_repo_id => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             },
             second_small_repo_id => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             },
         },
         large_repo_id,
@@ -326,6 +326,8 @@ where
             RepositoryId::new(0) => SmallRepoPermanentConfig {
                 bookmark_prefix: AsciiString::new(),
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id: RepositoryId::new(1),
@@ -525,6 +527,8 @@ pub fn get_live_commit_sync_config() -> Arc<dyn LiveCommitSyncConfig> {
             RepositoryId::new(1) => SmallRepoPermanentConfig {
                 bookmark_prefix,
                 common_pushrebase_bookmarks_map: HashMap::new(),
+                bookmark_redirection_namespaces: Vec::new(),
+                pushvar_passthrough_policy: Default::default(),
             }
         },
         large_repo_id: RepositoryId::new(0),
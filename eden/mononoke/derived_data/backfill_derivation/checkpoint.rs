@@ -0,0 +1,108 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
+use sql_construct::SqlConstruct;
+use sql_construct::SqlConstructFromMetadataDatabaseConfig;
+use sql_ext::mononoke_queries;
+use sql_ext::SqlConnections;
+
+/// How far a backfill of one derived data type has progressed through the commit range it was
+/// given, so an interrupted run can resume rather than re-derive from scratch.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BackfillCheckpoint {
+    /// Index into the caller's ordered changeset list of the first changeset not yet derived.
+    pub next_index: u64,
+    pub update_timestamp: Timestamp,
+}
+
+/// SQL-backed store for [`BackfillCheckpoint`]s, keyed by `(repo_id, derived_data_type)`.
+pub struct BackfillCheckpoints {
+    connections: SqlConnections,
+}
+
+impl SqlConstruct for BackfillCheckpoints {
+    const LABEL: &'static str = "backfill_derivation_checkpoints";
+
+    const CREATION_QUERY: &'static str =
+        include_str!("schemas/sqlite-backfill_derivation_checkpoints.sql");
+
+    fn from_sql_connections(connections: SqlConnections) -> Self {
+        Self { connections }
+    }
+}
+
+impl SqlConstructFromMetadataDatabaseConfig for BackfillCheckpoints {}
+
+impl BackfillCheckpoints {
+    pub async fn load(
+        &self,
+        repo_id: RepositoryId,
+        derived_data_type: &str,
+    ) -> Result<Option<BackfillCheckpoint>> {
+        let rows = SelectBackfillCheckpoint::query(
+            &self.connections.read_master_connection,
+            &repo_id,
+            &derived_data_type,
+        )
+        .await?;
+        Ok(rows
+            .into_iter()
+            .next()
+            .map(|(next_index, update_timestamp)| BackfillCheckpoint {
+                next_index,
+                update_timestamp,
+            }))
+    }
+
+    /// Persist that derivation has progressed up to (but not including) `next_index`.
+    pub async fn persist(
+        &self,
+        repo_id: RepositoryId,
+        derived_data_type: &str,
+        next_index: u64,
+    ) -> Result<()> {
+        ReplaceBackfillCheckpoint::query(
+            &self.connections.write_connection,
+            &[(
+                &repo_id,
+                &derived_data_type,
+                &next_index,
+                &Timestamp::now(),
+            )],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+mononoke_queries! {
+    read SelectBackfillCheckpoint(
+        repo_id: RepositoryId,
+        derived_data_type: &str,
+    ) -> (u64, Timestamp) {
+        "SELECT next_index, update_timestamp
+         FROM backfill_derivation_checkpoints
+         WHERE repo_id={repo_id} AND derived_data_type={derived_data_type}"
+    }
+
+    write ReplaceBackfillCheckpoint(
+        values: (
+            repo_id: RepositoryId,
+            derived_data_type: String,
+            next_index: u64,
+            update_timestamp: Timestamp,
+        ),
+    ) {
+        none,
+        "REPLACE INTO backfill_derivation_checkpoints
+         (repo_id, derived_data_type, next_index, update_timestamp)
+         VALUES {values}"
+    }
+}
@@ -0,0 +1,166 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::time::Duration;
+
+use anyhow::Result;
+use bulk_derivation::BulkDerivation;
+use context::CoreContext;
+use derived_data_manager::DerivableType;
+use derived_data_manager::DerivedDataManager;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryFutureExt;
+use futures::TryStreamExt;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use retry::retry_always;
+use slog::info;
+use slog::Logger;
+
+use crate::checkpoint::BackfillCheckpoints;
+
+/// Configuration controlling how a [`BackfillDriver`] divides up and paces a backfill.
+#[derive(Clone, Debug)]
+pub struct BackfillConfig {
+    /// Number of changesets derived per batch.
+    pub batch_size: usize,
+    /// Number of batches allowed to be in flight (and hence derived) concurrently.
+    pub concurrency: usize,
+    /// Maximum number of times a single batch is retried before the backfill gives up.
+    pub max_retries: usize,
+    /// Optional cap on the rate of changeset derivation; if set, the driver sleeps between
+    /// batches as needed to stay under this rate.
+    pub max_changesets_per_second: Option<u64>,
+}
+
+impl Default for BackfillConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            concurrency: 4,
+            max_retries: 5,
+            max_changesets_per_second: None,
+        }
+    }
+}
+
+/// Drives derivation of a single derived data type over an explicit, topologically sorted
+/// commit range with sharded (concurrent) workers, persistent checkpoints, automatic retry of
+/// failed batches, and throughput throttling, replacing the ad-hoc one-off scripts operators
+/// have historically used to backfill a newly added derived data type across existing history.
+///
+/// Progress is checkpointed in SQL after every batch, keyed by `(repo_id, derived_data_type)`,
+/// so a backfill interrupted partway through (deploy, OOM, operator Ctrl-C) resumes from where
+/// it left off on the next run instead of re-deriving from scratch.
+pub struct BackfillDriver {
+    manager: DerivedDataManager,
+    derived_data_type: DerivableType,
+    repo_id: RepositoryId,
+    checkpoints: BackfillCheckpoints,
+    config: BackfillConfig,
+    logger: Logger,
+}
+
+impl BackfillDriver {
+    pub fn new(
+        manager: DerivedDataManager,
+        derived_data_type: DerivableType,
+        repo_id: RepositoryId,
+        checkpoints: BackfillCheckpoints,
+        config: BackfillConfig,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            manager,
+            derived_data_type,
+            repo_id,
+            checkpoints,
+            config,
+            logger,
+        }
+    }
+
+    /// Derive `self.derived_data_type` for `csids`, which must be in topological order (oldest
+    /// first, i.e. parents before children). Resumes from the last checkpointed index, if any,
+    /// for this repo and derived data type.
+    pub async fn run(&self, ctx: &CoreContext, csids: &[ChangesetId]) -> Result<()> {
+        let start_index = self
+            .checkpoints
+            .load(self.repo_id, self.derived_data_type.as_ref())
+            .await?
+            .map_or(0, |checkpoint| checkpoint.next_index as usize);
+
+        if start_index >= csids.len() {
+            info!(
+                self.logger,
+                "{}: backfill already complete for all {} changesets",
+                self.derived_data_type,
+                csids.len(),
+            );
+            return Ok(());
+        }
+
+        info!(
+            self.logger,
+            "{}: backfilling changesets {}..{} (of {})",
+            self.derived_data_type,
+            start_index,
+            csids.len(),
+            csids.len(),
+        );
+
+        let batch_size = self.config.batch_size.max(1);
+        let batches: Vec<(usize, &[ChangesetId])> = csids[start_index..]
+            .chunks(batch_size)
+            .enumerate()
+            .map(|(i, batch)| (start_index + i * batch_size, batch))
+            .collect();
+
+        // `buffered` runs up to `concurrency` batches concurrently but yields their results in
+        // input order, so checkpoints below can be persisted monotonically from `try_for_each`
+        // even though the batches that produced them may have finished out of order.
+        stream::iter(batches)
+            .map(|(offset, batch)| {
+                self.derive_batch_with_retry(ctx, batch)
+                    .map_ok(move |()| (offset + batch.len(), batch.len()))
+            })
+            .buffered(self.config.concurrency.max(1))
+            .try_for_each(|(next_index, batch_len)| async move {
+                self.checkpoints
+                    .persist(self.repo_id, self.derived_data_type.as_ref(), next_index as u64)
+                    .await?;
+                self.throttle(batch_len).await;
+                Ok(())
+            })
+            .await
+    }
+
+    async fn derive_batch_with_retry(&self, ctx: &CoreContext, batch: &[ChangesetId]) -> Result<()> {
+        retry_always(
+            &self.logger,
+            |_attempt| {
+                self.manager
+                    .derive_exactly_underived_batch(ctx, batch, None, self.derived_data_type)
+            },
+            100,
+            // `retry_always` counts attempts, including the first one.
+            self.config.max_retries.saturating_add(1),
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn throttle(&self, derived_in_batch: usize) {
+        if let Some(max_per_second) = self.config.max_changesets_per_second {
+            if max_per_second > 0 {
+                let seconds = derived_in_batch as f64 / max_per_second as f64;
+                tokio::time::sleep(Duration::from_secs_f64(seconds)).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,18 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Library-level backfill driver for deriving a derived data type across a commit range,
+//! replacing the ad-hoc scripts operators have historically used when rolling out a new
+//! derived data type.
+
+mod checkpoint;
+mod driver;
+
+pub use crate::checkpoint::BackfillCheckpoint;
+pub use crate::checkpoint::BackfillCheckpoints;
+pub use crate::driver::BackfillConfig;
+pub use crate::driver::BackfillDriver;
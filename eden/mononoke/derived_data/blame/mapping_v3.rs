@@ -0,0 +1,214 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use async_trait::async_trait;
+use blobstore::Blobstore;
+use context::CoreContext;
+use derived_data_manager::dependencies;
+use derived_data_manager::BonsaiDerivable;
+use derived_data_manager::DerivableType;
+use derived_data_manager::DerivationContext;
+use derived_data_service_if as thrift;
+use fbthrift::compact_protocol;
+use mononoke_types::BlobstoreBytes;
+use mononoke_types::BonsaiChangeset;
+use mononoke_types::ChangesetId;
+use mononoke_types::ManifestUnodeId;
+use unodes::RootUnodeManifestId;
+
+use crate::RootBlameV2;
+
+/// Blame v3 root mapping.
+///
+/// This wraps the same per-commit root manifest pointer as [`RootBlameV2`] (the per-line,
+/// move-aware blame data itself is unchanged and is still read off `RootBlameV2`/`BlameV2`),
+/// and adds a binary-lifted skip list along the first-parent chain: entry `i` is the ancestor
+/// 2^i commits back. This lets a caller resolve "blame as of N generations back" by jumping
+/// through O(log N) ancestors instead of walking the linear history one commit at a time, which
+/// is the dominant cost for files with tens of thousands of revisions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootBlameV3 {
+    pub(crate) csid: ChangesetId,
+    pub(crate) root_manifest: RootUnodeManifestId,
+    pub(crate) skip_list: Vec<ChangesetId>,
+}
+
+impl RootBlameV3 {
+    pub fn root_manifest(&self) -> RootUnodeManifestId {
+        self.root_manifest
+    }
+
+    pub fn changeset_id(&self) -> ChangesetId {
+        self.csid
+    }
+
+    /// The binary-lifted skip list: entry `i` is the ancestor 2^i commits back along the
+    /// first-parent chain.
+    pub fn skip_list(&self) -> &[ChangesetId] {
+        &self.skip_list
+    }
+
+    pub(crate) fn from_thrift(blame: thrift::DerivedDataRootBlameV3) -> Result<Self> {
+        Ok(Self {
+            csid: ChangesetId::from_thrift(blame.changeset_id)?,
+            root_manifest: match blame.unode {
+                thrift::DerivedDataUnode::root_unode_manifest_id(id) => {
+                    ManifestUnodeId::from_thrift(id).map(RootUnodeManifestId)
+                }
+                thrift::DerivedDataUnode::UnknownField(x) => Err(anyhow!(
+                    "Can't convert RootBlameV3 from provided thrift::DerivedDataRootBlameV3, unknown field: {}",
+                    x,
+                )),
+            }?,
+            skip_list: blame
+                .skip_list
+                .into_iter()
+                .map(ChangesetId::from_thrift)
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+
+    pub(crate) fn into_thrift(self) -> thrift::DerivedDataRootBlameV3 {
+        thrift::DerivedDataRootBlameV3 {
+            changeset_id: self.csid.into_thrift(),
+            unode: thrift::DerivedDataUnode::root_unode_manifest_id(
+                self.root_manifest().manifest_unode_id().into_thrift(),
+            ),
+            skip_list: self
+                .skip_list
+                .into_iter()
+                .map(|id| id.into_thrift())
+                .collect(),
+        }
+    }
+}
+
+pub fn format_key(derivation_ctx: &DerivationContext, changeset_id: ChangesetId) -> String {
+    let root_prefix = "derived_root_blame_v3.";
+    let key_prefix = derivation_ctx.mapping_key_prefix::<RootBlameV3>();
+    format!("{}{}{}", root_prefix, key_prefix, changeset_id)
+}
+
+/// Build the skip list for `csid` given its first parent's already-derived `RootBlameV3`.
+///
+/// `skip[0]` is the first parent itself. For `k > 0`, `skip[k]` is the ancestor reached by
+/// following `skip[k - 1]` twice, i.e. `up[k][v] = up[k-1][up[k-1][v]]` in the classic binary
+/// lifting formulation. Since derivation always proceeds in topological (parent-before-child)
+/// order, every ancestor's `RootBlameV3` we need here is guaranteed to already be derived.
+async fn build_skip_list(
+    ctx: &CoreContext,
+    derivation_ctx: &DerivationContext,
+    first_parent: &RootBlameV3,
+) -> Result<Vec<ChangesetId>> {
+    let mut skip_list = vec![first_parent.csid];
+    loop {
+        let level = skip_list.len() - 1;
+        let candidate = *skip_list.last().expect("skip_list is never empty");
+        let candidate_blame = if candidate == first_parent.csid {
+            first_parent.clone()
+        } else {
+            derivation_ctx
+                .fetch_derived::<RootBlameV3>(ctx, candidate)
+                .await?
+                .ok_or_else(|| anyhow!("RootBlameV3 for ancestor {} not yet derived", candidate))?
+        };
+        match candidate_blame.skip_list.get(level) {
+            Some(next) => skip_list.push(*next),
+            None => break,
+        }
+    }
+    Ok(skip_list)
+}
+
+#[async_trait]
+impl BonsaiDerivable for RootBlameV3 {
+    const VARIANT: DerivableType = DerivableType::BlameV3;
+
+    type Dependencies = dependencies![RootBlameV2];
+    type PredecessorDependencies = dependencies![];
+
+    async fn derive_single(
+        ctx: &CoreContext,
+        derivation_ctx: &DerivationContext,
+        bonsai: BonsaiChangeset,
+        parents: Vec<Self>,
+    ) -> Result<Self, Error> {
+        let csid = bonsai.get_changeset_id();
+        let root_blame_v2 = derivation_ctx
+            .fetch_dependency::<RootBlameV2>(ctx, csid)
+            .await?;
+        let skip_list = match parents.first() {
+            Some(first_parent) => build_skip_list(ctx, derivation_ctx, first_parent).await?,
+            None => Vec::new(),
+        };
+        Ok(RootBlameV3 {
+            csid,
+            root_manifest: root_blame_v2.root_manifest(),
+            skip_list,
+        })
+    }
+
+    async fn store_mapping(
+        self,
+        ctx: &CoreContext,
+        derivation_ctx: &DerivationContext,
+        changeset_id: ChangesetId,
+    ) -> Result<()> {
+        let key = format_key(derivation_ctx, changeset_id);
+        derivation_ctx.blobstore().put(ctx, key, self.into()).await
+    }
+
+    async fn fetch(
+        ctx: &CoreContext,
+        derivation_ctx: &DerivationContext,
+        changeset_id: ChangesetId,
+    ) -> Result<Option<Self>> {
+        let key = format_key(derivation_ctx, changeset_id);
+        Ok(derivation_ctx
+            .blobstore()
+            .get(ctx, &key)
+            .await?
+            .map(TryInto::try_into)
+            .transpose()?)
+    }
+
+    fn from_thrift(data: thrift::DerivedData) -> Result<Self> {
+        if let thrift::DerivedData::blame(thrift::DerivedDataBlame::root_blame_v3(blame)) = data {
+            Self::from_thrift(blame)
+        } else {
+            Err(anyhow!(
+                "Can't convert {} from provided thrift::DerivedData",
+                Self::NAME.to_string(),
+            ))
+        }
+    }
+
+    fn into_thrift(data: Self) -> Result<thrift::DerivedData> {
+        Ok(thrift::DerivedData::blame(
+            thrift::DerivedDataBlame::root_blame_v3(data.into_thrift()),
+        ))
+    }
+}
+
+impl TryFrom<BlobstoreBytes> for RootBlameV3 {
+    type Error = Error;
+
+    fn try_from(blob_bytes: BlobstoreBytes) -> Result<Self> {
+        let thrift_tc = compact_protocol::deserialize(blob_bytes.into_bytes())?;
+        Self::from_thrift(thrift_tc)
+    }
+}
+
+impl From<RootBlameV3> for BlobstoreBytes {
+    fn from(blame: RootBlameV3) -> BlobstoreBytes {
+        let data = compact_protocol::serialize(&blame.into_thrift());
+        BlobstoreBytes::from_bytes(data)
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::HashSet;
+
+use anyhow::bail;
+use anyhow::Result;
+use derived_data_manager::DerivableType;
+
+/// Expands `requested` to include all of its transitive dependencies (via
+/// [`DerivableType::dependencies`]) and returns the full set ordered so that every type appears
+/// after all the types it depends on.
+///
+/// This is the runtime counterpart to the compile-time dependency resolution that
+/// `Derivable::Dependencies` already performs for a single type: it lets a scheduler that only
+/// knows which types to derive at runtime (e.g. from config or a CLI arg) plan a single combined
+/// pass over `requested` instead of re-deriving each type's dependency subtree independently.
+pub fn dependency_order(requested: &[DerivableType]) -> Result<Vec<DerivableType>> {
+    let mut order = Vec::new();
+    let mut done = HashSet::new();
+    let mut in_progress = Vec::new();
+    for derived_data_type in requested {
+        visit(*derived_data_type, &mut done, &mut order, &mut in_progress)?;
+    }
+    Ok(order)
+}
+
+fn visit(
+    derived_data_type: DerivableType,
+    done: &mut HashSet<DerivableType>,
+    order: &mut Vec<DerivableType>,
+    in_progress: &mut Vec<DerivableType>,
+) -> Result<()> {
+    if done.contains(&derived_data_type) {
+        return Ok(());
+    }
+    if in_progress.contains(&derived_data_type) {
+        bail!(
+            "cycle detected in derived data dependency graph: {} depends on itself transitively via {:?}",
+            derived_data_type,
+            in_progress,
+        );
+    }
+    in_progress.push(derived_data_type);
+    for dependency in derived_data_type.dependencies() {
+        visit(*dependency, done, order, in_progress)?;
+    }
+    in_progress.pop();
+    done.insert(derived_data_type);
+    order.push(derived_data_type);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use strum::IntoEnumIterator;
+
+    use super::*;
+
+    #[test]
+    fn single_type_with_no_dependencies() {
+        assert_eq!(
+            dependency_order(&[DerivableType::Unodes]).unwrap(),
+            vec![DerivableType::Unodes],
+        );
+    }
+
+    #[test]
+    fn dependency_comes_before_dependent() {
+        let order = dependency_order(&[DerivableType::BlameV2]).unwrap();
+        let unodes_pos = order.iter().position(|t| *t == DerivableType::Unodes);
+        let blame_pos = order.iter().position(|t| *t == DerivableType::BlameV2);
+        assert!(unodes_pos.is_some() && blame_pos.is_some());
+        assert!(unodes_pos < blame_pos);
+    }
+
+    #[test]
+    fn shared_dependency_is_not_duplicated() {
+        let order =
+            dependency_order(&[DerivableType::GitCommits, DerivableType::GitDeltaManifestsV2])
+                .unwrap();
+        assert_eq!(
+            order.iter().filter(|t| **t == DerivableType::GitTrees).count(),
+            1,
+        );
+    }
+
+    #[test]
+    fn all_types_produce_an_order_without_cycles() {
+        let all: Vec<DerivableType> = DerivableType::iter().collect();
+        let order = dependency_order(&all).unwrap();
+        assert_eq!(order.len(), all.len());
+    }
+}
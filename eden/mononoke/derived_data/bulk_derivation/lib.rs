@@ -5,13 +5,17 @@
  * GNU General Public License version 2.
  */
 
+use std::collections::HashSet;
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use async_trait::async_trait;
 use basename_suffix_skeleton_manifest_v3::RootBssmV3DirectoryId;
 use blame::RootBlameV2;
+use blame::RootBlameV3;
 use changeset_info::ChangesetInfo;
+use churn::DirectoryChurnInfo;
 use cloned::cloned;
 use context::CoreContext;
 use deleted_manifest::RootDeletedManifestV2Id;
@@ -39,6 +43,10 @@ use test_manifest::RootTestManifestDirectory;
 use test_sharded_manifest::RootTestShardedManifestDirectory;
 use unodes::RootUnodeManifestId;
 
+mod dependency_graph;
+
+pub use crate::dependency_graph::dependency_order;
+
 #[async_trait]
 pub trait BulkDerivation {
     /// Derive all the given derived data types for all the given changeset ids.
@@ -51,6 +59,24 @@ pub trait BulkDerivation {
         override_batch_size: Option<u64>,
     ) -> Result<(), SharedDerivationError>;
 
+    /// Derive all the given derived data types for all the given changeset ids, expanding
+    /// `derived_data_types` to include their transitive dependencies and deriving dependencies
+    /// strictly before the types that depend on them.
+    ///
+    /// Unlike [`BulkDerivation::derive_bulk`], which kicks off all requested types concurrently
+    /// and relies on each type's own ancestor walk to derive what it depends on as a side effect,
+    /// this walks the requested types' dependency graph up front and derives them one dependency
+    /// layer at a time, so a shared type that several requested types depend on (e.g. unodes for
+    /// both blame and fastlog) is only ever walked and derived once for the whole batch.
+    async fn derive_bulk_in_dependency_order(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        rederivation: Option<Arc<dyn Rederivation>>,
+        derived_data_types: &[DerivableType],
+        override_batch_size: Option<u64>,
+    ) -> Result<(), SharedDerivationError>;
+
     /// Derive data for exactly a batch of changesets.
     ///
     /// The provided batch of changesets must be in topological
@@ -120,6 +146,35 @@ pub trait BulkDerivation {
         rederivation: Option<Arc<dyn Rederivation>>,
         derived_data_type: DerivableType,
     ) -> Result<(), DerivationError>;
+
+    /// Re-derive a random sample (in `[0.0, 1.0]` of `csids`, selected independently per
+    /// changeset) of `csids` for `derived_data_type` and compare the result against what's
+    /// currently stored, to catch derivation nondeterminism (e.g. an unstable sort, or a
+    /// dependency on iteration order) before it causes silent divergence between two hosts
+    /// that derived the same commit at different times.
+    ///
+    /// Note that re-deriving a changeset forces it through the normal derive path, so a detected
+    /// mismatch is also repaired as a side effect of this pass: the newly re-derived value
+    /// replaces the stored one, exactly as it would for any other forced rederivation.
+    async fn verify_sample(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        derived_data_type: DerivableType,
+        sample_rate: f64,
+    ) -> Result<Vec<VerificationMismatch>, DerivationError>;
+}
+
+/// A discrepancy between previously stored derived data and the result of re-deriving it from
+/// scratch for the same changeset, as found by [`BulkDerivation::verify_sample`].
+#[derive(Debug, Clone)]
+pub struct VerificationMismatch {
+    pub csid: ChangesetId,
+    pub derived_data_type: DerivableType,
+    /// Debug representation of the value that was stored before re-derivation.
+    pub stored: String,
+    /// Debug representation of the value produced by re-deriving from scratch.
+    pub rederived: String,
 }
 
 struct SingleTypeManager<T: BonsaiDerivable> {
@@ -189,10 +244,17 @@ trait SingleTypeDerivation: Send + Sync {
         csid: ChangesetId,
         rederivation: Option<Arc<dyn Rederivation>>,
     ) -> Result<(), DerivationError>;
+
+    async fn verify_sample(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        sample_rate: f64,
+    ) -> Result<Vec<VerificationMismatch>, DerivationError>;
 }
 
 #[async_trait]
-impl<T: BonsaiDerivable> SingleTypeDerivation for SingleTypeManager<T> {
+impl<T: BonsaiDerivable + PartialEq> SingleTypeDerivation for SingleTypeManager<T> {
     async fn derive_heads_with_visited<'a>(
         &self,
         ctx: &'a CoreContext,
@@ -285,6 +347,54 @@ impl<T: BonsaiDerivable> SingleTypeDerivation for SingleTypeManager<T> {
             .await?;
         Ok(())
     }
+
+    async fn verify_sample(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        sample_rate: f64,
+    ) -> Result<Vec<VerificationMismatch>, DerivationError> {
+        let sampled: Vec<ChangesetId> = csids
+            .iter()
+            .copied()
+            .filter(|_| rand::random::<f64>() < sample_rate)
+            .collect();
+        if sampled.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let stored = self
+            .manager
+            .fetch_derived_batch::<T>(ctx, sampled.clone(), None)
+            .await?;
+
+        // Forces every sampled changeset through the normal derive path instead of being
+        // satisfied by the value we just fetched above.
+        let force_rederive: Arc<dyn Rederivation> =
+            Arc::new(Mutex::new(sampled.iter().copied().collect::<HashSet<_>>()));
+
+        let mut mismatches = Vec::new();
+        for csid in sampled {
+            let Some(stored_value) = stored.get(&csid) else {
+                // Nothing stored yet for this changeset; there's nothing to compare against.
+                continue;
+            };
+            let rederived = self
+                .manager
+                .derive::<T>(ctx, csid, Some(force_rederive.clone()))
+                .await
+                .map_err(|e| DerivationError::Error(anyhow::anyhow!(e.to_string())))?;
+            if *stored_value != rederived {
+                mismatches.push(VerificationMismatch {
+                    csid,
+                    derived_data_type: T::VARIANT,
+                    stored: format!("{:?}", stored_value),
+                    rederived: format!("{:?}", rederived),
+                });
+            }
+        }
+        Ok(mismatches)
+    }
 }
 
 fn manager_for_type(
@@ -295,6 +405,7 @@ fn manager_for_type(
     match derived_data_type {
         DerivableType::Unodes => Arc::new(SingleTypeManager::<RootUnodeManifestId>::new(manager)),
         DerivableType::BlameV2 => Arc::new(SingleTypeManager::<RootBlameV2>::new(manager)),
+        DerivableType::BlameV3 => Arc::new(SingleTypeManager::<RootBlameV3>::new(manager)),
         DerivableType::FileNodes => {
             Arc::new(SingleTypeManager::<FilenodesOnlyPublic>::new(manager))
         }
@@ -325,6 +436,9 @@ fn manager_for_type(
         DerivableType::TestShardedManifests => Arc::new(SingleTypeManager::<
             RootTestShardedManifestDirectory,
         >::new(manager)),
+        DerivableType::DirectoryChurn => {
+            Arc::new(SingleTypeManager::<DirectoryChurnInfo>::new(manager))
+        }
     }
 }
 
@@ -365,6 +479,31 @@ impl BulkDerivation for DerivedDataManager {
         Ok(())
     }
 
+    async fn derive_bulk_in_dependency_order(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        rederivation: Option<Arc<dyn Rederivation>>,
+        derived_data_types: &[DerivableType],
+        override_batch_size: Option<u64>,
+    ) -> Result<(), SharedDerivationError> {
+        let order = dependency_order(derived_data_types)
+            .map_err(|e| SharedDerivationError::from(DerivationError::from(e)))?;
+        let visited = VisitedDerivableTypesMap::default();
+        for derived_data_type in order {
+            manager_for_type(self, derived_data_type)
+                .derive_heads_with_visited(
+                    ctx,
+                    csids,
+                    override_batch_size,
+                    rederivation.clone(),
+                    visited.clone(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
     async fn derive_exactly_batch(
         &self,
         ctx: &CoreContext,
@@ -437,4 +576,15 @@ impl BulkDerivation for DerivedDataManager {
             .derive_from_predecessor(ctx, csid, rederivation)
             .await
     }
+
+    async fn verify_sample(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+        derived_data_type: DerivableType,
+        sample_rate: f64,
+    ) -> Result<Vec<VerificationMismatch>, DerivationError> {
+        let manager = manager_for_type(self, derived_data_type);
+        manager.verify_sample(ctx, csids, sample_rate).await
+    }
 }
@@ -0,0 +1,185 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::BTreeMap;
+
+use anyhow::Context;
+use anyhow::Result;
+use blobstore::BlobstoreGetData;
+use fbthrift::compact_protocol;
+use mononoke_types::errors::MononokeTypeError;
+use mononoke_types::BlobstoreBytes;
+use mononoke_types::BonsaiChangeset;
+use mononoke_types::ChangesetId;
+use mononoke_types::NonRootMPath;
+use mononoke_types_serialization as thrift;
+use sorted_vector_map::SortedVectorMap;
+
+/// Derived data recording, per commit, the number of files and bytes changed in each
+/// top-level directory.
+///
+/// Unlike most other derived data types, `DirectoryChurnInfo` has no dependency on its
+/// parents' derived values: it is computed directly from the file changes already present
+/// on the Bonsai changeset, so every commit can be derived independently and in parallel.
+/// The per-directory stats are plain counters, so they are also "mergeable": rolling up
+/// churn over a range of commits (e.g. for ownership analytics) is just summing the maps
+/// returned for each commit, without re-reading file changes from the changesets.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct DirectoryChurnInfo {
+    changeset_id: ChangesetId,
+    by_directory: SortedVectorMap<String, DirectoryChurnStats>,
+}
+
+/// Files and bytes changed (added, modified, or removed) in a single top-level directory
+/// by a single commit.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct DirectoryChurnStats {
+    pub files_changed: u64,
+    pub bytes_changed: u64,
+}
+
+impl DirectoryChurnStats {
+    fn add_assign(&mut self, other: &DirectoryChurnStats) {
+        self.files_changed += other.files_changed;
+        self.bytes_changed += other.bytes_changed;
+    }
+}
+
+/// The key used to bucket a changed path into a top-level directory: everything up to (but
+/// not including) the first path separator, or the empty string for a file at the repo root.
+fn top_level_directory(path: &NonRootMPath) -> String {
+    let mut elements = path.into_iter();
+    match (elements.next(), elements.next()) {
+        (Some(first), Some(_)) => String::from_utf8_lossy(first.as_ref()).into_owned(),
+        _ => String::new(),
+    }
+}
+
+impl DirectoryChurnInfo {
+    pub fn new(changeset_id: ChangesetId, changeset: &BonsaiChangeset) -> Self {
+        let mut by_directory: BTreeMap<String, DirectoryChurnStats> = BTreeMap::new();
+        for (path, file_change) in changeset.file_changes() {
+            let stats = by_directory.entry(top_level_directory(path)).or_default();
+            stats.files_changed += 1;
+            stats.bytes_changed += file_change.size().unwrap_or(0);
+        }
+        Self {
+            changeset_id,
+            by_directory: by_directory.into_iter().collect(),
+        }
+    }
+
+    /// Get id of the source Bonsai changeset.
+    pub fn changeset_id(&self) -> ChangesetId {
+        self.changeset_id
+    }
+
+    /// Churn stats for a single top-level directory (or the repo root, keyed by `""`),
+    /// if any files under it changed in this commit.
+    pub fn directory(&self, directory: &str) -> Option<DirectoryChurnStats> {
+        self.by_directory.get(directory).copied()
+    }
+
+    /// Churn stats for every top-level directory touched by this commit.
+    pub fn by_directory(&self) -> impl Iterator<Item = (&str, DirectoryChurnStats)> {
+        self.by_directory.iter().map(|(k, v)| (k.as_str(), *v))
+    }
+
+    /// Sum per-directory stats across a range of commits' churn info, e.g. to compute churn
+    /// over a date range without re-reading file changes.
+    pub fn merge_stats<'a>(
+        infos: impl IntoIterator<Item = &'a DirectoryChurnInfo>,
+    ) -> SortedVectorMap<String, DirectoryChurnStats> {
+        let mut merged: BTreeMap<String, DirectoryChurnStats> = BTreeMap::new();
+        for info in infos {
+            for (directory, stats) in info.by_directory.iter() {
+                merged
+                    .entry(directory.to_owned())
+                    .or_default()
+                    .add_assign(stats);
+            }
+        }
+        merged.into_iter().collect()
+    }
+
+    pub(crate) fn from_thrift(tc: thrift::churn::DirectoryChurnInfo) -> Result<Self> {
+        let catch_block = || -> Result<_> {
+            Ok(DirectoryChurnInfo {
+                changeset_id: ChangesetId::from_thrift(tc.changeset_id)?,
+                by_directory: tc
+                    .by_directory
+                    .into_iter()
+                    .map(|(k, v)| {
+                        (
+                            k,
+                            DirectoryChurnStats {
+                                files_changed: v.files_changed as u64,
+                                bytes_changed: v.bytes_changed as u64,
+                            },
+                        )
+                    })
+                    .collect(),
+            })
+        };
+
+        catch_block().with_context(|| {
+            MononokeTypeError::InvalidThrift(
+                "DirectoryChurnInfo".into(),
+                "Invalid directory churn info".into(),
+            )
+        })
+    }
+
+    pub fn into_thrift(self) -> thrift::churn::DirectoryChurnInfo {
+        thrift::churn::DirectoryChurnInfo {
+            changeset_id: self.changeset_id.into_thrift(),
+            by_directory: self
+                .by_directory
+                .into_iter()
+                .map(|(k, v)| {
+                    (
+                        k,
+                        thrift::churn::DirectoryChurnStats {
+                            files_changed: v.files_changed as i64,
+                            bytes_changed: v.bytes_changed as i64,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let thrift_tc = compact_protocol::deserialize(bytes).with_context(|| {
+            MononokeTypeError::BlobDeserializeError("DirectoryChurnInfo".into())
+        })?;
+        Self::from_thrift(thrift_tc)
+    }
+}
+
+impl TryFrom<BlobstoreBytes> for DirectoryChurnInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(blob_bytes: BlobstoreBytes) -> Result<Self> {
+        DirectoryChurnInfo::from_bytes(&blob_bytes.into_bytes())
+    }
+}
+
+impl TryFrom<BlobstoreGetData> for DirectoryChurnInfo {
+    type Error = anyhow::Error;
+
+    fn try_from(blob_get_data: BlobstoreGetData) -> Result<Self> {
+        blob_get_data.into_bytes().try_into()
+    }
+}
+
+impl From<DirectoryChurnInfo> for BlobstoreBytes {
+    fn from(info: DirectoryChurnInfo) -> BlobstoreBytes {
+        let data = compact_protocol::serialize(&info.into_thrift());
+        BlobstoreBytes::from_bytes(data)
+    }
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::HashMap;
+
+use anyhow::anyhow;
+use anyhow::Error;
+use anyhow::Result;
+use async_trait::async_trait;
+use blobstore::Blobstore;
+use context::CoreContext;
+use derived_data_manager::dependencies;
+use derived_data_manager::BonsaiDerivable;
+use derived_data_manager::DerivableType;
+use derived_data_manager::DerivationContext;
+use derived_data_service_if as thrift;
+use mononoke_types::BonsaiChangeset;
+use mononoke_types::ChangesetId;
+
+use crate::DirectoryChurnInfo;
+
+pub fn format_key(derivation_ctx: &DerivationContext, changeset_id: ChangesetId) -> String {
+    let root_prefix = "churn.blake2.";
+    let key_prefix = derivation_ctx.mapping_key_prefix::<DirectoryChurnInfo>();
+    format!("{}{}{}", root_prefix, key_prefix, changeset_id)
+}
+
+#[async_trait]
+impl BonsaiDerivable for DirectoryChurnInfo {
+    const VARIANT: DerivableType = DerivableType::DirectoryChurn;
+
+    // Computed entirely from the bonsai changeset's own file changes, so derivation of one
+    // commit never needs another commit's derived value.
+    type Dependencies = dependencies![];
+    type PredecessorDependencies = dependencies![];
+
+    async fn derive_single(
+        _ctx: &CoreContext,
+        _derivation_ctx: &DerivationContext,
+        bonsai: BonsaiChangeset,
+        _parents: Vec<Self>,
+    ) -> Result<Self, Error> {
+        Ok(DirectoryChurnInfo::new(bonsai.get_changeset_id(), &bonsai))
+    }
+
+    async fn derive_batch(
+        _ctx: &CoreContext,
+        _derivation_ctx: &DerivationContext,
+        bonsais: Vec<BonsaiChangeset>,
+    ) -> Result<HashMap<ChangesetId, Self>> {
+        Ok(bonsais
+            .into_iter()
+            .map(|bonsai| {
+                let csid = bonsai.get_changeset_id();
+                (csid, DirectoryChurnInfo::new(csid, &bonsai))
+            })
+            .collect())
+    }
+
+    async fn store_mapping(
+        self,
+        ctx: &CoreContext,
+        derivation_ctx: &DerivationContext,
+        changeset_id: ChangesetId,
+    ) -> Result<()> {
+        let key = format_key(derivation_ctx, changeset_id);
+        derivation_ctx.blobstore().put(ctx, key, self.into()).await
+    }
+
+    async fn fetch(
+        ctx: &CoreContext,
+        derivation_ctx: &DerivationContext,
+        changeset_id: ChangesetId,
+    ) -> Result<Option<Self>> {
+        let key = format_key(derivation_ctx, changeset_id);
+        Ok(derivation_ctx
+            .blobstore()
+            .get(ctx, &key)
+            .await?
+            .map(TryInto::try_into)
+            .transpose()?)
+    }
+
+    fn from_thrift(data: thrift::DerivedData) -> Result<Self> {
+        if let thrift::DerivedData::directory_churn(
+            thrift::DerivedDataDirectoryChurn::directory_churn(data),
+        ) = data
+        {
+            Self::from_thrift(data)
+        } else {
+            Err(anyhow!(
+                "Can't convert {} from provided thrift::DerivedData",
+                Self::NAME.to_string(),
+            ))
+        }
+    }
+
+    fn into_thrift(data: Self) -> Result<thrift::DerivedData> {
+        Ok(thrift::DerivedData::directory_churn(
+            thrift::DerivedDataDirectoryChurn::directory_churn(data.into_thrift()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use blobstore::Loadable;
+    use bonsai_hg_mapping::BonsaiHgMapping;
+    use bonsai_hg_mapping::BonsaiHgMappingRef;
+    use bookmarks::Bookmarks;
+    use commit_graph::CommitGraph;
+    use commit_graph::CommitGraphRef;
+    use commit_graph::CommitGraphWriter;
+    use fbinit::FacebookInit;
+    use filestore::FilestoreConfig;
+    use fixtures::Linear;
+    use fixtures::TestRepoFixture;
+    use mercurial_types::HgChangesetId;
+    use repo_blobstore::RepoBlobstore;
+    use repo_blobstore::RepoBlobstoreRef;
+    use repo_derived_data::RepoDerivedData;
+    use repo_derived_data::RepoDerivedDataRef;
+    use repo_identity::RepoIdentity;
+
+    use super::*;
+
+    #[facet::container]
+    struct Repo(
+        dyn BonsaiHgMapping,
+        dyn Bookmarks,
+        RepoBlobstore,
+        RepoDerivedData,
+        RepoIdentity,
+        CommitGraph,
+        dyn CommitGraphWriter,
+        FilestoreConfig,
+    );
+
+    #[fbinit::test]
+    async fn derive_churn_test(fb: FacebookInit) -> Result<(), Error> {
+        let repo: Repo = Linear::get_repo(fb).await;
+        let ctx = CoreContext::test_mock(fb);
+        let manager = repo.repo_derived_data().manager();
+
+        let hg_cs_id = HgChangesetId::from_str("3c15267ebf11807f3d772eb891272b911ec68759").unwrap();
+        let bcs_id = repo
+            .bonsai_hg_mapping()
+            .get_bonsai_from_hg(&ctx, hg_cs_id)
+            .await?
+            .unwrap();
+        let bcs = bcs_id.load(&ctx, repo.repo_blobstore()).await?;
+        let churn: DirectoryChurnInfo = manager.derive(&ctx, bcs_id, None).await?;
+
+        assert_eq!(churn.changeset_id(), bcs.get_changeset_id());
+        let expected_files: u64 = bcs.file_changes().count() as u64;
+        let derived_files: u64 = churn.by_directory().map(|(_, stats)| stats.files_changed).sum();
+        assert_eq!(derived_files, expected_files);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,13 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+mod churn;
+mod derive;
+
+pub use crate::churn::DirectoryChurnInfo;
+pub use crate::churn::DirectoryChurnStats;
+pub use crate::derive::format_key;
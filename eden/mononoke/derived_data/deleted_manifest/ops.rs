@@ -50,6 +50,27 @@ pub enum PathState {
 
 pub trait Repo = RepoDerivedDataRef + RepoBlobstoreArc + CommitGraphRef + Clone + Send + Sync;
 
+/// A single file found by [`DeletedManifestOps::list_deleted_files`].
+///
+/// This is deliberately minimal (just the path and the changeset that deleted it) since callers
+/// looking to resurrect a file typically just need enough to find the last live version of the
+/// file themselves from there (e.g. via [`DeletedManifestOps::resolve_path_state`] against one
+/// of `deleted_in`'s parents).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DeletedFileEntry {
+    pub path: MPath,
+    pub deleted_in: ChangesetId,
+}
+
+/// One page of [`DeletedManifestOps::list_deleted_files`] results.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DeletedFilesPage {
+    pub entries: Vec<DeletedFileEntry>,
+    /// Pass as `after` to continue listing from where this page left off. `None` means there
+    /// are no more pages.
+    pub next_after: Option<MPath>,
+}
+
 #[async_trait::async_trait]
 pub trait DeletedManifestOps: RootDeletedManifestIdCommon {
     /// Find if and when the path deleted.
@@ -339,6 +360,59 @@ pub trait DeletedManifestOps: RootDeletedManifestIdCommon {
         }
     }
 
+    /// List files deleted somewhere in the history of paths under `prefix`, ordered by path and
+    /// paginated via `after`/`limit`, so callers (e.g. "resurrect a file" workflows) don't have
+    /// to scan the whole deleted-files history client-side to find what used to live there.
+    async fn list_deleted_files(
+        &self,
+        ctx: &CoreContext,
+        blobstore: &impl Blobstore,
+        prefix: MPath,
+        after: Option<MPath>,
+        limit: usize,
+    ) -> Result<DeletedFilesPage, Error> {
+        let mut paths_and_ids = self
+            .find_entries(ctx, blobstore, vec![PathOrPrefix::Prefix(prefix)])
+            .try_collect::<Vec<_>>()
+            .await?;
+        paths_and_ids.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let start = match &after {
+            Some(after) => paths_and_ids.partition_point(|(path, _)| path <= after),
+            None => 0,
+        };
+        let mut page: Vec<_> = paths_and_ids
+            .into_iter()
+            .skip(start)
+            .take(limit + 1)
+            .collect();
+        let has_more = page.len() > limit;
+        page.truncate(limit);
+        // Continue after the last path examined, even if it was not itself a deleted file.
+        let last_path = page.last().map(|(path, _)| path.clone());
+
+        let mut entries = Vec::with_capacity(page.len());
+        for (path, mf_id) in page {
+            let mf = mf_id.load(ctx, blobstore).await?;
+            if let Some(deleted_in) = mf.linknode() {
+                entries.push(DeletedFileEntry {
+                    path,
+                    deleted_in: *deleted_in,
+                });
+            }
+        }
+        let next_after = if has_more {
+            last_path
+        } else {
+            None
+        };
+
+        Ok(DeletedFilesPage {
+            entries,
+            next_after,
+        })
+    }
+
     /// List all Deleted manifest entries recursively, that represent deleted paths.
     fn list_all_entries<'a>(
         &self,
@@ -18,6 +18,7 @@ pub use self::derivable::BonsaiDerivable;
 pub use self::error::DerivationError;
 pub use self::error::SharedDerivationError;
 pub use self::lease::DerivedDataLease;
+pub use self::manager::derive::DeriveWithDeadlineOutcome;
 pub use self::manager::derive::Rederivation;
 pub use self::manager::derive::VisitedDerivableTypesMap;
 pub use self::manager::DerivedDataManager;
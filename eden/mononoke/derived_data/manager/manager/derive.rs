@@ -83,6 +83,17 @@ impl Rederivation for Mutex<HashSet<ChangesetId>> {
 pub type VisitedDerivableTypesMap<'a, OkType, ErrType> =
     Arc<Mutex<HashMap<DerivableType, Shared<BoxFuture<'a, Result<OkType, ErrType>>>>>>;
 
+/// Result of [`DerivedDataManager::derive_with_deadline`]: either the derived data, or a report
+/// of how much ancestor derivation is still outstanding if the deadline was reached first.
+#[derive(Debug, Clone)]
+pub enum DeriveWithDeadlineOutcome<Derivable> {
+    Derived(Derivable),
+    Underived {
+        /// Number of underived ancestors (including the requested changeset) remaining.
+        remaining: u64,
+    },
+}
+
 impl DerivedDataManager {
     #[async_recursion]
     /// Returns the appropriate manager to derive given changeset, either this
@@ -363,6 +374,34 @@ impl DerivedDataManager {
         }
     }
 
+    /// Derive or retrieve derived data for a changeset, but give up after `deadline` instead of
+    /// blocking until derivation of the changeset and all its underived ancestors completes.
+    ///
+    /// This is for callers (e.g. interactive queries) that would rather get a quick, typed
+    /// "not ready yet" response than block indefinitely or fail outright on a commit that
+    /// happens to be underived.
+    pub async fn derive_with_deadline<Derivable>(
+        &self,
+        ctx: &CoreContext,
+        csid: ChangesetId,
+        rederivation: Option<Arc<dyn Rederivation>>,
+        deadline: Duration,
+    ) -> Result<DeriveWithDeadlineOutcome<Derivable>, SharedDerivationError>
+    where
+        Derivable: BonsaiDerivable,
+    {
+        let derive = self.derive::<Derivable>(ctx, csid, rederivation.clone());
+        match tokio::time::timeout(deadline, derive).await {
+            Ok(result) => Ok(DeriveWithDeadlineOutcome::Derived(result?)),
+            Err(_elapsed) => {
+                let remaining = self
+                    .count_underived::<Derivable>(ctx, csid, None, rederivation)
+                    .await?;
+                Ok(DeriveWithDeadlineOutcome::Underived { remaining })
+            }
+        }
+    }
+
     /// Derive or retrieve derived data for a changeset using other derived data types
     /// without requiring data to be derived for the parents of the changeset.
     pub async fn derive_from_predecessor<Derivable>(
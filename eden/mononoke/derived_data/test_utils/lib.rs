@@ -24,6 +24,12 @@ use mononoke_types::BonsaiChangeset;
 use mononoke_types::ChangesetId;
 use repo_blobstore::RepoBlobstoreRef;
 
+mod shapes;
+
+pub use crate::shapes::create_repo_with_shape;
+pub use crate::shapes::derive_all_types;
+pub use crate::shapes::RepoShape;
+
 pub async fn bonsai_changeset_from_hg(
     ctx: &CoreContext,
     repo: impl RepoBlobstoreRef + BonsaiHgMappingRef,
@@ -0,0 +1,84 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use bulk_derivation::BulkDerivation;
+use context::CoreContext;
+use derived_data_manager::DerivableType;
+use derived_data_manager::SharedDerivationError;
+use mononoke_types::ChangesetId;
+use rand::Rng;
+use repo_derived_data::RepoDerivedDataRef;
+use strum::IntoEnumIterator;
+use tests_utils::random::create_random_stack;
+use tests_utils::CreateCommitContext;
+use tests_utils::Repo;
+
+/// Shape of a synthetic repo to generate for derivation performance testing.
+///
+/// `stack_width` and `stack_depth` control how many parallel stacks are generated and how many
+/// commits deep each one is; directory width and nesting within a stack come from
+/// [`tests_utils::random`]'s own change generator, which already produces reasonably wide and
+/// deep directory structures. `merges` controls how many merge commits tie the stacks back
+/// together afterwards, so derivers that walk merge parents (e.g. unodes, blame) get exercised
+/// against wide parent sets rather than just linear history.
+#[derive(Clone, Copy, Debug)]
+pub struct RepoShape {
+    pub stack_width: usize,
+    pub stack_depth: usize,
+    pub merges: usize,
+}
+
+/// Synthesize a repo matching `shape` and return the id of its last created head changeset.
+/// When `shape.merges` is zero and there is more than one stack, the stacks stay separate and
+/// only the first stack's head is returned.
+pub async fn create_repo_with_shape(
+    ctx: &CoreContext,
+    repo: &impl Repo,
+    rng: &mut impl Rng,
+    shape: &RepoShape,
+) -> Result<ChangesetId> {
+    let stack_width = std::cmp::max(shape.stack_width, 1);
+    let mut heads = Vec::with_capacity(stack_width);
+    for _ in 0..stack_width {
+        let (head, _manifest) = create_random_stack(
+            ctx,
+            repo,
+            rng,
+            None,
+            std::iter::repeat(1).take(shape.stack_depth),
+        )
+        .await?;
+        heads.push(head);
+    }
+
+    let mut head = heads.remove(0);
+    for _ in 0..shape.merges {
+        let mut commit = CreateCommitContext::new(ctx, repo, vec![head]);
+        for parent in &heads {
+            commit = commit.add_parent(*parent);
+        }
+        head = commit.commit().await?;
+    }
+
+    Ok(head)
+}
+
+/// Derive every registered [`DerivableType`] for `csids`, in dependency order, so a fixture
+/// built with [`create_repo_with_shape`] exercises the full derivation stack the way a real
+/// landing would, rather than only the handful of types a test happens to touch directly.
+pub async fn derive_all_types(
+    ctx: &CoreContext,
+    repo: &impl Repo,
+    csids: &[ChangesetId],
+) -> Result<(), SharedDerivationError> {
+    let all_types: Vec<DerivableType> = DerivableType::iter().collect();
+    repo.repo_derived_data()
+        .manager()
+        .derive_bulk_in_dependency_order(ctx, csids, None, &all_types, None)
+        .await
+}
@@ -0,0 +1,116 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::time::Duration;
+
+use anyhow::Result;
+use bulk_derivation::BulkDerivation;
+use bulk_derivation::VerificationMismatch;
+use context::CoreContext;
+use derived_data_manager::DerivableType;
+use derived_data_manager::DerivedDataManager;
+use mononoke_types::ChangesetId;
+use slog::info;
+use slog::warn;
+use slog::Logger;
+
+/// Configuration controlling how an [`IntegrityVerifier`] samples and paces continuous
+/// verification.
+#[derive(Clone, Debug)]
+pub struct VerifierConfig {
+    /// Fraction, in `[0.0, 1.0]`, of the sampled commit window that gets re-derived and
+    /// compared on every pass.
+    pub sample_rate: f64,
+    /// How long to sleep between passes.
+    pub poll_interval: Duration,
+}
+
+impl Default for VerifierConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.01,
+            poll_interval: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Continuously re-derives a small random sample of commits for a single derived data type and
+/// compares the result against what's stored, to catch derivation nondeterminism (e.g. an
+/// unstable sort, or a dependency on iteration order) before it causes silent divergence between
+/// hosts that derived the same commit at different times.
+///
+/// Unlike `backfill_derivation::BackfillDriver`, this is not meant to run to completion: it is
+/// intended to be left running indefinitely alongside normal derivation traffic, continuously
+/// resampling whatever commit window the caller points it at.
+pub struct IntegrityVerifier {
+    manager: DerivedDataManager,
+    derived_data_type: DerivableType,
+    config: VerifierConfig,
+    logger: Logger,
+}
+
+impl IntegrityVerifier {
+    pub fn new(
+        manager: DerivedDataManager,
+        derived_data_type: DerivableType,
+        config: VerifierConfig,
+        logger: Logger,
+    ) -> Self {
+        Self {
+            manager,
+            derived_data_type,
+            config,
+            logger,
+        }
+    }
+
+    /// Verify `csids` once, returning any mismatches found during this pass.
+    pub async fn verify_once(
+        &self,
+        ctx: &CoreContext,
+        csids: &[ChangesetId],
+    ) -> Result<Vec<VerificationMismatch>> {
+        let mismatches = self
+            .manager
+            .verify_sample(ctx, csids, self.derived_data_type, self.config.sample_rate)
+            .await?;
+        for mismatch in &mismatches {
+            warn!(
+                self.logger,
+                "{}: derivation mismatch for {}: stored={} rederived={}",
+                mismatch.derived_data_type,
+                mismatch.csid,
+                mismatch.stored,
+                mismatch.rederived,
+            );
+        }
+        Ok(mismatches)
+    }
+
+    /// Run [`Self::verify_once`] in a loop, sleeping `config.poll_interval` between passes,
+    /// until `should_stop` returns `true`.
+    ///
+    /// `csids` is called fresh at the start of every pass, so the caller can move the sampled
+    /// commit window forward over time (e.g. by having it read from an `ArcSwap` that's updated
+    /// as new commits land) without restarting the verifier.
+    pub async fn run_continuously(
+        &self,
+        ctx: &CoreContext,
+        csids: impl Fn() -> Vec<ChangesetId>,
+        mut should_stop: impl FnMut() -> bool,
+    ) -> Result<()> {
+        while !should_stop() {
+            let window = csids();
+            if !window.is_empty() {
+                self.verify_once(ctx, &window).await?;
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+        info!(self.logger, "{}: verifier stopped", self.derived_data_type);
+        Ok(())
+    }
+}
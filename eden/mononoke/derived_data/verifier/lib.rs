@@ -0,0 +1,15 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Continuous derived data integrity verification: re-derives a sampled subset of commits and
+//! compares the result against what's stored, to catch derivation nondeterminism before it
+//! causes silent divergence between hosts.
+
+mod driver;
+
+pub use crate::driver::IntegrityVerifier;
+pub use crate::driver::VerifierConfig;
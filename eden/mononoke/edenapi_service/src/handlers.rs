@@ -70,6 +70,7 @@ mod handler;
 mod history;
 mod land;
 mod lookup;
+mod path_history;
 mod repos;
 mod suffix_query;
 mod trees;
@@ -88,6 +89,7 @@ pub enum SaplingRemoteApiMethod {
     AlterSnapshot,
     Blame,
     Bookmarks,
+    BookmarksSubscribe,
     Capabilities,
     CloudReferences,
     CloudShareWorkspace,
@@ -111,6 +113,7 @@ pub enum SaplingRemoteApiMethod {
     History,
     LandStack,
     Lookup,
+    PathHistory,
     SetBookmark,
     SuffixQuery,
     Trees,
@@ -127,6 +130,7 @@ impl fmt::Display for SaplingRemoteApiMethod {
             Self::AlterSnapshot => "alter_snapshot",
             Self::Blame => "blame",
             Self::Bookmarks => "bookmarks",
+            Self::BookmarksSubscribe => "bookmarks_subscribe",
             Self::Capabilities => "capabilities",
             Self::CloudReferences => "cloud_references",
             Self::CloudShareWorkspace => "cloud_share_workspace",
@@ -150,6 +154,7 @@ impl fmt::Display for SaplingRemoteApiMethod {
             Self::History => "history",
             Self::LandStack => "land_stack",
             Self::Lookup => "lookup",
+            Self::PathHistory => "path_history",
             Self::SetBookmark => "set_bookmark",
             Self::SuffixQuery => "suffix_query",
             Self::Trees => "trees",
@@ -424,6 +429,7 @@ pub fn build_router(ctx: ServerContext) -> Router {
             .to(proxygen_health_handler);
         Handlers::setup::<blame::BlameHandler>(route);
         Handlers::setup::<bookmarks::BookmarksHandler>(route);
+        Handlers::setup::<bookmarks::BookmarksSubscribeHandler>(route);
         Handlers::setup::<bookmarks::SetBookmarkHandler>(route);
         Handlers::setup::<commit_cloud::CommitCloudReferences>(route);
         Handlers::setup::<commit_cloud::CommitCloudShareWorkspace>(route);
@@ -450,6 +456,7 @@ pub fn build_router(ctx: ServerContext) -> Router {
         Handlers::setup::<land::LandStackHandler>(route);
         Handlers::setup::<lookup::LookupHandler>(route);
         Handlers::setup::<commit_cloud::CommitCloudSmartlog>(route);
+        Handlers::setup::<path_history::PathHistoryHandler>(route);
         Handlers::setup::<suffix_query::SuffixQueryHandler>(route);
         Handlers::setup::<trees::UploadTreesHandler>(route);
         route.get("/:repo/health_check").to(health_handler);
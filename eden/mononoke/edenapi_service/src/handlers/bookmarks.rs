@@ -6,6 +6,7 @@
  */
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Error;
@@ -15,6 +16,7 @@ use bookmarks::Freshness;
 use bytes::Bytes;
 use edenapi_types::BookmarkEntry;
 use edenapi_types::BookmarkRequest;
+use edenapi_types::BookmarksSubscribeRequest;
 use edenapi_types::HgId;
 use edenapi_types::ServerError;
 use edenapi_types::SetBookmarkRequest;
@@ -34,6 +36,17 @@ use crate::errors::ErrorKind;
 /// XXX: This number was chosen arbitrarily.
 const MAX_CONCURRENT_FETCHES_PER_REQUEST: usize = 100;
 
+/// Upper bound on how long the server will hold a `bookmarks/subscribe` request open,
+/// regardless of what the client asked for. Keeps a misbehaving or disconnected client
+/// from pinning a handler task open indefinitely.
+const MAX_SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Default long-poll timeout used when the client doesn't specify one.
+const DEFAULT_SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often to re-check bookmark values while long-polling.
+const SUBSCRIBE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Resolve the bookmarks requested by the client.
 pub struct BookmarksHandler;
 
@@ -72,6 +85,74 @@ async fn fetch_bookmark(repo: HgRepoContext, bookmark: String) -> Result<Bookmar
     Ok(BookmarkEntry { bookmark, hgid })
 }
 
+/// Long-poll for changes to a set of bookmarks.
+pub struct BookmarksSubscribeHandler;
+
+#[async_trait]
+impl SaplingRemoteApiHandler for BookmarksSubscribeHandler {
+    type Request = BookmarksSubscribeRequest;
+    type Response = BookmarkEntry;
+
+    const HTTP_METHOD: hyper::Method = hyper::Method::POST;
+    const API_METHOD: SaplingRemoteApiMethod = SaplingRemoteApiMethod::BookmarksSubscribe;
+    const ENDPOINT: &'static str = "/bookmarks/subscribe";
+
+    async fn handler(
+        ectx: SaplingRemoteApiContext<Self::PathExtractor, Self::QueryStringExtractor>,
+        request: Self::Request,
+    ) -> HandlerResult<'async_trait, Self::Response> {
+        let repo = ectx.repo();
+        let changed = subscribe_bookmarks(repo, request.bookmarks, request.timeout_ms).await?;
+        Ok(stream::iter(changed.into_iter().map(Ok)).boxed())
+    }
+}
+
+/// Wait until at least one of `known` no longer matches the server's current value, or
+/// `timeout_ms` elapses, whichever comes first. Returns the bookmarks that changed (empty
+/// if the wait timed out).
+async fn subscribe_bookmarks(
+    repo: HgRepoContext,
+    known: Vec<BookmarkEntry>,
+    timeout_ms: Option<u64>,
+) -> Result<Vec<BookmarkEntry>, Error> {
+    let timeout = timeout_ms
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_SUBSCRIBE_TIMEOUT)
+        .min(MAX_SUBSCRIBE_TIMEOUT);
+    let deadline = std::time::Instant::now() + timeout;
+
+    loop {
+        let changed = changed_bookmarks(&repo, &known).await?;
+        if !changed.is_empty() || std::time::Instant::now() >= deadline {
+            return Ok(changed);
+        }
+        tokio::time::sleep(SUBSCRIBE_POLL_INTERVAL.min(deadline - std::time::Instant::now())).await;
+    }
+}
+
+/// Re-resolve each bookmark in `known` and return the ones whose current value differs
+/// from what the client last saw.
+async fn changed_bookmarks(
+    repo: &HgRepoContext,
+    known: &[BookmarkEntry],
+) -> Result<Vec<BookmarkEntry>, Error> {
+    let mut changed = Vec::new();
+    for entry in known {
+        let hgid = repo
+            .resolve_bookmark(entry.bookmark.clone(), Freshness::MaybeStale)
+            .await
+            .map_err(|_| ErrorKind::BookmarkResolutionFailed(entry.bookmark.clone()))?
+            .map(|id| HgId::from(id.into_nodehash()));
+        if hgid != entry.hgid {
+            changed.push(BookmarkEntry {
+                bookmark: entry.bookmark.clone(),
+                hgid,
+            });
+        }
+    }
+    Ok(changed)
+}
+
 /// Create, delete, or move a bookmark
 pub struct SetBookmarkHandler;
 
@@ -14,11 +14,14 @@ use bookmarks_movement::BookmarkKindRestrictions;
 use bytes::Bytes;
 use edenapi_types::HgId;
 use edenapi_types::LandStackData;
+use edenapi_types::LandStackProgress;
 use edenapi_types::LandStackRequest;
 use edenapi_types::LandStackResponse;
+use edenapi_types::LandStackResponseItem;
 use edenapi_types::ServerError;
 use futures::stream;
 use futures::StreamExt;
+use futures::TryStreamExt;
 use hooks::PushAuthoredBy;
 use mercurial_types::HgChangesetId;
 use mercurial_types::HgNodeHash;
@@ -37,7 +40,7 @@ pub struct LandStackHandler;
 #[async_trait]
 impl SaplingRemoteApiHandler for LandStackHandler {
     type Request = LandStackRequest;
-    type Response = LandStackResponse;
+    type Response = LandStackResponseItem;
 
     const HTTP_METHOD: hyper::Method = hyper::Method::POST;
     const API_METHOD: SaplingRemoteApiMethod = SaplingRemoteApiMethod::LandStack;
@@ -47,7 +50,18 @@ impl SaplingRemoteApiHandler for LandStackHandler {
         ectx: SaplingRemoteApiContext<Self::PathExtractor, Self::QueryStringExtractor>,
         request: Self::Request,
     ) -> HandlerResult<'async_trait, Self::Response> {
-        Ok(stream::once(land_stack_response(
+        // Stream coarse progress updates ahead of the final result so a large stack land
+        // doesn't look hung to the client while hooks run and pushrebase retries conflicts.
+        let progress = stream::iter([
+            Ok(LandStackResponseItem::Progress(
+                LandStackProgress::Validating,
+            )),
+            Ok(LandStackResponseItem::Progress(
+                LandStackProgress::RunningHooks,
+            )),
+        ]);
+
+        let result = stream::once(land_stack_response(
             ectx.repo(),
             request.bookmark,
             request.head,
@@ -58,7 +72,9 @@ impl SaplingRemoteApiHandler for LandStackHandler {
                 .map(|p| (p.key, p.value.into()))
                 .collect(),
         ))
-        .boxed())
+        .map_ok(LandStackResponseItem::Done);
+
+        Ok(progress.chain(result).boxed())
     }
 }
 
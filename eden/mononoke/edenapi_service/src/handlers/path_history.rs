@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use anyhow::Error;
+use async_trait::async_trait;
+use edenapi_types::CommitId;
+use edenapi_types::PathHistoryRequest;
+use edenapi_types::PathHistoryResponse;
+use edenapi_types::PathHistoryResponseChunk;
+use edenapi_types::ServerError;
+use futures::future;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use mononoke_api::ChangesetPathHistoryOptions;
+use mononoke_api_hg::HgRepoContext;
+use mononoke_types::ChangesetId;
+
+use super::handler::SaplingRemoteApiContext;
+use super::HandlerResult;
+use super::SaplingRemoteApiHandler;
+use super::SaplingRemoteApiMethod;
+use crate::errors::ErrorKind;
+use crate::utils::to_mpath;
+
+/// XXX: This number was chosen arbitrarily.
+const DEFAULT_PATH_HISTORY_LIMIT: u32 = 100;
+
+/// Fetch the commits that modified a path, following renames server-side via mutable
+/// rename data. Saves the client from emulating fastlog/linkrev tracing itself across
+/// many round trips.
+pub struct PathHistoryHandler;
+
+#[async_trait]
+impl SaplingRemoteApiHandler for PathHistoryHandler {
+    type Request = PathHistoryRequest;
+    type Response = PathHistoryResponse;
+
+    const HTTP_METHOD: hyper::Method = hyper::Method::POST;
+    const API_METHOD: SaplingRemoteApiMethod = SaplingRemoteApiMethod::PathHistory;
+    const ENDPOINT: &'static str = "/path_history";
+
+    async fn handler(
+        ectx: SaplingRemoteApiContext<Self::PathExtractor, Self::QueryStringExtractor>,
+        request: Self::Request,
+    ) -> HandlerResult<'async_trait, Self::Response> {
+        let repo = ectx.repo();
+        Ok(stream::once(path_history_response(repo, request)).boxed())
+    }
+}
+
+async fn path_history_response(
+    repo: HgRepoContext,
+    request: PathHistoryRequest,
+) -> Result<PathHistoryResponse, Error> {
+    Ok(PathHistoryResponse {
+        data: path_history(repo, request)
+            .await
+            .map_err(|e| ServerError::generic(format!("{:?}", e))),
+    })
+}
+
+async fn path_history(
+    repo: HgRepoContext,
+    request: PathHistoryRequest,
+) -> Result<PathHistoryResponseChunk, Error> {
+    let repo = repo.repo_ctx();
+    let PathHistoryRequest {
+        path,
+        commit,
+        limit,
+        cursor,
+    } = request;
+    let limit = limit.unwrap_or(DEFAULT_PATH_HISTORY_LIMIT) as usize;
+
+    let changeset = repo
+        .changeset(commit.clone())
+        .await
+        .with_context(|| format!("Error getting changeset {}", commit))?
+        .ok_or_else(|| ErrorKind::CommitIdNotFound(commit.clone()))?;
+
+    let resume_after = match cursor {
+        Some(cursor) => Some(
+            repo.changeset(CommitId::Hg(cursor))
+                .await
+                .with_context(|| format!("Error getting cursor changeset {}", cursor))?
+                .ok_or(ErrorKind::HgIdNotFound(cursor))?
+                .id(),
+        ),
+        None => None,
+    };
+
+    let mpath = to_mpath(&path)?;
+    let opts = ChangesetPathHistoryOptions {
+        follow_mutable_file_history: true,
+        ..Default::default()
+    };
+
+    let path_context = changeset.path_with_history(mpath).await?;
+    let history = path_context.history(opts).await?;
+    // The cursor is the last entry of the previous page: resume right after it.
+    let history = match resume_after {
+        Some(cursor_id) => history
+            .try_skip_while(move |cs| future::ready(Ok(cs.id() != cursor_id)))
+            .skip(1)
+            .left_stream(),
+        None => history.right_stream(),
+    };
+    let mut results: Vec<_> = history.take(limit + 1).try_collect().await?;
+
+    let has_more = results.len() > limit;
+    results.truncate(limit);
+
+    let ids: Vec<ChangesetId> = results.iter().map(|cs| cs.id()).collect();
+    let hg_ids: HashMap<_, _> = repo
+        .many_changeset_hg_ids(ids.clone())
+        .await?
+        .into_iter()
+        .collect();
+
+    let mut entries = Vec::with_capacity(ids.len());
+    for id in &ids {
+        let hg_id = hg_ids
+            .get(id)
+            .ok_or(ErrorKind::BonsaiChangesetToHgIdError(*id))?
+            .into_nodehash()
+            .into();
+        entries.push(hg_id);
+    }
+
+    let next = if has_more {
+        entries.last().copied()
+    } else {
+        None
+    };
+
+    Ok(PathHistoryResponseChunk { entries, next })
+}
@@ -5,10 +5,13 @@
  * GNU General Public License version 2.
  */
 
+use std::sync::Arc;
+
 use bookmarks::BookmarkKey;
 use context::CoreContext;
 use fbinit::FacebookInit;
 use fixtures::TestRepoFixture;
+use hook_outcome_store::NullHookOutcomeStore;
 use maplit::hashset;
 use metaconfig_types::BookmarkParams;
 use metaconfig_types::HookManagerParams;
@@ -37,6 +40,7 @@ async fn hook_manager_repo(fb: FacebookInit, repo: &BasicTestRepo) -> HookManage
         },
         MononokeScubaSampleBuilder::with_discard(),
         "zoo".to_string(),
+        Arc::new(NullHookOutcomeStore),
     )
     .await
     .expect("Failed to construct HookManager")
@@ -19,6 +19,7 @@ pub(crate) mod block_new_bookmark_creations_by_name;
 pub(crate) mod block_unannotated_tags;
 pub(crate) mod block_unclean_merge_commits;
 pub(crate) mod deny_files;
+mod external_command;
 mod limit_commit_message_length;
 pub(crate) mod limit_commit_size;
 mod limit_directory_size;
@@ -33,6 +34,7 @@ mod no_insecure_filenames;
 pub(crate) mod no_questionable_filenames;
 pub(crate) mod no_windows_filenames;
 pub(crate) mod require_commit_message_pattern;
+mod rules;
 
 use anyhow::Result;
 use fbinit::FacebookInit;
@@ -97,6 +99,10 @@ pub async fn make_changeset_hook(
         "require_commit_message_pattern" => Some(b(
             require_commit_message_pattern::RequireCommitMessagePatternHook::new(&params.config)?,
         )),
+        "rules" => Some(b(rules::RulesHook::new(&params.config)?)),
+        "external_command" => Some(b(external_command::ExternalCommandHook::new(
+            &params.config,
+        )?)),
         _ => None,
     })
 }
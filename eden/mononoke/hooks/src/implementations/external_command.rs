@@ -0,0 +1,309 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::process::Stdio;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Result;
+use async_trait::async_trait;
+use bookmarks::BookmarkKey;
+use context::CoreContext;
+use mononoke_types::BonsaiChangeset;
+use mononoke_types::FileType;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::ChangesetHook;
+use crate::CrossRepoPushSource;
+use crate::HookConfig;
+use crate::HookExecution;
+use crate::HookRejectionInfo;
+use crate::HookStateProvider;
+use crate::PushAuthoredBy;
+
+/// The largest stdout payload we'll read back from an external hook before
+/// giving up, to stop a misbehaving program from exhausting server memory.
+const MAX_RESPONSE_BYTES: usize = 1024 * 1024;
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ExternalCommandConfig {
+    /// Path to the external program to execute.
+    command: String,
+
+    /// Extra arguments to pass to the program, before the JSON request is
+    /// written to its stdin.
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct FileRequest {
+    path: String,
+    status: &'static str,
+    content_id: Option<String>,
+    file_type: Option<FileType>,
+    size: Option<u64>,
+}
+
+#[derive(Serialize)]
+struct HookRequest {
+    bookmark: String,
+    changeset_id: String,
+    parents: Vec<String>,
+    author: String,
+    author_date: String,
+    message: String,
+    push_authored_by: &'static str,
+    files: Vec<FileRequest>,
+}
+
+#[derive(Deserialize)]
+struct HookResponse {
+    accepted: bool,
+    #[serde(default)]
+    message: Option<String>,
+}
+
+/// Hook that delegates the accept/reject decision to an external program,
+/// so that policies can be written and deployed without recompiling the
+/// server. The program is given a JSON description of the commit on stdin,
+/// and is expected to write a JSON decision to stdout before exiting.
+pub struct ExternalCommandHook {
+    config: ExternalCommandConfig,
+}
+
+impl ExternalCommandHook {
+    pub fn new(config: &HookConfig) -> Result<Self> {
+        Ok(Self {
+            config: config.parse_options()?,
+        })
+    }
+
+    fn build_request(
+        &self,
+        bookmark: &BookmarkKey,
+        changeset: &BonsaiChangeset,
+        push_authored_by: PushAuthoredBy,
+    ) -> HookRequest {
+        let files = changeset
+            .file_changes()
+            .map(|(path, file_change)| {
+                let (status, basic) = match file_change.simplify() {
+                    Some(basic) if file_change.is_changed() => ("modified", Some(basic)),
+                    Some(basic) => ("deleted", Some(basic)),
+                    None => ("deleted", None),
+                };
+                FileRequest {
+                    path: path.to_string(),
+                    status,
+                    content_id: basic.map(|b| b.content_id().to_string()),
+                    file_type: basic.map(|b| b.file_type()),
+                    size: basic.map(|b| b.size()),
+                }
+            })
+            .collect();
+
+        HookRequest {
+            bookmark: bookmark.to_string(),
+            changeset_id: changeset.get_changeset_id().to_string(),
+            parents: changeset.parents().map(|id| id.to_string()).collect(),
+            author: changeset.author().to_string(),
+            author_date: changeset.author_date().as_chrono().to_rfc3339(),
+            message: changeset.message().to_string(),
+            push_authored_by: if push_authored_by.service() {
+                "service"
+            } else {
+                "user"
+            },
+            files,
+        }
+    }
+
+    async fn invoke(&self, request: &HookRequest) -> Result<HookResponse> {
+        let payload = serde_json::to_vec(request).context("Failed to serialize hook request")?;
+
+        let mut child = Command::new(&self.config.command)
+            .args(&self.config.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("Failed to spawn external hook `{}`", self.config.command))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| anyhow!("External hook child has no stdin"))?;
+        let mut stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("External hook child has no stdout"))?;
+
+        // Write stdin and read stdout concurrently: a hook program that starts
+        // writing output before it has fully drained stdin would otherwise
+        // deadlock us here once either side fills its pipe buffer.
+        let write_fut = async {
+            stdin
+                .write_all(&payload)
+                .await
+                .context("Failed to write request to external hook")?;
+            drop(stdin);
+            Ok::<(), anyhow::Error>(())
+        };
+        let mut output = Vec::new();
+        let read_fut = async {
+            (&mut stdout)
+                .take(MAX_RESPONSE_BYTES as u64)
+                .read_to_end(&mut output)
+                .await
+                .context("Failed to read external hook response")
+        };
+        tokio::try_join!(write_fut, read_fut)?;
+
+        let status = child
+            .wait()
+            .await
+            .context("Failed to wait for external hook")?;
+        if !status.success() {
+            return Err(anyhow!(
+                "External hook `{}` exited with {}",
+                self.config.command,
+                status
+            ));
+        }
+
+        serde_json::from_slice(&output).context("Failed to parse external hook response")
+    }
+}
+
+#[async_trait]
+impl ChangesetHook for ExternalCommandHook {
+    async fn run<'this: 'cs, 'ctx: 'this, 'cs, 'fetcher: 'cs>(
+        &'this self,
+        _ctx: &'ctx CoreContext,
+        bookmark: &BookmarkKey,
+        changeset: &'cs BonsaiChangeset,
+        _content_manager: &'fetcher dyn HookStateProvider,
+        _cross_repo_push_source: CrossRepoPushSource,
+        push_authored_by: PushAuthoredBy,
+    ) -> Result<HookExecution> {
+        let request = self.build_request(bookmark, changeset, push_authored_by);
+        let response = self.invoke(&request).await?;
+        if response.accepted {
+            Ok(HookExecution::Accepted)
+        } else {
+            Ok(HookExecution::Rejected(HookRejectionInfo::new_long(
+                "Rejected by external hook",
+                response
+                    .message
+                    .unwrap_or_else(|| "No further details were provided".to_string()),
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbinit::FacebookInit;
+    use tests_utils::drawdag::changes;
+    use tests_utils::drawdag::create_from_dag_with_changes;
+    use tests_utils::BasicTestRepo;
+
+    use super::*;
+    use crate::testlib::test_changeset_hook;
+
+    fn hook_for_script(script: &str) -> Result<ExternalCommandHook> {
+        ExternalCommandHook::new(&HookConfig {
+            options: Some(serde_json::to_string(&serde_json::json!({
+                "command": "/bin/sh",
+                "args": ["-c", script],
+            }))?),
+            ..Default::default()
+        })
+    }
+
+    #[fbinit::test]
+    async fn test_external_command_accepts(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let repo: BasicTestRepo = test_repo_factory::build_empty(fb).await?;
+
+        let changesets = create_from_dag_with_changes(
+            &ctx,
+            &repo,
+            r##"
+                Z-A
+            "##,
+            changes! {
+                "A" => |c| c.add_file("file", "content"),
+            },
+        )
+        .await?;
+
+        let hook = hook_for_script("cat >/dev/null; echo '{\"accepted\": true}'")?;
+
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["A"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Accepted,
+        );
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    async fn test_external_command_rejects(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let repo: BasicTestRepo = test_repo_factory::build_empty(fb).await?;
+
+        let changesets = create_from_dag_with_changes(
+            &ctx,
+            &repo,
+            r##"
+                Z-A
+            "##,
+            changes! {
+                "A" => |c| c.add_file("file", "content"),
+            },
+        )
+        .await?;
+
+        let hook =
+            hook_for_script("cat >/dev/null; echo '{\"accepted\": false, \"message\": \"no\"}'")?;
+
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["A"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Rejected(HookRejectionInfo {
+                description: "Rejected by external hook".into(),
+                long_description: "no".into(),
+            }),
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use bookmarks::BookmarkKey;
+use context::CoreContext;
+use mononoke_types::BonsaiChangeset;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::ChangesetHook;
+use crate::CrossRepoPushSource;
+use crate::HookConfig;
+use crate::HookExecution;
+use crate::HookRejectionInfo;
+use crate::HookStateProvider;
+use crate::PushAuthoredBy;
+
+/// A single condition that can be evaluated against a commit, combined with
+/// others via `all_of`/`any_of`/`not` to build up arbitrary policies without
+/// needing a new compiled hook for each one.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum Rule {
+    /// Matches if any file touched by the commit has a path matching this
+    /// pattern.
+    PathMatches(#[serde(with = "serde_regex")] Regex),
+
+    /// Matches if the commit author matches this pattern.
+    AuthorMatches(#[serde(with = "serde_regex")] Regex),
+
+    /// Matches if the commit message matches this pattern.
+    MessageMatches(#[serde(with = "serde_regex")] Regex),
+
+    /// Matches if any added or modified file is larger than this many bytes.
+    FileSizeExceeds(u64),
+
+    /// Matches if all of the given rules match.
+    AllOf(Vec<Rule>),
+
+    /// Matches if any of the given rules match.
+    AnyOf(Vec<Rule>),
+
+    /// Matches if the given rule does not match.
+    Not(Box<Rule>),
+}
+
+impl Rule {
+    fn matches(&self, changeset: &BonsaiChangeset) -> bool {
+        match self {
+            Rule::PathMatches(pattern) => changeset
+                .file_changes()
+                .any(|(path, _)| pattern.is_match(&path.to_string())),
+            Rule::AuthorMatches(pattern) => pattern.is_match(changeset.author()),
+            Rule::MessageMatches(pattern) => pattern.is_match(changeset.message()),
+            Rule::FileSizeExceeds(limit) => changeset
+                .file_changes()
+                .any(|(_, file_change)| file_change.size().unwrap_or(0) > *limit),
+            Rule::AllOf(rules) => rules.iter().all(|rule| rule.matches(changeset)),
+            Rule::AnyOf(rules) => rules.iter().any(|rule| rule.matches(changeset)),
+            Rule::Not(rule) => !rule.matches(changeset),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct RulesConfig {
+    /// The commit is rejected if this rule matches.
+    rule: Rule,
+
+    /// Message to include in the hook rejection if `rule` matches.
+    message: String,
+}
+
+/// Generic hook that rejects commits matching a declarative rule tree of
+/// path patterns, author patterns, message patterns and file size limits,
+/// combined with and/or/not semantics, so common policies don't each need
+/// their own compiled hook.
+#[derive(Clone, Debug)]
+pub struct RulesHook {
+    config: RulesConfig,
+}
+
+impl RulesHook {
+    pub fn new(config: &HookConfig) -> Result<Self> {
+        Self::with_config(config.parse_options()?)
+    }
+
+    pub fn with_config(config: RulesConfig) -> Result<Self> {
+        Ok(Self { config })
+    }
+}
+
+#[async_trait]
+impl ChangesetHook for RulesHook {
+    async fn run<'this: 'cs, 'ctx: 'this, 'cs, 'fetcher: 'cs>(
+        &'this self,
+        _ctx: &'ctx CoreContext,
+        _bookmark: &BookmarkKey,
+        changeset: &'cs BonsaiChangeset,
+        _content_manager: &'fetcher dyn HookStateProvider,
+        _cross_repo_push_source: CrossRepoPushSource,
+        push_authored_by: PushAuthoredBy,
+    ) -> Result<HookExecution> {
+        if push_authored_by.service() {
+            return Ok(HookExecution::Accepted);
+        }
+        if self.config.rule.matches(changeset) {
+            return Ok(HookExecution::Rejected(HookRejectionInfo::new_long(
+                "Commit matched a blocked rule",
+                self.config.message.clone(),
+            )));
+        }
+        Ok(HookExecution::Accepted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fbinit::FacebookInit;
+    use tests_utils::bookmark;
+    use tests_utils::drawdag::changes;
+    use tests_utils::drawdag::create_from_dag_with_changes;
+    use tests_utils::BasicTestRepo;
+
+    use super::*;
+    use crate::testlib::test_changeset_hook;
+
+    #[fbinit::test]
+    async fn test_rule_combinators(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let repo: BasicTestRepo = test_repo_factory::build_empty(fb).await?;
+
+        let changesets = create_from_dag_with_changes(
+            &ctx,
+            &repo,
+            r##"
+                Z-A-B-C
+            "##,
+            changes! {
+                "A" => |c| c.set_author("alice").set_message("normal change").add_file("src/main.rs", "fn main() {}"),
+                "B" => |c| c.set_author("bot").set_message("normal change").add_file("src/main.rs", "fn main() {}"),
+                "C" => |c| c.set_author("alice").set_message("WIP do not land").add_file("src/main.rs", "fn main() {}"),
+            },
+        )
+        .await?;
+        bookmark(&ctx, &repo, "main")
+            .create_publishing(changesets["Z"])
+            .await?;
+
+        let hook = RulesHook::with_config(RulesConfig {
+            rule: Rule::AllOf(vec![
+                Rule::PathMatches(Regex::new(r"^src/")?),
+                Rule::AnyOf(vec![
+                    Rule::Not(Box::new(Rule::AuthorMatches(Regex::new(r"^bot$")?))),
+                    Rule::MessageMatches(Regex::new(r"(?i)wip")?),
+                ]),
+            ]),
+            message: String::from("src/ changes must come from bot and not be marked WIP"),
+        })?;
+
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["A"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Rejected(HookRejectionInfo {
+                description: "Commit matched a blocked rule".into(),
+                long_description: "src/ changes must come from bot and not be marked WIP".into(),
+            }),
+        );
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["B"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Accepted,
+        );
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["C"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Rejected(HookRejectionInfo {
+                description: "Commit matched a blocked rule".into(),
+                long_description: "src/ changes must come from bot and not be marked WIP".into(),
+            }),
+        );
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    async fn test_file_size_exceeds(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let repo: BasicTestRepo = test_repo_factory::build_empty(fb).await?;
+
+        let changesets = create_from_dag_with_changes(
+            &ctx,
+            &repo,
+            r##"
+                Z-A-B
+            "##,
+            changes! {
+                "A" => |c| c.add_file("big", "0123456789"),
+                "B" => |c| c.add_file("small", "01"),
+            },
+        )
+        .await?;
+        bookmark(&ctx, &repo, "main")
+            .create_publishing(changesets["Z"])
+            .await?;
+
+        let hook = RulesHook::with_config(RulesConfig {
+            rule: Rule::FileSizeExceeds(5),
+            message: String::from("files must not exceed 5 bytes"),
+        })?;
+
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["A"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Rejected(HookRejectionInfo {
+                description: "Commit matched a blocked rule".into(),
+                long_description: "files must not exceed 5 bytes".into(),
+            }),
+        );
+        assert_eq!(
+            test_changeset_hook(
+                &ctx,
+                &repo,
+                &hook,
+                "main",
+                changesets["B"],
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?,
+            HookExecution::Accepted,
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::BTreeMap;
+
+use anyhow::anyhow;
+use anyhow::Context;
+use anyhow::Result;
+use context::CoreContext;
+use megarepo_configs::MergeMode;
+use megarepo_configs::Source;
+use megarepo_configs::SourceMappingRules;
+use megarepo_configs::SourceRevision;
+use megarepo_configs::SyncConfigVersion;
+use megarepo_configs::SyncTargetConfig;
+use megarepo_configs::Target;
+
+use crate::verification::verify_config;
+
+/// Builder for a single [`Source`] of a [`SyncTargetConfig`].
+///
+/// Construct via [`SyncTargetConfigBuilder::source`].
+#[derive(Clone, Debug)]
+pub struct SourceBuilder {
+    source_name: String,
+    repo_id: i64,
+    revision: SourceRevision,
+    name: Option<String>,
+    default_prefix: Option<String>,
+    linkfiles: BTreeMap<String, String>,
+    overrides: BTreeMap<String, Vec<String>>,
+    merge_mode: Option<MergeMode>,
+}
+
+impl SourceBuilder {
+    pub fn new(source_name: impl Into<String>, repo_id: i64, revision: SourceRevision) -> Self {
+        Self {
+            source_name: source_name.into(),
+            repo_id,
+            revision,
+            name: None,
+            default_prefix: None,
+            linkfiles: BTreeMap::new(),
+            overrides: BTreeMap::new(),
+            merge_mode: None,
+        }
+    }
+
+    /// Name of the original (git) repo this source comes from. Defaults to
+    /// the source name if not set.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn default_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.default_prefix = Some(prefix.into());
+        self
+    }
+
+    pub fn linkfile(mut self, link: impl Into<String>, target: impl Into<String>) -> Self {
+        self.linkfiles.insert(link.into(), target.into());
+        self
+    }
+
+    pub fn override_path(mut self, prefix: impl Into<String>, targets: Vec<String>) -> Self {
+        self.overrides.insert(prefix.into(), targets);
+        self
+    }
+
+    pub fn merge_mode(mut self, merge_mode: MergeMode) -> Self {
+        self.merge_mode = Some(merge_mode);
+        self
+    }
+
+    fn build(self) -> Result<Source> {
+        let default_prefix = self
+            .default_prefix
+            .ok_or_else(|| anyhow!("source '{}' is missing a default_prefix", self.source_name))?;
+        Ok(Source {
+            source_name: self.source_name.clone(),
+            repo_id: self.repo_id,
+            name: self.name.unwrap_or(self.source_name),
+            revision: self.revision,
+            mapping: SourceMappingRules {
+                default_prefix,
+                linkfiles: self.linkfiles.into_iter().collect(),
+                overrides: self.overrides.into_iter().collect(),
+            },
+            merge_mode: self.merge_mode,
+        })
+    }
+}
+
+/// Builder for a [`SyncTargetConfig`], intended for tooling that generates
+/// configs programmatically. `build` runs the same validation that
+/// `add_sync_target` applies via [`verify_config`], so a config that passes
+/// `build` is guaranteed not to be rejected for the reasons `verify_config`
+/// checks.
+#[derive(Clone, Debug)]
+pub struct SyncTargetConfigBuilder {
+    target: Target,
+    version: SyncConfigVersion,
+    sources: Vec<SourceBuilder>,
+}
+
+impl SyncTargetConfigBuilder {
+    pub fn new(target: Target, version: impl Into<SyncConfigVersion>) -> Self {
+        Self {
+            target,
+            version: version.into(),
+            sources: Vec::new(),
+        }
+    }
+
+    pub fn source(mut self, source: SourceBuilder) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Build and validate the `SyncTargetConfig`.
+    pub fn build(self, ctx: &CoreContext) -> Result<SyncTargetConfig> {
+        let sources = self
+            .sources
+            .into_iter()
+            .map(SourceBuilder::build)
+            .collect::<Result<Vec<_>>>()?;
+        let config = SyncTargetConfig {
+            target: self.target,
+            sources,
+            version: self.version,
+        };
+        verify_config(ctx, &config).context("SyncTargetConfig failed validation")?;
+        Ok(config)
+    }
+}
+
+/// Serialize a `SyncTargetConfig` using the same canonical (simplejson
+/// thrift) encoding that Mononoke itself uses to persist configs, so
+/// callers can compare or store configs byte-for-byte identically to what
+/// `add_sync_target` would write.
+pub fn to_canonical_json(config: &SyncTargetConfig) -> Result<String> {
+    String::from_utf8(fbthrift::simplejson_protocol::serialize(config).to_vec())
+        .context("failed to serialize SyncTargetConfig")
+}
+
+#[cfg(test)]
+mod test {
+    use fbinit::FacebookInit;
+
+    use super::*;
+
+    #[fbinit::test]
+    fn test_builder_happy_path(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+
+        let config = SyncTargetConfigBuilder::new(
+            Target {
+                repo_id: 1,
+                bookmark: "target".to_string(),
+            },
+            "version1",
+        )
+        .source(
+            SourceBuilder::new("source1", 1, SourceRevision::bookmark("hello".to_string()))
+                .default_prefix("pre/fix1")
+                .linkfile("link/source_1", "link_target_1"),
+        )
+        .build(&ctx)?;
+
+        assert_eq!(config.sources.len(), 1);
+        assert!(to_canonical_json(&config).is_ok());
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    fn test_builder_rejects_non_unique_source_names(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+
+        let result = SyncTargetConfigBuilder::new(
+            Target {
+                repo_id: 1,
+                bookmark: "target".to_string(),
+            },
+            "version1",
+        )
+        .source(
+            SourceBuilder::new("source1", 1, SourceRevision::bookmark("hello".to_string()))
+                .default_prefix("pre/fix1"),
+        )
+        .source(
+            SourceBuilder::new("source1", 1, SourceRevision::bookmark("hello".to_string()))
+                .default_prefix("pre/fix2"),
+        )
+        .build(&ctx);
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    fn test_builder_rejects_missing_default_prefix(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+
+        let result = SyncTargetConfigBuilder::new(
+            Target {
+                repo_id: 1,
+                bookmark: "target".to_string(),
+            },
+            "version1",
+        )
+        .source(SourceBuilder::new(
+            "source1",
+            1,
+            SourceRevision::bookmark("hello".to_string()),
+        ))
+        .build(&ctx);
+
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
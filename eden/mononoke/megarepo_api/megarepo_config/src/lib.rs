@@ -25,6 +25,7 @@ pub use megarepo_configs::SyncTargetConfig;
 pub use megarepo_configs::Target;
 pub use megarepo_configs::WithExtraMoveCommit;
 use megarepo_error::MegarepoError;
+mod builder;
 #[cfg(fbcode_build)]
 mod db;
 #[cfg(fbcode_build)]
@@ -34,6 +35,9 @@ mod oss;
 mod test_impl;
 mod verification;
 
+pub use builder::to_canonical_json;
+pub use builder::SourceBuilder;
+pub use builder::SyncTargetConfigBuilder;
 #[cfg(fbcode_build)]
 pub use facebook::CfgrMononokeMegarepoConfigs;
 use metaconfig_types::RepoConfig;
@@ -5,6 +5,7 @@
  * GNU General Public License version 2.
  */
 
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 use anyhow::anyhow;
@@ -15,7 +16,8 @@ use slog::warn;
 
 /// Verify the config
 pub fn verify_config(ctx: &CoreContext, config: &SyncTargetConfig) -> Result<()> {
-    verify_unique_source_names(ctx, config)
+    verify_unique_source_names(ctx, config)?;
+    verify_no_linkfile_collisions(ctx, config)
 }
 
 fn verify_unique_source_names(ctx: &CoreContext, config: &SyncTargetConfig) -> Result<()> {
@@ -42,6 +44,39 @@ fn verify_unique_source_names(ctx: &CoreContext, config: &SyncTargetConfig) -> R
     }
 }
 
+// Verifies that no two sources declare a linkfile at the same destination
+// path. This is the same check `add_sync_target`/`change_target_config`
+// perform on the move commits they generate, but catching it here means
+// tooling that generates configs gets an error at config validation time
+// rather than after doing the work of creating and uploading the move
+// commits.
+fn verify_no_linkfile_collisions(ctx: &CoreContext, config: &SyncTargetConfig) -> Result<()> {
+    let mut seen: HashMap<&String, &String> = HashMap::new();
+    let mut collisions = Vec::new();
+    for source in &config.sources {
+        for dst in source.mapping.linkfiles.keys() {
+            if let Some(existing_source_name) = seen.insert(dst, &source.source_name) {
+                collisions.push((
+                    dst.clone(),
+                    existing_source_name.clone(),
+                    source.source_name.clone(),
+                ));
+            }
+        }
+    }
+
+    if !collisions.is_empty() {
+        warn!(
+            ctx.logger(),
+            "SyncTargetConfig validation error: linkfile destination collisions: {:?}", collisions
+        );
+
+        Err(anyhow!("Linkfile destination collisions: {:?}", collisions))
+    } else {
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod verification_tests {
     use fbinit::FacebookInit;
@@ -131,4 +166,25 @@ mod verification_tests {
 
         Ok(())
     }
+
+    #[fbinit::test]
+    fn test_verify_no_linkfile_collisions(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+
+        let cfg = get_good_cfg();
+        assert!(verify_no_linkfile_collisions(&ctx, &cfg).is_ok());
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    fn test_verify_no_linkfile_collisions_bad(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+
+        let mut cfg = get_good_cfg();
+        cfg.sources[1].mapping.linkfiles = cfg.sources[0].mapping.linkfiles.clone();
+        assert!(verify_no_linkfile_collisions(&ctx, &cfg).is_err());
+
+        Ok(())
+    }
 }
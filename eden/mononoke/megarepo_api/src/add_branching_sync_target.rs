@@ -8,6 +8,7 @@
 use std::sync::Arc;
 
 use context::CoreContext;
+use megarepo_config::verify_config;
 use megarepo_config::MononokeMegarepoConfigs;
 use megarepo_config::SyncTargetConfig;
 use megarepo_config::Target;
@@ -48,6 +49,8 @@ impl<'a> AddBranchingSyncTarget<'a> {
         sync_target_config: SyncTargetConfig,
         branching_point: ChangesetId,
     ) -> Result<ChangesetId, MegarepoError> {
+        verify_config(ctx, &sync_target_config).map_err(MegarepoError::request)?;
+
         let repo = self
             .find_repo_by_id(ctx, sync_target_config.target.repo_id)
             .await?;
@@ -38,9 +38,9 @@ use crate::common::MegarepoOp;
 /// All the changes will be realized by removing and readding the source. In the
 /// future we can make this datastructure richer and include less disruptive
 /// methods of introducing small changes (like for example adding a linkfile).
-struct SyncTargetConfigChanges {
-    added: Vec<(Source, ChangesetId)>,
-    removed: Vec<(Source, ChangesetId)>,
+pub(crate) struct SyncTargetConfigChanges {
+    pub(crate) added: Vec<(Source, ChangesetId)>,
+    pub(crate) removed: Vec<(Source, ChangesetId)>,
 }
 
 /// Comparator used for sorting the sources.
@@ -50,7 +50,7 @@ fn cmp_by_name(a: &Source, b: &Source) -> Ordering {
 
 /// Compares the current state with the desired end state and returns the changes
 /// needed to apply to current state.
-fn diff_configs(
+pub(crate) fn diff_configs(
     old_config: &SyncTargetConfig,
     old_changesets: &BTreeMap<SourceName, ChangesetId>,
     new_config: &SyncTargetConfig,
@@ -65,6 +65,8 @@ use requests_table::LongRunningRequestsQueue;
 use slog::info;
 use slog::o;
 use slog::warn;
+use stage_sync_target_config::StageSyncTargetConfig;
+use stage_sync_target_config::SyncTargetConfigStagingPlan;
 
 mod add_branching_sync_target;
 #[cfg(test)]
@@ -81,6 +83,7 @@ mod megarepo_test_utils;
 mod remerge_source;
 #[cfg(test)]
 mod remerge_source_test;
+mod stage_sync_target_config;
 mod sync_changeset;
 
 pub trait Repo = BonsaiHgMappingRef
@@ -495,6 +498,25 @@ impl MegarepoApi {
             .await
     }
 
+    /// Stages a prospective `change_target_config()` call against the current
+    /// state of the target: resolves the new version's sources to concrete
+    /// changesets, and computes the paths that version would add or remove,
+    /// failing if any of the added paths would conflict with what's already
+    /// in the target. Performs no writes.
+    pub async fn stage_change_target_config(
+        &self,
+        ctx: &CoreContext,
+        target: Target,
+        new_version: SyncConfigVersion,
+    ) -> Result<SyncTargetConfigStagingPlan, MegarepoError> {
+        let stage_sync_target_config =
+            StageSyncTargetConfig::new(&self.megarepo_configs, &self.mononoke);
+
+        stage_sync_target_config
+            .run(ctx, &target, new_version)
+            .await
+    }
+
     /// Adds new sync target. Returs the commit hash of newly created target's head.
     pub async fn change_target_config(
         &self,
@@ -0,0 +1,190 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use context::CoreContext;
+use futures::stream::TryStreamExt;
+use megarepo_config::MononokeMegarepoConfigs;
+use megarepo_config::Source;
+use megarepo_config::SourceRevision;
+use megarepo_config::SyncConfigVersion;
+use megarepo_config::Target;
+use megarepo_error::MegarepoError;
+use megarepo_mapping::SourceName;
+use metaconfig_types::RepoConfigArc;
+use mononoke_api::Mononoke;
+use mononoke_types::ChangesetId;
+use mononoke_types::MPath;
+
+use crate::change_target_config::diff_configs;
+use crate::common::MegarepoOp;
+use crate::common::find_bookmark_and_value;
+use crate::common::find_target_bookmark_and_value;
+use crate::common::find_target_sync_config;
+
+/// The result of staging a new mapping version against the current state of
+/// a target: the changesets this version would actually merge in (resolved
+/// from each source's `SourceRevision`, rather than hand-picked by the
+/// operator), and the set of target paths that would start or stop being
+/// populated by this change.
+pub struct SyncTargetConfigStagingPlan {
+    pub new_version: SyncConfigVersion,
+    pub changesets_to_merge: BTreeMap<SourceName, ChangesetId>,
+    pub added_paths: BTreeSet<MPath>,
+    pub removed_paths: BTreeSet<MPath>,
+}
+
+/// Stages a prospective `change_target_config()` call: resolves the new
+/// version's sources to concrete changesets, diffs it against the config
+/// currently live on the target, computes which paths the diff would add
+/// or remove, and validates that none of the added paths already exist in
+/// the target outside of what's being removed. Performs no writes - the
+/// caller can use the resulting `changesets_to_merge` to drive an actual
+/// `change_target_config()` call once satisfied with the plan.
+pub struct StageSyncTargetConfig<'a> {
+    pub megarepo_configs: &'a Arc<dyn MononokeMegarepoConfigs>,
+    pub mononoke: &'a Arc<Mononoke>,
+}
+
+impl<'a> MegarepoOp for StageSyncTargetConfig<'a> {
+    fn mononoke(&self) -> &Arc<Mononoke> {
+        self.mononoke
+    }
+}
+
+impl<'a> StageSyncTargetConfig<'a> {
+    pub fn new(
+        megarepo_configs: &'a Arc<dyn MononokeMegarepoConfigs>,
+        mononoke: &'a Arc<Mononoke>,
+    ) -> Self {
+        Self {
+            megarepo_configs,
+            mononoke,
+        }
+    }
+
+    pub async fn run(
+        self,
+        ctx: &CoreContext,
+        target: &Target,
+        new_version: SyncConfigVersion,
+    ) -> Result<SyncTargetConfigStagingPlan, MegarepoError> {
+        let target_repo = self.find_repo_by_id(ctx, target.repo_id).await?;
+        let (_target_bookmark, target_location) =
+            find_target_bookmark_and_value(ctx, &target_repo, target).await?;
+
+        let (old_remapping_state, old_config) = find_target_sync_config(
+            ctx,
+            target_repo.repo(),
+            target_location,
+            target,
+            self.megarepo_configs,
+        )
+        .await?;
+
+        let repo_config = target_repo.repo().repo_config_arc();
+        let new_config = self
+            .megarepo_configs
+            .get_config_by_version(
+                ctx.clone(),
+                repo_config,
+                target.clone(),
+                new_version.clone(),
+            )
+            .await?;
+
+        let changesets_to_merge = self
+            .resolve_changesets_to_merge(ctx, &new_config.sources)
+            .await?;
+
+        let diff = diff_configs(
+            &old_config,
+            &old_remapping_state.latest_synced_changesets,
+            &new_config,
+            &changesets_to_merge,
+        )?;
+
+        let mut added_paths = BTreeSet::new();
+        for (source, cs_id) in &diff.added {
+            added_paths.extend(
+                self.paths_in_target_belonging_to_source(ctx, source, *cs_id)
+                    .await?,
+            );
+        }
+        let mut removed_paths = BTreeSet::new();
+        for (source, cs_id) in &diff.removed {
+            removed_paths.extend(
+                self.paths_in_target_belonging_to_source(ctx, source, *cs_id)
+                    .await?,
+            );
+        }
+
+        let target_cs = target_repo
+            .changeset(target_location)
+            .await?
+            .ok_or_else(|| {
+                MegarepoError::internal(anyhow!("programming error - target changeset not found!"))
+            })?;
+        let newly_occupied = added_paths
+            .difference(&removed_paths)
+            .cloned()
+            .collect::<Vec<_>>();
+        let mut conflicting = target_cs
+            .paths(newly_occupied.into_iter())
+            .await?
+            .map_err(MegarepoError::internal);
+        if let Some(path_context) = conflicting.try_next().await? {
+            return Err(MegarepoError::request(anyhow!(
+                "path {} cannot be added to the target by version {} - it's already present",
+                path_context.path(),
+                new_version,
+            )));
+        }
+
+        Ok(SyncTargetConfigStagingPlan {
+            new_version,
+            changesets_to_merge,
+            added_paths,
+            removed_paths,
+        })
+    }
+
+    /// Resolves each source's `SourceRevision` to a concrete changeset,
+    /// looking up bookmarks in the source repo as needed, rather than
+    /// requiring the caller to have done this resolution themselves.
+    async fn resolve_changesets_to_merge(
+        &self,
+        ctx: &CoreContext,
+        sources: &[Source],
+    ) -> Result<BTreeMap<SourceName, ChangesetId>, MegarepoError> {
+        let mut changesets_to_merge = BTreeMap::new();
+        for source in sources {
+            let cs_id = match &source.revision {
+                SourceRevision::hash(hash) => {
+                    ChangesetId::from_bytes(hash).map_err(MegarepoError::request)?
+                }
+                SourceRevision::bookmark(bookmark) => {
+                    let source_repo = self.find_repo_by_id(ctx, source.repo_id).await?;
+                    let (_bookmark, cs_id) =
+                        find_bookmark_and_value(ctx, &source_repo, bookmark).await?;
+                    cs_id
+                }
+                SourceRevision::UnknownField(_) => {
+                    return Err(MegarepoError::internal(anyhow!(
+                        "unexpected source revision!"
+                    )));
+                }
+            };
+            changesets_to_merge.insert(SourceName::new(&source.source_name), cs_id);
+        }
+        Ok(changesets_to_merge)
+    }
+}
@@ -1142,6 +1142,9 @@ mod test {
                     disable_acl_checker: false,
                     all_hooks_bypassed: false,
                     bypassed_commits_scuba_table: Some("commits_bypassed_hooks".to_string()),
+                    max_concurrent_hook_executions: None,
+                    circuit_breaker_failure_threshold: None,
+                    bypass_token_signing_key: None,
                 }),
                 bookmarks: vec![
                     BookmarkParams {
@@ -1175,6 +1178,8 @@ mod test {
                             bypass: Some(HookBypass::new_with_commit_msg("@allow_hook1".into())),
                             options: Some(r#"{"test": "abcde"}"#.to_string()),
                             log_only: false,
+                            timeout: None,
+                            critical: true,
                             strings: hashmap! {},
                             ints: hashmap! {},
                             ints_64: hashmap! {},
@@ -1190,6 +1195,8 @@ mod test {
                             bypass: None,
                             options: None,
                             log_only: true,
+                            timeout: None,
+                            critical: true,
                             strings: hashmap! {},
                             ints: hashmap! {
                                 "int1".into() => 44,
@@ -14,12 +14,18 @@ use anyhow::Result;
 use ascii::AsciiString;
 use bookmarks_types::BookmarkKey;
 use commitsync::CommonCommitSyncConfig as RawCommonCommitSyncConfig;
+use commitsync::RawBookmarkRedirectionNamespace;
 use itertools::Itertools;
+use metaconfig_types::BookmarkOrRegex;
+use metaconfig_types::BookmarkRedirectionMode;
+use metaconfig_types::BookmarkRedirectionNamespace;
 use metaconfig_types::CommitSyncConfig;
 use metaconfig_types::CommitSyncConfigVersion;
 use metaconfig_types::CommonCommitSyncConfig;
+use metaconfig_types::ComparableRegex;
 use metaconfig_types::DefaultSmallToLargeCommitSyncPathAction;
 use metaconfig_types::GitSubmodulesChangesAction;
+use metaconfig_types::PushvarPassthroughPolicy;
 use metaconfig_types::SmallRepoCommitSyncConfig;
 use metaconfig_types::SmallRepoGitSubmoduleConfig;
 use metaconfig_types::SmallRepoPermanentConfig;
@@ -27,11 +33,13 @@ use metaconfig_types::DEFAULT_GIT_SUBMODULE_METADATA_FILE_PREFIX;
 use mononoke_types::hash::GitSha1;
 use mononoke_types::NonRootMPath;
 use mononoke_types::RepositoryId;
+use regex::Regex;
 use repos::RawCommitSyncConfig;
 use repos::RawCommitSyncSmallRepoConfig;
 use repos::RawGitSubmodulesChangesAction;
 
 use crate::convert::Convert;
+use crate::errors::ConfigurationError;
 
 fn check_no_duplicate_small_repos(small_repos: &[RawCommitSyncSmallRepoConfig]) -> Result<()> {
     let small_repo_counts: HashMap<i32, u32> = {
@@ -277,10 +285,25 @@ impl Convert for RawCommonCommitSyncConfig {
                     .into_iter()
                     .map(|(k, v)| Ok((BookmarkKey::from_str(&k)?, BookmarkKey::from_str(&v)?)))
                     .collect::<Result<_>>()?;
+                let bookmark_redirection_namespaces = small_repo_config
+                    .bookmark_redirection_namespaces
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(Convert::convert)
+                    .collect::<Result<_>>()?;
+                let pushvar_passthrough_policy = match small_repo_config.allowed_redirected_pushvars
+                {
+                    Some(allowed) => {
+                        PushvarPassthroughPolicy::AllowList(allowed.into_iter().collect())
+                    }
+                    None => PushvarPassthroughPolicy::ForwardAll,
+                };
 
                 let config = SmallRepoPermanentConfig {
                     bookmark_prefix,
                     common_pushrebase_bookmarks_map,
+                    bookmark_redirection_namespaces,
+                    pushvar_passthrough_policy,
                 };
                 Ok((repo_id, config))
             })
@@ -297,3 +320,43 @@ impl Convert for RawCommonCommitSyncConfig {
         Ok(config)
     }
 }
+
+impl Convert for RawBookmarkRedirectionNamespace {
+    type Output = BookmarkRedirectionNamespace;
+
+    fn convert(self) -> Result<Self::Output> {
+        let bookmark = match (self.regex, self.name) {
+            (None, Some(name)) => BookmarkOrRegex::Bookmark(BookmarkKey::new(name)?),
+            (Some(regex), None) => match Regex::new(&regex) {
+                Ok(regex) => BookmarkOrRegex::Regex(ComparableRegex::new(regex)),
+                Err(err) => {
+                    return Err(ConfigurationError::InvalidConfig(format!(
+                        "invalid bookmark redirection namespace regex: {}",
+                        err
+                    ))
+                    .into());
+                }
+            },
+            _ => {
+                return Err(ConfigurationError::InvalidConfig(
+                    "bookmark redirection namespace needs to specify regex xor name".into(),
+                )
+                .into());
+            }
+        };
+
+        let mode = match self.mode.as_str() {
+            "redirected" => BookmarkRedirectionMode::Redirected,
+            "local" => BookmarkRedirectionMode::Local,
+            other => {
+                return Err(ConfigurationError::InvalidConfig(format!(
+                    "invalid bookmark redirection mode: {} (expected \"redirected\" or \"local\")",
+                    other
+                ))
+                .into());
+            }
+        };
+
+        Ok(BookmarkRedirectionNamespace { bookmark, mode })
+    }
+}
@@ -6,6 +6,7 @@
  */
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use anyhow::anyhow;
 use anyhow::Context;
@@ -128,6 +129,15 @@ impl Convert for RawHookManagerParams {
             disable_acl_checker: self.disable_acl_checker,
             all_hooks_bypassed: self.all_hooks_bypassed,
             bypassed_commits_scuba_table: self.bypassed_commits_scuba_table,
+            max_concurrent_hook_executions: self
+                .max_concurrent_hook_executions
+                .map(|v| v.try_into())
+                .transpose()?,
+            circuit_breaker_failure_threshold: self
+                .circuit_breaker_failure_threshold
+                .map(|v| v.try_into())
+                .transpose()?,
+            bypass_token_signing_key: self.bypass_token_signing_key,
         })
     }
 }
@@ -162,6 +172,8 @@ impl Convert for RawHookConfig {
             bypass,
             options: self.config_json,
             log_only: self.log_only.unwrap_or_default(),
+            timeout: self.timeout_ms.map(|ms| Duration::from_millis(ms as u64)),
+            critical: self.critical.unwrap_or(true),
             strings: self.config_strings.unwrap_or_default(),
             ints: self.config_ints.unwrap_or_default(),
             ints_64: self.config_ints_64.unwrap_or_default(),
@@ -514,6 +514,16 @@ pub struct HookManagerParams {
     pub all_hooks_bypassed: bool,
     /// Scuba table for bypassed commits logging.
     pub bypassed_commits_scuba_table: Option<String>,
+    /// Maximum number of hook executions to run concurrently per
+    /// `run_hooks_for_bookmark` call.  If `None`, a built-in default is used.
+    pub max_concurrent_hook_executions: Option<usize>,
+    /// Number of consecutive execution failures a non-critical hook can
+    /// accumulate before its circuit breaker trips.  If `None`, the circuit
+    /// breaker is disabled.
+    pub circuit_breaker_failure_threshold: Option<u32>,
+    /// Key used to verify signed, time-limited bypass tokens passed via the
+    /// `hook_bypass_token` pushvar.  If `None`, bypass tokens are rejected.
+    pub bypass_token_signing_key: Option<String>,
 }
 
 /// Configuration might be done for a single bookmark or for all bookmarks matching a regex
@@ -651,7 +661,7 @@ impl HookBypass {
 }
 
 /// Configs that are being passed to the hook during runtime
-#[derive(Debug, Default, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
 pub struct HookConfig {
     /// An optional way to bypass a hook
     pub bypass: Option<HookBypass>,
@@ -659,6 +669,16 @@ pub struct HookConfig {
     pub options: Option<String>,
     /// Whether this hook is log-only
     pub log_only: bool,
+    /// Maximum time the hook is allowed to run for.  If it runs for longer,
+    /// the execution is treated as a failure.  If `None`, no timeout is
+    /// enforced.
+    pub timeout: Option<Duration>,
+    /// Whether this hook is critical.  Non-critical hooks that persistently
+    /// fail are automatically disabled by the circuit breaker (see
+    /// `HookManagerParams::circuit_breaker_failure_threshold`), logging a
+    /// warning instead of blocking pushes.  Critical hooks are never
+    /// disabled this way.
+    pub critical: bool,
 
     // Deprecated config options
     /// Map of config to it's value. Values here are strings
@@ -675,6 +695,24 @@ pub struct HookConfig {
     pub int_64_lists: HashMap<String, Vec<i64>>,
 }
 
+impl Default for HookConfig {
+    fn default() -> Self {
+        Self {
+            bypass: None,
+            options: None,
+            log_only: false,
+            timeout: None,
+            critical: true,
+            strings: HashMap::new(),
+            ints: HashMap::new(),
+            ints_64: HashMap::new(),
+            string_lists: HashMap::new(),
+            int_lists: HashMap::new(),
+            int_64_lists: HashMap::new(),
+        }
+    }
+}
+
 impl HookConfig {
     /// Parse hook config options into a deserializable struct.
     pub fn parse_options<'a, T: serde::Deserialize<'a>>(&'a self) -> Result<T> {
@@ -1468,6 +1506,110 @@ pub struct SmallRepoPermanentConfig {
     /// between the large repos and some of the small repos (e.g: a small repo imported
     /// from git may want to sync its `heads/master` to `master` in a large repo)
     pub common_pushrebase_bookmarks_map: HashMap<BookmarkKey, BookmarkKey>,
+    /// Rules classifying namespaces of bookmarks in this small repo as either
+    /// push-redirected to the large repo, or handled locally.  If empty, all
+    /// bookmarks are redirected, preserving the historical all-or-nothing
+    /// behaviour.
+    pub bookmark_redirection_namespaces: Vec<BookmarkRedirectionNamespace>,
+    /// Policy controlling which pushvars are forwarded from a small-repo
+    /// operation to the corresponding redirected large-repo operation.
+    pub pushvar_passthrough_policy: PushvarPassthroughPolicy,
+}
+
+/// Policy controlling which pushvars are forwarded from a small-repo
+/// operation to the corresponding large-repo operation when the operation is
+/// push-redirected.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub enum PushvarPassthroughPolicy {
+    /// Forward every pushvar unchanged, preserving the historical
+    /// all-or-nothing behaviour.
+    #[default]
+    ForwardAll,
+    /// Forward only pushvars whose name is in this set; strip all others
+    /// (e.g. hook-bypass pushvars) before the op reaches the large repo.
+    AllowList(HashSet<String>),
+}
+
+impl PushvarPassthroughPolicy {
+    /// Returns whether `pushvar_name` should be forwarded to the large repo
+    /// under this policy.
+    pub fn allows(&self, pushvar_name: &str) -> bool {
+        match self {
+            PushvarPassthroughPolicy::ForwardAll => true,
+            PushvarPassthroughPolicy::AllowList(allowed) => allowed.contains(pushvar_name),
+        }
+    }
+}
+
+/// Whether pushes to a bookmark namespace should be redirected to the large
+/// repo, or handled locally in the small repo.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum BookmarkRedirectionMode {
+    /// Pushes to this bookmark namespace are redirected to the large repo.
+    Redirected,
+    /// Pushes to this bookmark namespace are handled locally in the small repo.
+    Local,
+}
+
+/// A rule classifying a namespace of bookmarks (by exact name or regex) as
+/// either redirected to the large repo, or kept local to the small repo.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BookmarkRedirectionNamespace {
+    /// The bookmark or bookmark namespace this rule applies to.
+    pub bookmark: BookmarkOrRegex,
+    /// The redirection mode for bookmarks matching this namespace.
+    pub mode: BookmarkRedirectionMode,
+}
+
+impl SmallRepoPermanentConfig {
+    /// Returns the redirection mode that applies to `bookmark`.
+    ///
+    /// If `bookmark_redirection_namespaces` is empty, every bookmark is
+    /// redirected, preserving the historical all-or-nothing behaviour. If
+    /// it's non-empty, `bookmark` must match the namespace of exactly one
+    /// mode, otherwise this is a configuration error.
+    pub fn redirection_mode_for_bookmark(
+        &self,
+        bookmark: &BookmarkKey,
+    ) -> Result<BookmarkRedirectionMode> {
+        redirection_mode_for_bookmark(&self.bookmark_redirection_namespaces, bookmark)
+    }
+}
+
+/// Returns the redirection mode that `bookmark` matches amongst `namespaces`.
+///
+/// If `namespaces` is empty, every bookmark is redirected, preserving the
+/// historical all-or-nothing behaviour. If it's non-empty, `bookmark` must
+/// match the namespace of exactly one mode, otherwise this is a
+/// configuration error.
+pub fn redirection_mode_for_bookmark(
+    namespaces: &[BookmarkRedirectionNamespace],
+    bookmark: &BookmarkKey,
+) -> Result<BookmarkRedirectionMode> {
+    if namespaces.is_empty() {
+        return Ok(BookmarkRedirectionMode::Redirected);
+    }
+
+    let matched_modes: HashSet<_> = namespaces
+        .iter()
+        .filter(|namespace| namespace.bookmark.matches(bookmark))
+        .map(|namespace| namespace.mode)
+        .collect();
+
+    match matched_modes.len() {
+        0 => Err(anyhow!(
+            "Bookmark '{}' does not match any push redirection namespace",
+            bookmark
+        )),
+        1 => Ok(matched_modes
+            .into_iter()
+            .next()
+            .expect("just checked len == 1")),
+        _ => Err(anyhow!(
+            "Bookmark '{}' matches push redirection namespaces with conflicting modes",
+            bookmark
+        )),
+    }
 }
 
 /// Source Control Service options
@@ -22,6 +22,7 @@ use bonsai_svnrev_mapping::BonsaiSvnrevMappingRef;
 use bookmarks::BookmarkKey;
 use bytes::Bytes;
 use changeset_info::ChangesetInfo;
+use churn::DirectoryChurnInfo;
 use chrono::DateTime;
 use chrono::FixedOffset;
 use cloned::cloned;
@@ -30,10 +31,12 @@ use commit_graph::CommitGraphArc;
 use commit_graph::CommitGraphRef;
 use commit_graph::LinearAncestorsStreamBuilder;
 use context::CoreContext;
+use deleted_manifest::DeletedFilesPage;
 use deleted_manifest::DeletedManifestOps;
 use deleted_manifest::RootDeletedManifestIdCommon;
 use deleted_manifest::RootDeletedManifestV2Id;
 use derived_data_manager::BonsaiDerivable;
+use derived_data_manager::DeriveWithDeadlineOutcome;
 use fsnodes::RootFsnodeId;
 use futures::future::try_join;
 use futures::stream;
@@ -121,6 +124,7 @@ pub struct ChangesetContext {
     id: ChangesetId,
     bonsai_changeset: LazyShared<Result<BonsaiChangeset, MononokeError>>,
     changeset_info: LazyShared<Result<ChangesetInfo, MononokeError>>,
+    directory_churn: LazyShared<Result<DirectoryChurnInfo, MononokeError>>,
     root_unode_manifest_id: LazyShared<Result<RootUnodeManifestId, MononokeError>>,
     root_fsnode_id: LazyShared<Result<RootFsnodeId, MononokeError>>,
     root_skeleton_manifest_id: LazyShared<Result<RootSkeletonManifestId, MononokeError>>,
@@ -178,6 +182,7 @@ impl ChangesetContext {
     pub(crate) fn new(repo_ctx: RepoContext, id: ChangesetId) -> Self {
         let bonsai_changeset = LazyShared::new_empty();
         let changeset_info = LazyShared::new_empty();
+        let directory_churn = LazyShared::new_empty();
         let root_unode_manifest_id = LazyShared::new_empty();
         let root_fsnode_id = LazyShared::new_empty();
         let root_skeleton_manifest_id = LazyShared::new_empty();
@@ -187,6 +192,7 @@ impl ChangesetContext {
             repo_ctx,
             id,
             changeset_info,
+            directory_churn,
             bonsai_changeset,
             root_unode_manifest_id,
             root_fsnode_id,
@@ -311,6 +317,22 @@ impl ChangesetContext {
         }
     }
 
+    /// Derive a derivable data type for this changeset, but give up after `deadline` instead of
+    /// blocking until derivation of the changeset and all its underived ancestors completes.
+    ///
+    /// This is for callers (e.g. interactive queries) that would rather get a quick, typed
+    /// "not ready yet" response than block indefinitely on a commit that happens to be underived.
+    pub async fn derive_with_deadline<Derivable: BonsaiDerivable>(
+        &self,
+        deadline: std::time::Duration,
+    ) -> Result<DeriveWithDeadlineOutcome<Derivable>, MononokeError> {
+        let repo_derived_data = self.repo_ctx.repo().repo_derived_data_arc();
+        repo_derived_data
+            .derive_with_deadline::<Derivable>(self.ctx(), self.id, deadline)
+            .await
+            .map_err(MononokeError::from)
+    }
+
     pub(crate) async fn root_unode_manifest_id(
         &self,
     ) -> Result<RootUnodeManifestId, MononokeError> {
@@ -547,6 +569,30 @@ impl ChangesetContext {
         Ok(self.deleted_paths_impl(self.root_deleted_manifest_v2_id().await?, paths))
     }
 
+    /// List files that were deleted at some point in the history of paths under `prefix`,
+    /// ordered by path and paginated via `after`/`limit`.
+    ///
+    /// Unlike `deleted_paths`, this doesn't require the caller to already know which paths to
+    /// ask about: it's meant for "resurrect a file" workflows that need to discover what used to
+    /// live under a prefix in the first place.
+    pub async fn list_deleted_files(
+        &self,
+        prefix: MPath,
+        after: Option<MPath>,
+        limit: usize,
+    ) -> Result<DeletedFilesPage, MononokeError> {
+        let root = self.root_deleted_manifest_v2_id().await?;
+        Ok(root
+            .list_deleted_files(
+                self.ctx(),
+                self.repo_ctx().repo().repo_blobstore(),
+                prefix,
+                after,
+                limit,
+            )
+            .await?)
+    }
+
     /// Get the `BonsaiChangeset` information for this changeset.
     async fn bonsai_changeset(&self) -> Result<BonsaiChangeset, MononokeError> {
         self.bonsai_changeset
@@ -571,6 +617,15 @@ impl ChangesetContext {
         }
     }
 
+    /// Get the per-top-level-directory file/byte change counts (`DirectoryChurnInfo`) for
+    /// this changeset, powering ownership/churn analytics without scanning file changes at
+    /// query time.
+    pub async fn directory_churn(&self) -> Result<DirectoryChurnInfo, MononokeError> {
+        self.directory_churn
+            .get_or_init(|| self.derive::<DirectoryChurnInfo>())
+            .await
+    }
+
     /// The IDs of the parents of the changeset.
     pub async fn parents(&self) -> Result<Vec<ChangesetId>, MononokeError> {
         Ok(self.changeset_info().await?.parents().collect())
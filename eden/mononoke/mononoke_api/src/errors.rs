@@ -10,6 +10,7 @@ use std::convert::Infallible;
 use std::error::Error as StdError;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::Error;
 use blame::BlameError;
@@ -25,6 +26,7 @@ use megarepo_error::MegarepoError;
 use mononoke_types::path::MPath;
 use mononoke_types::ChangesetId;
 use pushrebase::PushrebaseError;
+use rate_limiting::RateLimitReason;
 use repo_authorization::AuthorizationError;
 use thiserror::Error;
 
@@ -98,10 +100,28 @@ pub enum MononokeError {
     NotAvailable(String),
     #[error("permission denied: {0}")]
     AuthorizationError(String),
+    #[error("throttled: {reason} (retry after {retry_after:?})")]
+    Throttled {
+        reason: String,
+        retry_after: Duration,
+    },
     #[error("internal error: {0}")]
     InternalError(#[source] InternalError),
 }
 
+impl From<RateLimitReason> for MononokeError {
+    fn from(reason: RateLimitReason) -> Self {
+        let retry_after = match &reason {
+            RateLimitReason::RateLimitedMetric(_metric, window) => *window,
+            RateLimitReason::LoadShedMetric(..) => Duration::from_secs(1),
+        };
+        MononokeError::Throttled {
+            reason: reason.to_string(),
+            retry_after,
+        }
+    }
+}
+
 impl From<Error> for MononokeError {
     fn from(e: Error) -> Self {
         MononokeError::InternalError(InternalError(Arc::new(e)))
@@ -90,6 +90,7 @@ use git_symbolic_refs::GitSymbolicRefs;
 use git_types::MappedGitCommitId;
 use hook_manager::manager::HookManager;
 use hook_manager::manager::HookManagerArc;
+use hook_outcome_store::HookOutcomeStore;
 use itertools::Itertools;
 use live_commit_sync_config::LiveCommitSyncConfig;
 use mercurial_derivation::MappedHgChangesetId;
@@ -158,12 +159,16 @@ use crate::tree::TreeContext;
 use crate::tree::TreeId;
 use crate::xrepo::CandidateSelectionHintArgs;
 
+pub mod admission_control;
+pub mod compare;
 pub mod create_bookmark;
 pub mod create_changeset;
 pub mod delete_bookmark;
 pub mod git;
+pub mod hook_outcomes;
 pub mod land_stack;
 pub mod move_bookmark;
+pub mod run_hooks;
 pub mod update_submodule_expansion;
 
 pub use git::upload_non_blob_git_object;
@@ -286,6 +291,9 @@ pub struct Repo {
     #[facet]
     pub hook_manager: HookManager,
 
+    #[facet]
+    pub hook_outcome_store: dyn HookOutcomeStore,
+
     #[facet]
     pub repo_handler_base: RepoHandlerBase,
 
@@ -338,12 +346,22 @@ async fn maybe_push_redirector(
         let large_repo = repos.get_by_id(large_repo_id.id()).ok_or_else(|| {
             MononokeError::InvalidRequest(format!("Large repo '{}' not found", large_repo_id))
         })?;
+        let small_repo_config = base.common_commit_sync_config.small_repos.get(&repo.repoid());
+        let bookmark_redirection_namespaces = small_repo_config
+            .map(|small_repo_config| small_repo_config.bookmark_redirection_namespaces.clone())
+            .unwrap_or_default();
+        let pushvar_passthrough_policy = small_repo_config
+            .map(|small_repo_config| small_repo_config.pushvar_passthrough_policy.clone())
+            .unwrap_or_default();
         Ok(Some(
             PushRedirectorArgs::new(
                 large_repo,
                 repo.clone(),
                 base.synced_commit_mapping.clone(),
                 base.target_repo_dbs.clone(),
+                bookmark_redirection_namespaces,
+                pushvar_passthrough_policy,
+                base.cache_handler_factory.clone(),
             )
             .into_push_redirector(
                 ctx,
@@ -799,6 +817,11 @@ impl RepoContext {
         self.repo.hook_manager_arc()
     }
 
+    /// The hook outcome store for the referenced repository.
+    pub fn hook_outcome_store(&self) -> Arc<dyn HookOutcomeStore> {
+        self.repo.hook_outcome_store_arc()
+    }
+
     /// The base for push redirection logic for this repo
     pub fn maybe_push_redirector_base(&self) -> Option<&PushRedirectorBase> {
         self.repo
@@ -808,6 +831,22 @@ impl RepoContext {
             .map(AsRef::as_ref)
     }
 
+    /// The push redirector for this repo, if `bookmark` should be
+    /// push-redirected to the large repo. Returns `None` if there is no
+    /// redirector configured for this repo, or if `bookmark` is configured
+    /// to be handled locally.
+    pub fn push_redirector_for_bookmark(
+        &self,
+        bookmark: &BookmarkKey,
+    ) -> Result<Option<&Arc<PushRedirector<Repo>>>, MononokeError> {
+        match self.push_redirector.as_ref() {
+            Some(redirector) if redirector.should_redirect_bookmark(bookmark)? => {
+                Ok(Some(redirector))
+            }
+            _ => Ok(None),
+        }
+    }
+
     /// The configuration for the referenced repository.
     pub fn config(&self) -> &RepoConfig {
         self.repo.config()
@@ -0,0 +1,54 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use rate_limiting::Metric;
+
+use crate::errors::MononokeError;
+use crate::repo::RepoContext;
+
+/// Priority class for an admission-control check. `Low` priority requests are
+/// shed first, so that heavy, best-effort API consumers (e.g. bulk tooling)
+/// back off before interactive traffic is affected.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Priority {
+    /// Interactive requests made on behalf of a human waiting for a result.
+    High,
+    /// Normal automation: the default for most API calls.
+    Normal,
+    /// Best-effort bulk/background work that should be the first to be shed
+    /// under load.
+    Low,
+}
+
+impl RepoContext {
+    /// Check whether a call to a `RepoContext` entry point should be
+    /// admitted, given the caller's identities (taken from the current
+    /// session) and the `Metric` the entry point contributes to.
+    ///
+    /// This is a thin wrapper around the session's pluggable
+    /// [`rate_limiting::RateLimiter`]: high priority calls only respect load
+    /// shedding (so interactive users aren't rate limited away), while
+    /// normal and low priority calls are also subject to the metric's rate
+    /// limit. Callers that are throttled get back a typed
+    /// [`MononokeError::Throttled`] with a `retry_after` they can surface to
+    /// clients, rather than the whole service degrading under load.
+    pub async fn check_admission(
+        &self,
+        metric: Metric,
+        priority: Priority,
+    ) -> Result<(), MononokeError> {
+        let session = self.ctx().session();
+
+        session.check_load_shed()?;
+
+        if priority != Priority::High {
+            session.check_rate_limit(metric).await?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use derived_data_manager::DerivableType;
+use futures::stream;
+use futures::stream::Stream;
+use futures::stream::TryStreamExt;
+use mononoke_types::ChangesetId;
+
+use crate::errors::MononokeError;
+use crate::repo::RepoContext;
+
+/// One entry in the result of [`RepoContext::compare_with`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RepoCompareEntry {
+    /// The bookmark exists in both repos and (under the name mapping)
+    /// points at the same head commit.
+    Matching {
+        bookmark: String,
+        cs_id: ChangesetId,
+    },
+    /// The bookmark exists in both repos but points at different head
+    /// commits.
+    DivergentHead {
+        bookmark: String,
+        cs_id: ChangesetId,
+        other_cs_id: ChangesetId,
+    },
+    /// The bookmark exists in this repo but not (under the mapped name) in
+    /// the other repo.
+    MissingInOther {
+        bookmark: String,
+        cs_id: ChangesetId,
+    },
+    /// The bookmark exists in the other repo but not (under the mapped
+    /// name) in this repo.
+    MissingInThis {
+        bookmark: String,
+        other_cs_id: ChangesetId,
+    },
+    /// The bookmark's head commit matches on both sides, but the other
+    /// repo has not yet derived one of the requested derived data types
+    /// for it.
+    DerivedDataMissing {
+        bookmark: String,
+        cs_id: ChangesetId,
+        derivable_type: DerivableType,
+    },
+}
+
+impl RepoContext {
+    /// Compare this repo against `other` (e.g. a mirror of this repo),
+    /// streaming one entry per public bookmark, including bookmarks that
+    /// match on both sides.
+    ///
+    /// `bookmark_name_mapping` translates a bookmark name in this repo to
+    /// the name it is expected to have in `other` (use the identity
+    /// function if both repos use the same bookmark names). For every
+    /// bookmark whose head commit matches on both sides, `derived_data_types`
+    /// are additionally checked for presence in `other`, so that mirror
+    /// operators can distinguish a truly divergent bookmark from one that
+    /// the mirror simply hasn't finished deriving data for yet.
+    pub async fn compare_with<'a>(
+        &'a self,
+        other: &'a RepoContext,
+        bookmark_name_mapping: impl Fn(&str) -> String,
+        derived_data_types: &'a [DerivableType],
+    ) -> Result<impl Stream<Item = Result<RepoCompareEntry, MononokeError>> + 'a, MononokeError>
+    {
+        let this_bookmarks: HashMap<String, ChangesetId> = self
+            .list_bookmarks(false, None, None, None)
+            .await?
+            .try_collect()
+            .await?;
+        let other_bookmarks: HashMap<String, ChangesetId> = other
+            .list_bookmarks(false, None, None, None)
+            .await?
+            .try_collect()
+            .await?;
+
+        let mut entries = Vec::new();
+        let mut seen_in_other = HashSet::new();
+
+        for (bookmark, cs_id) in &this_bookmarks {
+            let mapped_name = bookmark_name_mapping(bookmark);
+            match other_bookmarks.get(&mapped_name) {
+                None => entries.push(RepoCompareEntry::MissingInOther {
+                    bookmark: bookmark.clone(),
+                    cs_id: *cs_id,
+                }),
+                Some(other_cs_id) => {
+                    seen_in_other.insert(mapped_name);
+                    if cs_id == other_cs_id {
+                        entries.push(RepoCompareEntry::Matching {
+                            bookmark: bookmark.clone(),
+                            cs_id: *cs_id,
+                        });
+                        for derivable_type in derived_data_types {
+                            if !other
+                                .is_derived(other.ctx(), *other_cs_id, *derivable_type)
+                                .await?
+                            {
+                                entries.push(RepoCompareEntry::DerivedDataMissing {
+                                    bookmark: bookmark.clone(),
+                                    cs_id: *cs_id,
+                                    derivable_type: *derivable_type,
+                                });
+                            }
+                        }
+                    } else {
+                        entries.push(RepoCompareEntry::DivergentHead {
+                            bookmark: bookmark.clone(),
+                            cs_id: *cs_id,
+                            other_cs_id: *other_cs_id,
+                        });
+                    }
+                }
+            }
+        }
+
+        for (other_bookmark, other_cs_id) in other_bookmarks {
+            if !seen_in_other.contains(&other_bookmark) {
+                entries.push(RepoCompareEntry::MissingInThis {
+                    bookmark: other_bookmark,
+                    other_cs_id,
+                });
+            }
+        }
+
+        Ok(stream::iter(entries.into_iter().map(Ok)))
+    }
+}
@@ -5,6 +5,7 @@
  * GNU General Public License version 2.
  */
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use anyhow::format_err;
@@ -49,7 +50,7 @@ impl RepoContext {
             .with_pushvars(pushvars);
             op.log_new_public_commits_to_scribe()
         }
-        let create_op = if let Some(redirector) = self.push_redirector.as_ref() {
+        let create_op = if let Some(redirector) = self.push_redirector_for_bookmark(bookmark)? {
             let large_bookmark = redirector.small_to_large_bookmark(bookmark).await?;
             if &large_bookmark == bookmark {
                 return Err(MononokeError::InvalidRequest(format!(
@@ -89,10 +90,20 @@ impl RepoContext {
         pushvars: Option<&HashMap<String, Bytes>>,
         affected_changesets_limit: Option<usize>,
     ) -> Result<(), MononokeError> {
+        let redirector = self.push_redirector_for_bookmark(bookmark)?;
+        let filtered_pushvars: Option<Cow<'_, HashMap<String, Bytes>>> = match redirector {
+            Some(redirector) => redirector.filter_pushvars(pushvars),
+            None => pushvars.map(Cow::Borrowed),
+        };
         let create_op = self
-            .create_bookmark_op(bookmark, target, pushvars, affected_changesets_limit)
+            .create_bookmark_op(
+                bookmark,
+                target,
+                filtered_pushvars.as_deref(),
+                affected_changesets_limit,
+            )
             .await?;
-        if let Some(redirector) = self.push_redirector.as_ref() {
+        if let Some(redirector) = redirector {
             let ctx = self.ctx();
             let log_id = create_op
                 .run(
@@ -43,6 +43,7 @@ use mononoke_types::FileChange;
 use mononoke_types::GitLfs;
 use mononoke_types::MPathElement;
 use mononoke_types::NonRootMPath;
+use rate_limiting::Metric;
 use repo_authorization::RepoWriteOperation;
 use repo_blobstore::RepoBlobstore;
 use repo_blobstore::RepoBlobstoreRef;
@@ -57,6 +58,7 @@ use crate::errors::MononokeError;
 use crate::file::FileId;
 use crate::file::FileType;
 use crate::path::MononokePathPrefixes;
+use crate::repo::admission_control::Priority;
 use crate::repo::RepoContext;
 use crate::specifiers::ChangesetSpecifier;
 
@@ -707,6 +709,28 @@ impl RepoContext {
             .map_err(|e| anyhow!("Expected 1 changeset, but created {}", e.len()).into())
     }
 
+    /// Create a new merge changeset from two parents.
+    ///
+    /// This is `create_changeset(vec![p1, p2], info, resolutions, bubble)`
+    /// under a name that makes the two-parent merge case explicit at call
+    /// sites. Merge conflicts (paths modified differently by each parent
+    /// and not covered by `resolutions`) are still detected, as
+    /// `create_changeset_stack` already checks them for every new
+    /// changeset, and are returned as a structured
+    /// `MononokeError::MergeConflicts` before any blobs are uploaded or the
+    /// bonsai changeset is written.
+    pub async fn create_merge_changeset(
+        &self,
+        p1: ChangesetId,
+        p2: ChangesetId,
+        info: CreateInfo,
+        resolutions: BTreeMap<MPath, CreateChange>,
+        bubble: Option<&Bubble>,
+    ) -> Result<(SortedVectorMap<String, Vec<u8>>, ChangesetContext), MononokeError> {
+        self.create_changeset(vec![p1, p2], info, resolutions, bubble)
+            .await
+    }
+
     /// Create a new stack of changesets in the repository.
     ///
     /// The first new changeset is created with the given metadata by unioning the
@@ -726,6 +750,8 @@ impl RepoContext {
         bubble: Option<&Bubble>,
     ) -> Result<Vec<(SortedVectorMap<String, Vec<u8>>, ChangesetContext)>, MononokeError> {
         self.start_write()?;
+        self.check_admission(Metric::Commits, Priority::Normal)
+            .await?;
         self.authorization_context()
             .require_repo_write(self.ctx(), self.repo(), RepoWriteOperation::CreateChangeset)
             .await?;
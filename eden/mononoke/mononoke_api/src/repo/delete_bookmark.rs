@@ -5,6 +5,7 @@
  * GNU General Public License version 2.
  */
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use anyhow::Context;
@@ -57,7 +58,7 @@ impl RepoContext {
             )
             .with_pushvars(pushvars)
         }
-        let delete_op = if let Some(redirector) = self.push_redirector.as_ref() {
+        let delete_op = if let Some(redirector) = self.push_redirector_for_bookmark(bookmark)? {
             let large_bookmark = redirector.small_to_large_bookmark(bookmark).await?;
             if &large_bookmark == bookmark {
                 return Err(MononokeError::InvalidRequest(format!(
@@ -83,10 +84,15 @@ impl RepoContext {
         old_target: Option<ChangesetId>,
         pushvars: Option<&HashMap<String, Bytes>>,
     ) -> Result<(), MononokeError> {
+        let redirector = self.push_redirector_for_bookmark(bookmark)?;
+        let filtered_pushvars: Option<Cow<'_, HashMap<String, Bytes>>> = match redirector {
+            Some(redirector) => redirector.filter_pushvars(pushvars),
+            None => pushvars.map(Cow::Borrowed),
+        };
         let delete_op = self
-            .delete_bookmark_op(bookmark, old_target, pushvars)
+            .delete_bookmark_op(bookmark, old_target, filtered_pushvars.as_deref())
             .await?;
-        if let Some(redirector) = self.push_redirector.as_ref() {
+        if let Some(redirector) = redirector {
             let ctx = self.ctx();
             let log_id = delete_op
                 .run(self.ctx(), self.authorization_context(), &redirector.repo)
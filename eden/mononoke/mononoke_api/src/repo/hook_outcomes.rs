@@ -0,0 +1,26 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use hook_outcome_store::HookOutcomeRecord;
+use mononoke_types::ChangesetId;
+
+use crate::errors::MononokeError;
+use crate::repo::RepoContext;
+
+impl RepoContext {
+    /// Fetch the persisted hook outcomes recorded for a changeset, e.g. to
+    /// explain to a user why a past push was rejected.
+    pub async fn hook_outcomes_for_changeset(
+        &self,
+        cs_id: ChangesetId,
+    ) -> Result<Vec<HookOutcomeRecord>, MononokeError> {
+        Ok(self
+            .hook_outcome_store()
+            .get_outcomes_for_changeset(self.ctx(), cs_id)
+            .await?)
+    }
+}
@@ -156,7 +156,7 @@ impl RepoContext {
             .try_collect()
             .await?;
 
-        let outcome = if let Some(redirector) = self.push_redirector.as_ref() {
+        let outcome = if let Some(redirector) = self.push_redirector_for_bookmark(&bookmark)? {
             // run hooks on small repo
             bookmarks_movement::run_hooks(
                 ctx,
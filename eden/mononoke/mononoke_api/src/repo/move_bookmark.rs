@@ -5,6 +5,7 @@
  * GNU General Public License version 2.
  */
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 use anyhow::format_err;
@@ -38,7 +39,7 @@ impl RepoContext {
         allow_non_fast_forward: bool,
         pushvars: Option<&'a HashMap<String, Bytes>>,
         affected_changesets_limit: Option<usize>,
-    ) -> Result<UpdateBookmarkOp<'a>, MononokeError> {
+    ) -> Result<(UpdateBookmarkOp<'a>, Option<ChangesetId>), MononokeError> {
         self.start_write()?;
 
         // We need to find out where the bookmark currently points to in order
@@ -81,7 +82,7 @@ impl RepoContext {
             .with_pushvars(pushvars);
             op.log_new_public_commits_to_scribe()
         }
-        let op = if let Some(redirector) = self.push_redirector.as_ref() {
+        let op = if let Some(redirector) = self.push_redirector_for_bookmark(bookmark)? {
             let large_bookmark = redirector.small_to_large_bookmark(bookmark).await?;
             if &large_bookmark == bookmark {
                 return Err(MononokeError::InvalidRequest(format!(
@@ -109,22 +110,28 @@ impl RepoContext {
             let old_target = redirector
                 .get_small_to_large_commit_equivalent(ctx, old_target)
                 .await?;
-            make_move_op(
-                &large_bookmark,
-                target,
-                old_target,
-                allow_non_fast_forward,
-                pushvars,
-                affected_changesets_limit,
+            (
+                make_move_op(
+                    &large_bookmark,
+                    target,
+                    old_target,
+                    allow_non_fast_forward,
+                    pushvars,
+                    affected_changesets_limit,
+                ),
+                Some(target),
             )
         } else {
-            make_move_op(
-                bookmark,
-                target,
-                old_target,
-                allow_non_fast_forward,
-                pushvars,
-                affected_changesets_limit,
+            (
+                make_move_op(
+                    bookmark,
+                    target,
+                    old_target,
+                    allow_non_fast_forward,
+                    pushvars,
+                    affected_changesets_limit,
+                ),
+                None,
             )
         };
         Ok(op)
@@ -140,17 +147,22 @@ impl RepoContext {
         pushvars: Option<&HashMap<String, Bytes>>,
         affected_changesets_limit: Option<usize>,
     ) -> Result<(), MononokeError> {
-        let update_op = self
+        let redirector = self.push_redirector_for_bookmark(bookmark)?;
+        let filtered_pushvars: Option<Cow<'_, HashMap<String, Bytes>>> = match redirector {
+            Some(redirector) => redirector.filter_pushvars(pushvars),
+            None => pushvars.map(Cow::Borrowed),
+        };
+        let (update_op, large_repo_target) = self
             .move_bookmark_op(
                 bookmark,
                 target,
                 old_target,
                 allow_non_fast_forward,
-                pushvars,
+                filtered_pushvars.as_deref(),
                 affected_changesets_limit,
             )
             .await?;
-        if let Some(redirector) = self.push_redirector.as_ref() {
+        if let Some(redirector) = redirector {
             let ctx = self.ctx();
             let log_id = update_op
                 .run(
@@ -162,6 +174,19 @@ impl RepoContext {
                 .await?;
             // Wait for bookmark to catch up on small repo
             redirector.ensure_backsynced(ctx, log_id).await?;
+            if justknobs::eval(
+                "scm/mononoke:verify_backsynced_bookmark_move_working_copy",
+                None,
+                None,
+            )
+            .unwrap_or(false)
+            {
+                if let Some(large_repo_target) = large_repo_target {
+                    redirector
+                        .verify_bookmark_move_working_copy(ctx, large_repo_target)
+                        .await?;
+                }
+            }
         } else {
             update_op
                 .run(
@@ -192,7 +217,7 @@ impl RepoContext {
                 "move_bookmark_with_transaction",
             ));
         }
-        let update_op = self
+        let (update_op, _) = self
             .move_bookmark_op(
                 bookmark,
                 target,
@@ -0,0 +1,68 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::HashMap;
+
+use blobstore::Loadable;
+use bookmarks::BookmarkKey;
+use bytes::Bytes;
+use cloned::cloned;
+use futures::stream::FuturesOrdered;
+use futures::stream::TryStreamExt;
+use hook_manager::manager::HookManagerRef;
+use hook_manager::CrossRepoPushSource;
+use hook_manager::HookOutcome;
+use hook_manager::PushAuthoredBy;
+use mononoke_types::ChangesetId;
+use repo_blobstore::RepoBlobstoreRef;
+
+use crate::errors::MononokeError;
+use crate::repo::RepoContext;
+
+impl RepoContext {
+    /// Evaluate all hooks configured for `bookmark` against `commits`, as if
+    /// they were being pushed to it, without moving the bookmark or writing
+    /// anything. Returns the per-hook per-commit outcomes, so callers can
+    /// pre-validate a stack before landing it.
+    pub async fn run_hooks_dry_run(
+        &self,
+        bookmark: impl AsRef<str>,
+        commits: Vec<ChangesetId>,
+        pushvars: Option<&HashMap<String, Bytes>>,
+    ) -> Result<Vec<HookOutcome>, MononokeError> {
+        let bookmark = BookmarkKey::new(bookmark.as_ref())?;
+
+        let ctx = self.ctx();
+        let blobstore = self.repo().repo_blobstore();
+        let changesets = commits
+            .into_iter()
+            .map(|cs_id| {
+                cloned!(ctx);
+                async move {
+                    cs_id
+                        .load(&ctx, blobstore)
+                        .map_err(MononokeError::from)
+                        .await
+                }
+            })
+            .collect::<FuturesOrdered<_>>()
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        Ok(self
+            .hook_manager()
+            .run_hooks_for_bookmark(
+                ctx,
+                changesets.iter(),
+                &bookmark,
+                pushvars,
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+            )
+            .await?)
+    }
+}
@@ -15,6 +15,8 @@ mod test_repo;
 mod test_repo_bookmarks;
 mod test_repo_create_changeset;
 mod test_repo_create_changeset_stack;
+mod test_repo_create_merge_changeset;
+mod test_repo_hook_outcomes;
 mod test_repo_land_stack;
 mod test_repo_modify_bookmarks;
 mod test_sparse_profile;
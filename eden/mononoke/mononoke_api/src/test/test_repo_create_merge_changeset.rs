@@ -0,0 +1,185 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use anyhow::Error;
+use assert_matches::assert_matches;
+use bytes::Bytes;
+use chrono::FixedOffset;
+use chrono::TimeZone;
+use fbinit::FacebookInit;
+use fixtures::Linear;
+use fixtures::TestRepoFixture;
+use maplit::btreemap;
+use mononoke_types::path::MPath;
+
+use crate::ChangesetId;
+use crate::CoreContext;
+use crate::CreateChange;
+use crate::CreateChangeFile;
+use crate::CreateInfo;
+use crate::Mononoke;
+use crate::MononokeError;
+use crate::RepoContext;
+
+fn test_info(message: &str) -> CreateInfo {
+    CreateInfo {
+        author: String::from("Test Author <test@example.com>"),
+        author_date: FixedOffset::east_opt(0)
+            .unwrap()
+            .with_ymd_and_hms(2000, 2, 1, 12, 0, 0)
+            .unwrap(),
+        committer: None,
+        committer_date: None,
+        message: message.to_string(),
+        extra: BTreeMap::new(),
+        git_extra_headers: None,
+    }
+}
+
+async fn test_repo(fb: FacebookInit) -> Result<RepoContext, Error> {
+    let ctx = CoreContext::test_mock(fb);
+    let mononoke =
+        Mononoke::new_test(vec![("test".to_string(), Linear::get_repo(fb).await)]).await?;
+    Ok(mononoke
+        .repo(ctx, "test")
+        .await?
+        .expect("repo exists")
+        .build()
+        .await?)
+}
+
+#[fbinit::test]
+async fn test_create_merge_commit(fb: FacebookInit) -> Result<(), Error> {
+    let repo = test_repo(fb).await?;
+
+    let initial_hash = "7785606eb1f26ff5722c831de402350cf97052dc44bc175da6ac0d715a3dbbf6";
+    let initial_parent = ChangesetId::from_str(initial_hash)?;
+
+    let (_, p1) = repo
+        .create_changeset(
+            vec![initial_parent],
+            test_info("p1"),
+            btreemap! {
+                MPath::try_from("TEST_FILE_1")? =>
+                CreateChange::Tracked(CreateChangeFile::new_regular("p1\n"), None),
+            },
+            None,
+        )
+        .await?;
+    let (_, p2) = repo
+        .create_changeset(
+            vec![initial_parent],
+            test_info("p2"),
+            btreemap! {
+                MPath::try_from("TEST_FILE_2")? =>
+                CreateChange::Tracked(CreateChangeFile::new_regular("p2\n"), None),
+            },
+            None,
+        )
+        .await?;
+
+    // The two parents touch unrelated paths, so merging them with no
+    // resolutions should succeed.
+    let (_, merge) = repo
+        .create_merge_changeset(p1.id(), p2.id(), test_info("merge"), BTreeMap::new(), None)
+        .await?;
+
+    assert_eq!(merge.parents().await?, vec![p1.id(), p2.id()]);
+    assert_eq!(
+        merge
+            .path_with_content("TEST_FILE_1")
+            .await?
+            .file()
+            .await?
+            .expect("file should exist")
+            .content_concat()
+            .await?,
+        Bytes::from("p1\n")
+    );
+    assert_eq!(
+        merge
+            .path_with_content("TEST_FILE_2")
+            .await?
+            .file()
+            .await?
+            .expect("file should exist")
+            .content_concat()
+            .await?,
+        Bytes::from("p2\n")
+    );
+
+    Ok(())
+}
+
+#[fbinit::test]
+async fn test_create_merge_commit_conflict(fb: FacebookInit) -> Result<(), Error> {
+    let repo = test_repo(fb).await?;
+
+    let initial_hash = "7785606eb1f26ff5722c831de402350cf97052dc44bc175da6ac0d715a3dbbf6";
+    let initial_parent = ChangesetId::from_str(initial_hash)?;
+
+    let (_, p1) = repo
+        .create_changeset(
+            vec![initial_parent],
+            test_info("p1"),
+            btreemap! {
+                MPath::try_from("TEST_FILE")? =>
+                CreateChange::Tracked(CreateChangeFile::new_regular("p1\n"), None),
+            },
+            None,
+        )
+        .await?;
+    let (_, p2) = repo
+        .create_changeset(
+            vec![initial_parent],
+            test_info("p2"),
+            btreemap! {
+                MPath::try_from("TEST_FILE")? =>
+                CreateChange::Tracked(CreateChangeFile::new_regular("p2\n"), None),
+            },
+            None,
+        )
+        .await?;
+
+    // Both parents modify the same path differently: merging without a
+    // resolution should fail with a structured conflict error.
+    let result = repo
+        .create_merge_changeset(p1.id(), p2.id(), test_info("merge"), BTreeMap::new(), None)
+        .await;
+    assert_matches!(result, Err(MononokeError::MergeConflicts { .. }));
+
+    // Providing a resolution for the conflicting path should let the merge
+    // succeed.
+    let (_, merge) = repo
+        .create_merge_changeset(
+            p1.id(),
+            p2.id(),
+            test_info("merge"),
+            btreemap! {
+                MPath::try_from("TEST_FILE")? =>
+                CreateChange::Tracked(CreateChangeFile::new_regular("merged\n"), None),
+            },
+            None,
+        )
+        .await?;
+    assert_eq!(
+        merge
+            .path_with_content("TEST_FILE")
+            .await?
+            .file()
+            .await?
+            .expect("file should exist")
+            .content_concat()
+            .await?,
+        Bytes::from("merged\n")
+    );
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use context::CoreContext;
+use fbinit::FacebookInit;
+use hook_outcome_store::HookOutcomeRecord;
+use hook_outcome_store::HookOutcomeStoreArc;
+use mononoke_types::Timestamp;
+use tests_utils::drawdag::create_from_dag;
+
+use crate::repo::Repo;
+use crate::repo::RepoContext;
+
+#[fbinit::test]
+async fn hook_outcomes_for_changeset_round_trip(fb: FacebookInit) -> Result<()> {
+    let ctx = CoreContext::test_mock(fb);
+    let repo: Repo = test_repo_factory::build_empty(ctx.fb).await?;
+    let changesets = create_from_dag(&ctx, &repo, "A-B").await?;
+    let cs_id = changesets["B"];
+
+    let record = HookOutcomeRecord {
+        cs_id,
+        hook_name: "block_empty_commit".to_string(),
+        bookmark: "trunk".to_string(),
+        accepted: false,
+        rejection_message: Some("commit has no changes".to_string()),
+        duration_ms: 7,
+        timestamp: Timestamp::from_timestamp_secs(1),
+    };
+    repo.hook_outcome_store_arc()
+        .record_outcome(&ctx, &record)
+        .await?;
+
+    let repo_ctx = RepoContext::new_test(ctx.clone(), Arc::new(repo)).await?;
+    let outcomes = repo_ctx.hook_outcomes_for_changeset(cs_id).await?;
+    assert_eq!(outcomes, vec![record]);
+
+    Ok(())
+}
+
+#[fbinit::test]
+async fn hook_outcomes_for_changeset_with_no_outcomes_is_empty(fb: FacebookInit) -> Result<()> {
+    let ctx = CoreContext::test_mock(fb);
+    let repo: Repo = test_repo_factory::build_empty(ctx.fb).await?;
+    let changesets = create_from_dag(&ctx, &repo, "A-B").await?;
+
+    let repo_ctx = RepoContext::new_test(ctx.clone(), Arc::new(repo)).await?;
+    let outcomes = repo_ctx
+        .hook_outcomes_for_changeset(changesets["A"])
+        .await?;
+    assert_eq!(outcomes, vec![]);
+
+    Ok(())
+}
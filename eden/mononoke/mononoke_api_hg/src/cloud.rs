@@ -123,15 +123,9 @@ impl HgRepoContext {
 
         let public_frontier = repo
             .commit_graph()
-            .ancestors_frontier_with(ctx, cs_ids.clone(), |csid| {
+            .ancestors_frontier_with_batch(ctx, cs_ids.clone(), |csids| {
                 borrowed!(ctx, repo);
-                async move {
-                    Ok(repo
-                        .phases()
-                        .get_cached_public(ctx, vec![csid])
-                        .await?
-                        .contains(&csid))
-                }
+                async move { repo.phases().get_cached_public(ctx, csids).await }
             })
             .await?;
 
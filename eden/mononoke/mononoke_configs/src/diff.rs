@@ -0,0 +1,136 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use async_trait::async_trait;
+use metaconfig_parser::RepoConfigs;
+use metaconfig_types::RepoConfig;
+
+/// The top-level [`RepoConfig`] fields that changed between two versions of
+/// the same repo's config, as reported to a [`RepoConfigChangeReceiver`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RepoConfigDiff {
+    /// Name of the repo whose config changed.
+    pub repo_name: String,
+    /// Names of the top-level `RepoConfig` fields that differ between the
+    /// old and new config. Empty only for a newly added repo, in which case
+    /// it means "the whole config is new" rather than "nothing changed".
+    pub changed_fields: Vec<&'static str>,
+    /// True if this repo did not exist in the previous config at all.
+    pub is_new_repo: bool,
+}
+
+/// Trait for library embedders that want to react to a single repo's config
+/// changing (e.g. to adjust rate limits or hooks in place) without having to
+/// diff the whole [`RepoConfigs`] snapshot themselves on every update.
+#[async_trait]
+pub trait RepoConfigChangeReceiver: Send + Sync {
+    /// Called once per repo whose config changed, with the new config and a
+    /// description of which fields changed. This should not be too long
+    /// running, for the same reasons as `ConfigUpdateReceiver::apply_update`.
+    async fn repo_config_changed(
+        &self,
+        repo_name: &str,
+        new_config: &RepoConfig,
+        diff: &RepoConfigDiff,
+    ) -> anyhow::Result<()>;
+}
+
+macro_rules! changed_fields {
+    ($old:expr, $new:expr, [$($field:ident),+ $(,)?]) => {{
+        let mut changed_fields = Vec::new();
+        $(
+            if $old.$field != $new.$field {
+                changed_fields.push(stringify!($field));
+            }
+        )+
+        changed_fields
+    }};
+}
+
+fn diff_repo_config(old: &RepoConfig, new: &RepoConfig) -> Vec<&'static str> {
+    changed_fields!(
+        old,
+        new,
+        [
+            enabled,
+            storage_config,
+            generation_cache_size,
+            repoid,
+            scuba_table_hooks,
+            scuba_local_path_hooks,
+            cache_warmup,
+            bookmarks,
+            infinitepush,
+            hooks,
+            push,
+            pushrebase,
+            lfs,
+            hash_validation_percentage,
+            readonly,
+            redaction,
+            hook_manager_params,
+            list_keys_patterns_max,
+            filestore,
+            hook_max_file_size,
+            hipster_acl,
+            source_control_service,
+            source_control_service_monitoring,
+            derived_data_config,
+            enforce_lfs_acl_check,
+            repo_client_use_warm_bookmarks_cache,
+            repo_client_knobs,
+            phabricator_callsign,
+            backup_repo_config,
+            acl_region_config,
+            walker_config,
+            cross_repo_commit_validation_config,
+            sparse_profiles_config,
+            hg_sync_config,
+            backup_hg_sync_config,
+            update_logging_config,
+            commit_graph_config,
+            default_commit_identity_scheme,
+            deep_sharding_config,
+            everstore_local_path,
+            git_concurrency,
+            metadata_logger_config,
+            zelos_config,
+            bookmark_name_for_objects_count,
+            default_objects_count,
+            x_repo_sync_source_mapping,
+            commit_cloud_config,
+            mononoke_cas_sync_config,
+            git_lfs_interpret_pointers,
+        ]
+    )
+}
+
+/// Compute the per-repo diffs between two versions of [`RepoConfigs`].
+/// Repos that are unchanged, or that were removed in `new`, are omitted.
+pub fn diff_repo_configs(old: &RepoConfigs, new: &RepoConfigs) -> Vec<RepoConfigDiff> {
+    let mut diffs = Vec::new();
+    for (repo_name, new_config) in &new.repos {
+        match old.repos.get(repo_name) {
+            Some(old_config) => {
+                let changed_fields = diff_repo_config(old_config, new_config);
+                if !changed_fields.is_empty() {
+                    diffs.push(RepoConfigDiff {
+                        repo_name: repo_name.clone(),
+                        changed_fields,
+                        is_new_repo: false,
+                    });
+                }
+            }
+            None => diffs.push(RepoConfigDiff {
+                repo_name: repo_name.clone(),
+                changed_fields: Vec::new(),
+                is_new_repo: true,
+            }),
+        }
+    }
+    diffs
+}
@@ -35,6 +35,12 @@ use stats::prelude::*;
 use tokio::runtime::Handle;
 use tokio::task::JoinHandle;
 
+pub mod diff;
+
+pub use diff::diff_repo_configs;
+pub use diff::RepoConfigChangeReceiver;
+pub use diff::RepoConfigDiff;
+
 const LIVENESS_INTERVAL: u64 = 300;
 type Swappable<T> = Arc<ArcSwap<T>>;
 
@@ -51,6 +57,7 @@ pub struct MononokeConfigs {
     repo_configs: Swappable<RepoConfigs>,
     storage_configs: Swappable<StorageConfigs>,
     update_receivers: Swappable<Vec<Arc<dyn ConfigUpdateReceiver>>>,
+    repo_config_change_receivers: Swappable<Vec<Arc<dyn RepoConfigChangeReceiver>>>,
     config_info: Swappable<Option<ConfigInfo>>,
     maybe_config_updater: Option<JoinHandle<()>>,
     maybe_liveness_updater: Option<JoinHandle<()>>,
@@ -72,6 +79,7 @@ impl MononokeConfigs {
         let repo_configs = metaconfig_parser::load_repo_configs(&config_path, config_store)?;
         let repo_configs = Arc::new(ArcSwap::from_pointee(repo_configs));
         let update_receivers = Arc::new(ArcSwap::from_pointee(vec![]));
+        let repo_config_change_receivers = Arc::new(ArcSwap::from_pointee(vec![]));
         let maybe_config_handle = configerator_config_handle(config_path.as_ref(), config_store)?;
         let config_info = if let Some(config_handle) = maybe_config_handle.as_ref() {
             if let Ok(new_config_info) = build_config_info(config_handle.get()) {
@@ -95,12 +103,19 @@ impl MononokeConfigs {
         // If the configuration is backed by a static source, the config update watcher
         // and the config updater handle will be None.
         let maybe_config_updater = maybe_config_watcher.map(|config_watcher| {
-            cloned!(storage_configs, repo_configs, config_info, update_receivers);
+            cloned!(
+                storage_configs,
+                repo_configs,
+                config_info,
+                update_receivers,
+                repo_config_change_receivers
+            );
             runtime_handle.spawn(watch_and_update(
                 repo_configs,
                 storage_configs,
                 config_info,
                 update_receivers,
+                repo_config_change_receivers,
                 config_watcher,
                 logger,
             ))
@@ -109,6 +124,7 @@ impl MononokeConfigs {
             repo_configs,
             storage_configs,
             update_receivers,
+            repo_config_change_receivers,
             config_info,
             maybe_config_updater,
             maybe_config_handle,
@@ -158,6 +174,18 @@ impl MononokeConfigs {
         update_receivers.push(update_receiver);
         self.update_receivers.store(Arc::new(update_receivers));
     }
+
+    /// Register an instance of RepoConfigChangeReceiver to receive, for every config
+    /// update, one notification per repo whose config actually changed, together with
+    /// a diff of which top-level fields changed. Useful for embedders that want to
+    /// react to individual repos' config changes (e.g. adjusting limits or hooks in
+    /// place) without re-deriving a diff from the full RepoConfigs snapshot themselves.
+    pub fn register_for_repo_config_changes(&self, receiver: Arc<dyn RepoConfigChangeReceiver>) {
+        let mut receivers =
+            Vec::from_iter(self.repo_config_change_receivers.load().iter().cloned());
+        receivers.push(receiver);
+        self.repo_config_change_receivers.store(Arc::new(receivers));
+    }
 }
 
 impl Drop for MononokeConfigs {
@@ -187,6 +215,7 @@ async fn watch_and_update(
     storage_configs: Swappable<StorageConfigs>,
     config_info: Swappable<Option<ConfigInfo>>,
     update_receivers: Swappable<Vec<Arc<dyn ConfigUpdateReceiver>>>,
+    repo_config_change_receivers: Swappable<Vec<Arc<dyn RepoConfigChangeReceiver>>>,
     mut config_watcher: ConfigUpdateWatcher<RawRepoConfigs>,
     logger: Logger,
 ) {
@@ -207,6 +236,7 @@ async fn watch_and_update(
                         } else {
                             warn!(logger, "Could not compute new config_info");
                         }
+                        let old_repo_configs = repo_configs.load_full();
                         let new_repo_configs = Arc::new(new_repo_configs);
                         let new_storage_configs = Arc::new(new_storage_configs);
                         repo_configs.store(new_repo_configs.clone());
@@ -231,6 +261,35 @@ async fn watch_and_update(
                             // Need to publish a value of 0 to keep the counter alive
                             STATS::refresh_failure_count.add_value(0);
                         }
+                        let repo_config_diffs =
+                            diff_repo_configs(&old_repo_configs, &new_repo_configs);
+                        let repo_config_change_receivers = repo_config_change_receivers.load();
+                        let repo_config_change_tasks = repo_config_diffs.iter().flat_map(|diff| {
+                            let new_config = new_repo_configs.repos.get(&diff.repo_name);
+                            repo_config_change_receivers
+                                .iter()
+                                .filter_map(move |receiver| {
+                                    new_config.map(|new_config| {
+                                        receiver.repo_config_changed(
+                                            &diff.repo_name,
+                                            new_config,
+                                            diff,
+                                        )
+                                    })
+                                })
+                        });
+                        if let Err(e) = join_all(repo_config_change_tasks)
+                            .await
+                            .into_iter()
+                            .collect::<Result<Vec<_>>>()
+                        {
+                            error!(
+                                logger,
+                                "Failure in sending repo config change to receivers. Error: {:?}",
+                                e
+                            );
+                            STATS::refresh_failure_count.add_value(1);
+                        }
                     }
                     Err(e) => {
                         error!(
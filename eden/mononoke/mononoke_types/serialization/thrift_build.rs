@@ -10,6 +10,7 @@ eden/mononoke/mononoke_types/serialization/blame.thrift crate //eden/mononoke/mo
 eden/mononoke/mononoke_types/serialization/bonsai.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
 eden/mononoke/mononoke_types/serialization/bssm.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
 eden/mononoke/mononoke_types/serialization/changeset_info.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
+eden/mononoke/mononoke_types/serialization/churn.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
 eden/mononoke/mononoke_types/serialization/content.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
 eden/mononoke/mononoke_types/serialization/data.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
 eden/mononoke/mononoke_types/serialization/deleted_manifest.thrift crate //eden/mononoke/mononoke_types/serialization:mononoke_types_serialization-rust
@@ -41,6 +42,7 @@ fn main() {
             "bonsai.thrift",
             "bssm.thrift",
             "changeset_info.thrift",
+            "churn.thrift",
             "content.thrift",
             "data.thrift",
             "deleted_manifest.thrift",
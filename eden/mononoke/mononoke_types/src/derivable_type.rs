@@ -27,9 +27,11 @@ use crate::thrift;
 )]
 pub enum DerivableType {
     BlameV2,
+    BlameV3,
     BssmV3,
     ChangesetInfo,
     DeletedManifests,
+    DirectoryChurn,
     Fastlog,
     FileNodes,
     Fsnodes,
@@ -50,9 +52,11 @@ impl DerivableType {
         // BonsaiDerivable::NAME
         Ok(match s {
             "blame" => DerivableType::BlameV2,
+            "blame_v3" => DerivableType::BlameV3,
             "bssm_v3" => DerivableType::BssmV3,
             "changeset_info" => DerivableType::ChangesetInfo,
             "deleted_manifest" => DerivableType::DeletedManifests,
+            "directory_churn" => DerivableType::DirectoryChurn,
             "fastlog" => DerivableType::Fastlog,
             "filenodes" => DerivableType::FileNodes,
             "fsnodes" => DerivableType::Fsnodes,
@@ -73,9 +77,11 @@ impl DerivableType {
         // BonsaiDerivable::NAME
         match self {
             DerivableType::BlameV2 => "blame",
+            DerivableType::BlameV3 => "blame_v3",
             DerivableType::BssmV3 => "bssm_v3",
             DerivableType::ChangesetInfo => "changeset_info",
             DerivableType::DeletedManifests => "deleted_manifest",
+            DerivableType::DirectoryChurn => "directory_churn",
             DerivableType::Fastlog => "fastlog",
             DerivableType::FileNodes => "filenodes",
             DerivableType::Fsnodes => "fsnodes",
@@ -90,12 +96,44 @@ impl DerivableType {
             DerivableType::Unodes => "unodes",
         }
     }
+    /// Returns the other `DerivableType`s that must be derived before this one, mirroring the
+    /// `Dependencies` associated type that each `BonsaiDerivable` impl declares at compile time.
+    ///
+    /// This is the runtime-introspectable counterpart used by schedulers (e.g. `bulk_derivation`)
+    /// that only know which types to derive at runtime, and so can't name `Derivable::Dependencies`
+    /// directly.
+    pub const fn dependencies(&self) -> &'static [DerivableType] {
+        match self {
+            DerivableType::BlameV2 => &[DerivableType::Unodes],
+            DerivableType::BlameV3 => &[DerivableType::BlameV2],
+            DerivableType::BssmV3 => &[DerivableType::SkeletonManifests],
+            DerivableType::ChangesetInfo => &[],
+            DerivableType::DeletedManifests => &[DerivableType::Unodes],
+            DerivableType::DirectoryChurn => &[],
+            DerivableType::Fastlog => &[DerivableType::Unodes],
+            DerivableType::FileNodes => &[DerivableType::HgChangesets],
+            DerivableType::Fsnodes => &[],
+            DerivableType::HgChangesets => &[],
+            DerivableType::HgAugmentedManifests => &[DerivableType::HgChangesets],
+            DerivableType::GitTrees => &[],
+            DerivableType::GitCommits => &[DerivableType::GitTrees],
+            DerivableType::GitDeltaManifestsV2 => {
+                &[DerivableType::GitTrees, DerivableType::GitCommits]
+            }
+            DerivableType::SkeletonManifests => &[],
+            DerivableType::TestManifests => &[],
+            DerivableType::TestShardedManifests => &[],
+            DerivableType::Unodes => &[],
+        }
+    }
     pub fn from_thrift(other: thrift::DerivedDataType) -> Result<Self> {
         Ok(match other {
             thrift::DerivedDataType::BLAME => Self::BlameV2,
+            thrift::DerivedDataType::BLAME_V3 => Self::BlameV3,
             thrift::DerivedDataType::BSSM_V3 => Self::BssmV3,
             thrift::DerivedDataType::CHANGESET_INFO => Self::ChangesetInfo,
             thrift::DerivedDataType::DELETED_MANIFEST_V2 => Self::DeletedManifests,
+            thrift::DerivedDataType::DIRECTORY_CHURN => Self::DirectoryChurn,
             thrift::DerivedDataType::FASTLOG => Self::Fastlog,
             thrift::DerivedDataType::FILENODE => Self::FileNodes,
             thrift::DerivedDataType::FSNODE => Self::Fsnodes,
@@ -114,9 +152,11 @@ impl DerivableType {
     pub fn into_thrift(&self) -> thrift::DerivedDataType {
         match self {
             Self::BlameV2 => thrift::DerivedDataType::BLAME,
+            Self::BlameV3 => thrift::DerivedDataType::BLAME_V3,
             Self::BssmV3 => thrift::DerivedDataType::BSSM_V3,
             Self::ChangesetInfo => thrift::DerivedDataType::CHANGESET_INFO,
             Self::DeletedManifests => thrift::DerivedDataType::DELETED_MANIFEST_V2,
+            Self::DirectoryChurn => thrift::DerivedDataType::DIRECTORY_CHURN,
             Self::Fastlog => thrift::DerivedDataType::FASTLOG,
             Self::FileNodes => thrift::DerivedDataType::FILENODE,
             Self::Fsnodes => thrift::DerivedDataType::FSNODE,
@@ -153,6 +193,17 @@ mod tests {
             );
         }
     }
+    #[test]
+    fn dependencies_must_not_be_self_referential() {
+        for variant in DerivableType::iter() {
+            assert!(
+                !variant.dependencies().contains(&variant),
+                "{:?} lists itself as a dependency",
+                variant
+            );
+        }
+    }
+
     #[test]
     fn name_derived_data_type_conversion_must_be_bidirectional() {
         for variant in DerivableType::iter() {
@@ -15,6 +15,7 @@ use anyhow::Error;
 use anyhow::Result;
 use ascii::AsciiString;
 use async_trait::async_trait;
+use bookmarks_types::BookmarkKey;
 use commit_graph::ArcCommitGraph;
 use context::CoreContext;
 use futures::future::try_join;
@@ -22,8 +23,10 @@ use futures::future::BoxFuture;
 use futures::future::FutureExt;
 use mononoke_types::ChangesetId;
 use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
 use phases::ArcPhases;
 use phases::Phase;
+use phases::PhaseTransition;
 use phases::Phases;
 use sql::mysql;
 use sql::mysql_async::prelude::ConvIr;
@@ -253,6 +256,35 @@ impl Phases for SqlPhases {
         self.add_public_raw(ctx, csids).await
     }
 
+    async fn add_reachable_as_public_with_bookmark(
+        &self,
+        ctx: &CoreContext,
+        heads: Vec<ChangesetId>,
+        bookmark: BookmarkKey,
+    ) -> Result<Vec<ChangesetId>> {
+        let newly_public = mark_reachable_as_public(ctx, self, &heads, false).await?;
+        self.phases_store
+            .add_phase_transitions_raw(
+                ctx,
+                self.repo_id,
+                newly_public.clone(),
+                Some(&bookmark),
+                Timestamp::now(),
+            )
+            .await?;
+        Ok(newly_public)
+    }
+
+    async fn get_public_transition(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Option<PhaseTransition>> {
+        self.phases_store
+            .get_phase_transition_raw(ctx, self.repo_id, cs_id)
+            .await
+    }
+
     fn with_frozen_public_heads(&self, heads: Vec<ChangesetId>) -> ArcPhases {
         let heads_fetcher = Arc::new(move |_ctx: &CoreContext| {
             let heads = heads.clone();
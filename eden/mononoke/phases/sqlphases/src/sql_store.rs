@@ -13,6 +13,7 @@ use std::time::Duration;
 use anyhow::Context as _;
 use anyhow::Error;
 use async_trait::async_trait;
+use bookmarks_types::BookmarkKey;
 use bytes::Bytes;
 use caching_ext::fill_cache;
 use caching_ext::get_or_fill;
@@ -32,7 +33,9 @@ use maplit::hashset;
 use memcache::KeyGen;
 use mononoke_types::ChangesetId;
 use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
 use phases::Phase;
+use phases::PhaseTransition;
 use sql::Connection;
 use sql_ext::mononoke_queries;
 use stats::prelude::*;
@@ -173,6 +176,71 @@ impl SqlPhasesStore {
         .await?;
         Ok(ans.into_iter().map(|x| x.0).collect())
     }
+
+    /// Record the phase transitions for the given commits, which just became
+    /// public as a result of `bookmark` moving.  A commit only transitions
+    /// once, so existing entries are left untouched.
+    pub async fn add_phase_transitions_raw(
+        &self,
+        ctx: &CoreContext,
+        repo_id: RepositoryId,
+        csids: Vec<ChangesetId>,
+        bookmark: Option<&BookmarkKey>,
+        timestamp: Timestamp,
+    ) -> Result<(), Error> {
+        if csids.is_empty() {
+            return Ok(());
+        }
+        let bookmark_name = bookmark.map(|bookmark| bookmark.to_string());
+        let transitions: Vec<_> = csids
+            .iter()
+            .map(|csid| (&repo_id, csid, &bookmark_name, &timestamp))
+            .collect();
+
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlWrites);
+
+        InsertPhaseTransition::maybe_traced_query(
+            &self.write_connection,
+            ctx.client_request_info(),
+            &transitions,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Return the recorded phase transition for a commit, if any.
+    pub async fn get_phase_transition_raw(
+        &self,
+        ctx: &CoreContext,
+        repo_id: RepositoryId,
+        cs_id: ChangesetId,
+    ) -> Result<Option<PhaseTransition>, Error> {
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlReadsReplica);
+        let rows = SelectPhaseTransition::maybe_traced_query(
+            &self.read_connection,
+            ctx.client_request_info(),
+            &repo_id,
+            &cs_id,
+        )
+        .await?;
+
+        rows.into_iter()
+            .next()
+            .map(|(bookmark_name, timestamp)| {
+                let bookmark = bookmark_name
+                    .map(|bookmark_name| BookmarkKey::new(bookmark_name))
+                    .transpose()?;
+                Ok(PhaseTransition {
+                    cs_id,
+                    bookmark,
+                    timestamp,
+                })
+            })
+            .transpose()
+    }
 }
 
 impl MemcacheEntity for SqlPhase {
@@ -295,4 +363,17 @@ mononoke_queries! {
                 AND phase like 'Public'"
         )
     }
+
+    write InsertPhaseTransition(values: (repo_id: RepositoryId, cs_id: ChangesetId, bookmark_name: Option<String>, timestamp: Timestamp)) {
+        none,
+        mysql("INSERT IGNORE INTO phase_transitions (repo_id, cs_id, bookmark_name, timestamp) VALUES {values}")
+        sqlite("INSERT OR IGNORE INTO phase_transitions (repo_id, cs_id, bookmark_name, timestamp) VALUES {values}")
+    }
+
+    read SelectPhaseTransition(repo_id: RepositoryId, cs_id: ChangesetId) -> (Option<String>, Timestamp) {
+        "SELECT bookmark_name, timestamp
+        FROM phase_transitions
+        WHERE repo_id = {repo_id}
+            AND cs_id = {cs_id}"
+    }
 }
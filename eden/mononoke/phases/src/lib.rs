@@ -13,9 +13,11 @@ use std::fmt;
 use abomonation_derive::Abomonation;
 use anyhow::Result;
 use async_trait::async_trait;
+use bookmarks_types::BookmarkKey;
 use context::CoreContext;
 pub use errors::PhasesError;
 use mononoke_types::ChangesetId;
+use mononoke_types::Timestamp;
 
 #[derive(Abomonation, Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Phase {
@@ -53,6 +55,23 @@ impl TryFrom<u32> for Phase {
     }
 }
 
+/// A record of the point in time and the cause of a commit's transition
+/// from draft to public, so that operators can answer "when did this
+/// commit become public, and via which bookmark" without reconstructing
+/// the answer from bookmark update logs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PhaseTransition {
+    /// The changeset that transitioned to public.
+    pub cs_id: ChangesetId,
+    /// The bookmark whose move caused the changeset to become public, if
+    /// known.  Transitions recorded by `add_reachable_as_public_with_bookmark`
+    /// always carry it; `add_reachable_as_public` records no transition at
+    /// all.
+    pub bookmark: Option<BookmarkKey>,
+    /// When the transition was recorded.
+    pub timestamp: Timestamp,
+}
+
 /// Phases tracks which commits are public, and which commits are draft.
 ///
 /// A commit ordinarily becomes public when it is reachable from any
@@ -100,6 +119,25 @@ pub trait Phases: Send + Sync {
     /// List all public commits.
     async fn list_all_public(&self, ctx: &CoreContext) -> Result<Vec<ChangesetId>>;
 
+    /// Mark all commits reachable from heads as public, recording the
+    /// given bookmark as the cause of the transition for any commit that
+    /// newly became public as a result.  Returns all the newly public
+    /// commits, same as `add_reachable_as_public`.
+    async fn add_reachable_as_public_with_bookmark(
+        &self,
+        ctx: &CoreContext,
+        heads: Vec<ChangesetId>,
+        bookmark: BookmarkKey,
+    ) -> Result<Vec<ChangesetId>>;
+
+    /// Returns the recorded phase transition for a commit, i.e. when and
+    /// via which bookmark it became public, if that is known.
+    async fn get_public_transition(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Option<PhaseTransition>>;
+
     /// Return a copy of this phases object with the set of public
     /// heads frozen.
     fn with_frozen_public_heads(&self, heads: Vec<ChangesetId>) -> ArcPhases;
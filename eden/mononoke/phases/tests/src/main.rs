@@ -357,3 +357,47 @@ async fn test_mark_reachable_as_public(fb: FacebookInit) -> Result<()> {
 
     Ok(())
 }
+
+#[fbinit::test]
+async fn test_phase_transition_log(fb: FacebookInit) -> Result<()> {
+    let repo: PhasesTestRepo = fixtures::BranchEven::get_repo(fb).await;
+    let hgcss = [
+        "15c40d0abc36d47fb51c8eaec51ac7aad31f669c",
+        "3cda5c78aa35f0f5b09780d971197b51cad4613a",
+    ];
+    let ctx = CoreContext::test_mock(fb);
+
+    borrowed!(ctx, repo);
+
+    delete_all_publishing_bookmarks(ctx, repo).await?;
+
+    let bcss = future::try_join_all(hgcss.iter().map(|hgcs| async move {
+        let bcs = repo
+            .bonsai_hg_mapping()
+            .get_bonsai_from_hg(ctx, HgChangesetId::from_str(hgcs)?)
+            .await?
+            .ok_or_else(|| format_err!("Invalid hgcs: {}", hgcs))?;
+        Result::<_, Error>::Ok(bcs)
+    }))
+    .await?;
+
+    let phases = repo.phases();
+    let bookmark = BookmarkKey::new("master")?;
+
+    assert!(phases.get_public_transition(ctx, bcss[0]).await?.is_none());
+
+    phases
+        .add_reachable_as_public_with_bookmark(ctx, vec![bcss[0]], bookmark.clone())
+        .await?;
+
+    let transition = phases
+        .get_public_transition(ctx, bcss[0])
+        .await?
+        .ok_or_else(|| format_err!("Expected a recorded phase transition for bcss[0]"))?;
+    assert_eq!(transition.bookmark, Some(bookmark));
+
+    // A commit that's still draft has no recorded transition.
+    assert!(phases.get_public_transition(ctx, bcss[1]).await?.is_none());
+
+    Ok(())
+}
@@ -9,6 +9,7 @@ use std::pin::Pin;
 
 use anyhow::Result;
 use borrowed::borrowed;
+use commit_graph_types::edges::ChangesetNode;
 use commit_graph_types::frontier::ChangesetFrontier;
 use commit_graph_types::storage::Prefetch;
 use context::CoreContext;
@@ -36,13 +37,14 @@ use crate::CommitGraph;
 /// - including only changesets that satisfy a given property (if this property doesn't
 /// hold for one changeset then it mustn't hold for any of its parents).
 ///
-/// - including only changesets that are descendants of any one changeset.
+/// - including only changesets that are descendants of any one of a set of
+/// changesets (roots).
 pub struct AncestorsStreamBuilder {
     commit_graph: ArcCommitGraph,
     ctx: CoreContext,
     heads: Vec<ChangesetId>,
     common: Vec<ChangesetId>,
-    descendants_of: Option<ChangesetId>,
+    descendants_of: Vec<ChangesetId>,
     property: Box<
         dyn Fn(ChangesetId) -> Pin<Box<dyn Future<Output = Result<bool>> + Send>> + Send + Sync,
     >,
@@ -55,7 +57,7 @@ impl AncestorsStreamBuilder {
             ctx,
             heads,
             common: vec![],
-            descendants_of: None,
+            descendants_of: vec![],
             property: Box::new(|_| Box::pin(future::ready(Ok(true)))),
         }
     }
@@ -66,7 +68,14 @@ impl AncestorsStreamBuilder {
     }
 
     pub fn descendants_of(mut self, descendants_of: ChangesetId) -> Self {
-        self.descendants_of = Some(descendants_of);
+        self.descendants_of = vec![descendants_of];
+        self
+    }
+
+    /// Restrict the stream to changesets that are descendants of any one of
+    /// `roots`, generalizing `descendants_of` to more than one root.
+    pub fn descendants_of_any(mut self, roots: Vec<ChangesetId>) -> Self {
+        self.descendants_of = roots;
         self
     }
 
@@ -116,7 +125,7 @@ impl AncestorsStreamBuilder {
             ctx: CoreContext,
             heads: ChangesetFrontier,
             common: ChangesetFrontier,
-            descendants_of: Option<(ChangesetId, Generation)>,
+            descendants_of: Vec<(ChangesetId, Generation)>,
             property: Box<
                 dyn Fn(ChangesetId) -> Pin<Box<dyn Future<Output = Result<bool>> + Send>>
                     + Send
@@ -124,34 +133,38 @@ impl AncestorsStreamBuilder {
             >,
         }
 
-        let heads = match self.descendants_of {
-            Some(descendants_of) => {
-                stream::iter(self.heads)
-                    .map(anyhow::Ok)
-                    .try_filter_map(|head| {
-                        borrowed!(self.commit_graph: &CommitGraph, self.ctx);
-                        async move {
-                            match commit_graph.is_ancestor(ctx, descendants_of, head).await? {
-                                true => Ok(Some(head)),
-                                false => Ok(None),
-                            }
+        let heads = if self.descendants_of.is_empty() {
+            self.heads
+        } else {
+            let roots = &self.descendants_of;
+            stream::iter(self.heads)
+                .map(anyhow::Ok)
+                .try_filter_map(|head| {
+                    borrowed!(self.commit_graph: &CommitGraph, self.ctx, roots);
+                    async move {
+                        match is_descendant_of_any(commit_graph, ctx, roots, head).await? {
+                            true => Ok(Some(head)),
+                            false => Ok(None),
                         }
-                    })
-                    .try_collect()
-                    .await?
-            }
-            None => self.heads,
+                    }
+                })
+                .try_collect()
+                .await?
         };
 
-        let descendants_of = match self.descendants_of {
-            Some(descendants_of) => Some((
-                descendants_of,
-                self.commit_graph
-                    .changeset_generation(&self.ctx, descendants_of)
-                    .await?,
-            )),
-            None => None,
-        };
+        let descendants_of = stream::iter(self.descendants_of)
+            .map(anyhow::Ok)
+            .and_then(|descendants_of| {
+                borrowed!(self.commit_graph: &CommitGraph, self.ctx);
+                async move {
+                    let generation = commit_graph
+                        .changeset_generation(ctx, descendants_of)
+                        .await?;
+                    Ok((descendants_of, generation))
+                }
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
 
         let (heads, common) = futures::try_join!(
             self.commit_graph.frontier(&self.ctx, heads),
@@ -200,20 +213,16 @@ impl AncestorsStreamBuilder {
 
                     for (_cs_id, edges) in all_edges.into_iter() {
                         for parent in edges.parents.iter() {
-                            if let Some((descendants_of, descendants_of_gen)) = descendants_of {
-                                // There is no need to query ancestry if the skip tree parent's generation number
-                                // is greater than or equal to the generation number of descendants_of. This is
-                                // because the skip tree parent is the common ancestor of all parents, and since
-                                // the current changeset is a descendant of descendants_of, all of its parents
-                                // will also be descendants of it.
-                                if !edges.skip_tree_parent.map_or(false, |skip_tree_parent| {
-                                    skip_tree_parent.generation >= *descendants_of_gen
-                                }) && !commit_graph
-                                    .is_ancestor(ctx, *descendants_of, parent.cs_id)
-                                    .await?
-                                {
-                                    continue;
-                                }
+                            if !descendants_of.is_empty()
+                                && !is_descendant_of_any_root(
+                                    commit_graph,
+                                    ctx,
+                                    descendants_of,
+                                    *parent,
+                                )
+                                .await?
+                            {
+                                continue;
                             }
                             heads
                                 .entry(parent.generation)
@@ -232,3 +241,37 @@ impl AncestorsStreamBuilder {
         .boxed())
     }
 }
+
+/// Returns true if `descendant` is a descendant of any changeset in `roots`.
+async fn is_descendant_of_any(
+    commit_graph: &CommitGraph,
+    ctx: &CoreContext,
+    roots: &[ChangesetId],
+    descendant: ChangesetId,
+) -> Result<bool> {
+    for root in roots {
+        if commit_graph.is_ancestor(ctx, *root, descendant).await? {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Returns true if `parent` is a descendant of any changeset in `roots`.
+/// Roots with a higher generation than `parent` are skipped without an
+/// ancestry query, since `parent` cannot descend from them.
+async fn is_descendant_of_any_root(
+    commit_graph: &CommitGraph,
+    ctx: &CoreContext,
+    roots: &[(ChangesetId, Generation)],
+    parent: ChangesetNode,
+) -> Result<bool> {
+    for (root, root_generation) in roots {
+        if parent.generation >= *root_generation
+            && commit_graph.is_ancestor(ctx, *root, parent.cs_id).await?
+        {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
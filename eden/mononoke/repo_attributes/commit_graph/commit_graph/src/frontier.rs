@@ -6,6 +6,7 @@
  */
 
 use std::collections::HashMap;
+use std::collections::HashSet;
 
 use anyhow::anyhow;
 use anyhow::Result;
@@ -181,6 +182,53 @@ impl CommitGraph {
         }
     }
 
+    /// Like `lower_frontier_step`, but `batch_property` is given the whole
+    /// frontier row being popped in a single call, rather than being invoked
+    /// once per changeset in the row.
+    ///
+    /// This is intended for properties backed by a bulk lookup (e.g. a
+    /// cached-public phase check), where invoking the property once per
+    /// changeset would mean one round trip per changeset rather than one per
+    /// frontier row. Changesets visited while lowering through the skip tree
+    /// (i.e. outside of the row that was batched) are still checked
+    /// individually, since they aren't known until the walk reaches them.
+    pub(crate) async fn lower_frontier_step_batch<BatchProperty, Out>(
+        &self,
+        ctx: &CoreContext,
+        frontier: &mut ChangesetFrontier,
+        batch_property: BatchProperty,
+        prefetch: Prefetch,
+    ) -> Result<Option<Vec<ChangesetId>>>
+    where
+        BatchProperty: Fn(Vec<ChangesetId>) -> Out + Send + Sync,
+        Out: Future<Output = Result<HashSet<ChangesetId>>>,
+    {
+        let row = match frontier.last_key_value() {
+            Some((_, cs_ids)) => cs_ids.iter().copied().collect::<Vec<_>>(),
+            None => return Ok(None),
+        };
+        let row_set = row.iter().copied().collect::<HashSet<_>>();
+        let satisfies_property = batch_property(row).await?;
+
+        let property = |node: ChangesetNode| {
+            borrowed!(satisfies_property, row_set, batch_property);
+            async move {
+                if row_set.contains(&node.cs_id) {
+                    anyhow::Ok(satisfies_property.contains(&node.cs_id))
+                } else {
+                    anyhow::Ok(
+                        batch_property(vec![node.cs_id])
+                            .await?
+                            .contains(&node.cs_id),
+                    )
+                }
+            }
+        };
+
+        self.lower_frontier_step(ctx, frontier, property, prefetch)
+            .await
+    }
+
     /// Lower a frontier so that it contains the highest ancestors of the
     /// frontier that have a generation number less than or equal to
     /// `generation`.
@@ -20,6 +20,7 @@ use anyhow::Context;
 use anyhow::Result;
 use borrowed::borrowed;
 use buffered_commit_graph_storage::BufferedCommitGraphStorage;
+pub use commit_graph_types::edges::ChangesetAnnotations;
 use commit_graph_types::edges::ChangesetEdges;
 use commit_graph_types::edges::ChangesetNode;
 pub use commit_graph_types::edges::ChangesetParents;
@@ -33,6 +34,7 @@ use commit_graph_types::storage::CommitGraphStorage;
 use commit_graph_types::storage::Prefetch;
 use commit_graph_types::storage::PrefetchTarget;
 use context::CoreContext;
+use futures::future;
 use futures::stream;
 use futures::stream::BoxStream;
 use futures::stream::FuturesUnordered;
@@ -54,6 +56,7 @@ use vec1::Vec1;
 pub use crate::ancestors_stream::AncestorsStreamBuilder;
 pub use crate::compat::ParentsFetcher;
 pub use crate::linear::LinearAncestorsStreamBuilder;
+pub use crate::speculative::SpeculativeCommitGraph;
 pub use crate::writer::ArcCommitGraphWriter;
 pub use crate::writer::BaseCommitGraphWriter;
 pub use crate::writer::CommitGraphWriter;
@@ -68,6 +71,7 @@ mod core;
 mod frontier;
 mod linear;
 mod segments;
+mod speculative;
 mod writer;
 
 /// Commit Graph.
@@ -282,6 +286,27 @@ impl CommitGraph {
             .collect())
     }
 
+    /// Returns the generation, first-parent linear depth, and skew-binary
+    /// ancestor pointers of many changesets in a single call, so that
+    /// services computing things like "commits behind master" or
+    /// rendering graphs don't need a separate batch query per annotation.
+    pub async fn many_changeset_annotations(
+        &self,
+        ctx: &CoreContext,
+        cs_ids: &[ChangesetId],
+    ) -> Result<HashMap<ChangesetId, ChangesetAnnotations>> {
+        let fetched_edges = self
+            .storage
+            .fetch_many_edges(ctx, cs_ids, Prefetch::None)
+            .await?;
+        Ok(fetched_edges
+            .into_iter()
+            .map(|(cs_id, fetched_edges)| {
+                (cs_id, ChangesetAnnotations::from(&fetched_edges.edges()))
+            })
+            .collect())
+    }
+
     /// Return only the changesets that are found in the commit graph.
     pub async fn known_changesets(
         &self,
@@ -329,6 +354,40 @@ impl CommitGraph {
         Ok(ancestors_frontier.into_iter().collect())
     }
 
+    /// Like `ancestors_frontier_with`, but `monotonic_property` is given an
+    /// entire frontier row (generation level) in a single call, rather than
+    /// being invoked once per changeset.
+    ///
+    /// This lets callers combine a bulk reachability check (e.g.
+    /// `Phases::get_cached_public`) with the frontier computation in one
+    /// pass, instead of issuing one lookup per candidate changeset.
+    ///
+    /// Note: The property needs to be monotonic i.e. if the
+    /// property holds for one changeset then it has to hold
+    /// for all its parents.
+    pub async fn ancestors_frontier_with_batch<'a, BatchMonotonicProperty, Out>(
+        &'a self,
+        ctx: &'a CoreContext,
+        heads: Vec<ChangesetId>,
+        monotonic_property: BatchMonotonicProperty,
+    ) -> Result<Vec<ChangesetId>>
+    where
+        BatchMonotonicProperty: Fn(Vec<ChangesetId>) -> Out + Send + Sync + 'a,
+        Out: Future<Output = Result<HashSet<ChangesetId>>>,
+    {
+        let mut ancestors_frontier = vec![];
+        let mut frontier = self.frontier(ctx, heads).await?;
+
+        while let Some(ancestors_frontier_extension) = self
+            .lower_frontier_step_batch(ctx, &mut frontier, &monotonic_property, Prefetch::None)
+            .await?
+        {
+            ancestors_frontier.extend(ancestors_frontier_extension);
+        }
+
+        Ok(ancestors_frontier.into_iter().collect())
+    }
+
     /// Returns true if the ancestor changeset is an ancestor of the descendant
     /// changeset.
     ///
@@ -528,6 +587,29 @@ impl CommitGraph {
         Ok(stream::iter(range.into_iter().rev()).boxed())
     }
 
+    /// Returns all changesets that are both descendants of any changeset in
+    /// `roots` and ancestors of any changeset in `heads`, in topological
+    /// order.
+    ///
+    /// This generalizes `range_stream` to more than one root and head, for
+    /// stack analysis and bisect tooling that would otherwise emulate this
+    /// with repeated pairwise range queries.
+    pub async fn range(
+        &self,
+        ctx: &CoreContext,
+        roots: Vec<ChangesetId>,
+        heads: Vec<ChangesetId>,
+    ) -> Result<Vec<ChangesetId>> {
+        let range: Vec<_> = AncestorsStreamBuilder::new(Arc::new(self.clone()), ctx.clone(), heads)
+            .descendants_of_any(roots)
+            .build()
+            .await?
+            .try_collect()
+            .await?;
+
+        Ok(range.into_iter().rev().collect())
+    }
+
     /// Returns all of the highest generation changesets that
     /// are ancestors of both u and v, sorted by changeset id.
     pub async fn common_base(
@@ -595,6 +677,143 @@ impl CommitGraph {
         }
     }
 
+    /// Returns all of the highest generation changesets that are ancestors
+    /// of every changeset in `heads`, sorted by changeset id.
+    ///
+    /// This generalizes `common_base` to more than two heads, for stack
+    /// analysis and bisect tooling that would otherwise emulate this with
+    /// repeated pairwise `common_base` calls.
+    pub async fn gca_many(
+        &self,
+        ctx: &CoreContext,
+        heads: Vec<ChangesetId>,
+    ) -> Result<Vec<ChangesetId>> {
+        let mut heads = heads;
+        heads.sort();
+        heads.dedup();
+
+        if heads.len() <= 1 {
+            return Ok(heads);
+        }
+
+        let mut frontiers = future::try_join_all(
+            heads
+                .into_iter()
+                .map(|head| self.single_frontier(ctx, head)),
+        )
+        .await?;
+
+        loop {
+            let lowest_highest_generation = match frontiers
+                .iter()
+                .filter_map(|frontier| frontier.last_key_value().map(|(generation, _)| *generation))
+                .min()
+            {
+                Some(generation) => generation,
+                // One of the frontiers is empty, so there are no common ancestors.
+                None => return Ok(vec![]),
+            };
+
+            for frontier in frontiers.iter_mut() {
+                self.lower_frontier(ctx, frontier, lowest_highest_generation)
+                    .await?;
+            }
+
+            let mut intersection = frontiers[0]
+                .get(&lowest_highest_generation)
+                .cloned()
+                .unwrap_or_default();
+            for frontier in &frontiers[1..] {
+                if intersection.is_empty() {
+                    break;
+                }
+                let cs_ids = frontier
+                    .get(&lowest_highest_generation)
+                    .cloned()
+                    .unwrap_or_default();
+                intersection = intersection.intersection(&cs_ids).copied().collect();
+            }
+
+            if !intersection.is_empty() {
+                let mut result = intersection.into_iter().collect::<Vec<_>>();
+                result.sort();
+                return Ok(result);
+            }
+
+            for frontier in frontiers.iter_mut() {
+                self.lower_frontier_highest_generation(ctx, frontier)
+                    .await?;
+            }
+        }
+    }
+
+    /// Picks up to `count` well-spread commits from the range of changesets
+    /// strictly between `good` and `bad`, using generation numbers to spread
+    /// the choices evenly across the range.
+    ///
+    /// This is intended for a server-assisted bisect endpoint: the server
+    /// can hand the client a handful of candidate commits to test next,
+    /// rather than the client downloading the whole range graph between
+    /// `good` and `bad` to compute midpoints itself.
+    ///
+    /// Returned commits are ordered from closest-to-`good` to
+    /// closest-to-`bad`. Returns fewer than `count` commits if there aren't
+    /// that many in the range.
+    pub async fn bisect_midpoints(
+        &self,
+        ctx: &CoreContext,
+        good: ChangesetId,
+        bad: ChangesetId,
+        count: u64,
+    ) -> Result<Vec<ChangesetId>> {
+        if count == 0 {
+            return Ok(vec![]);
+        }
+
+        let candidates: Vec<ChangesetId> = self
+            .range(ctx, vec![good], vec![bad])
+            .await?
+            .into_iter()
+            .filter(|cs_id| *cs_id != good && *cs_id != bad)
+            .collect();
+
+        if candidates.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let generations = self.many_changeset_generations(ctx, &candidates).await?;
+        let mut candidates_by_generation = candidates
+            .iter()
+            .map(|cs_id| (generations[cs_id].value(), *cs_id))
+            .collect::<Vec<_>>();
+        candidates_by_generation.sort();
+
+        let min_generation = candidates_by_generation.first().map_or(0, |(gen, _)| *gen);
+        let max_generation = candidates_by_generation.last().map_or(0, |(gen, _)| *gen);
+        let span = max_generation - min_generation;
+        let count = std::cmp::min(count, candidates_by_generation.len() as u64);
+
+        let mut midpoints = Vec::with_capacity(count as usize);
+        let mut used = HashSet::new();
+        for i in 1..=count {
+            let target_generation = min_generation + span * i / (count + 1);
+            // Find the candidate whose generation is closest to the target,
+            // skipping any candidate already picked for a previous midpoint.
+            let closest = candidates_by_generation
+                .iter()
+                .filter(|(_, cs_id)| !used.contains(cs_id))
+                .min_by_key(|(generation, _)| (*generation as i64 - target_generation as i64).abs())
+                .map(|(_, cs_id)| *cs_id);
+            if let Some(cs_id) = closest {
+                used.insert(cs_id);
+                midpoints.push(cs_id);
+            }
+        }
+
+        midpoints.sort_by_key(|cs_id| generations[cs_id]);
+        Ok(midpoints)
+    }
+
     /// Slices ancestors of heads into a sequence of slices for processing.
     ///
     /// Each slice contains a frontier of changesets within a generation range, returning
@@ -107,6 +107,95 @@ impl CommitGraph {
             })))
         }).boxed())
     }
+
+    /// Returns a stream of the first-parent (linear) history of `head`,
+    /// starting at `head` itself and following first-parent edges, up to
+    /// `limit` changesets.
+    ///
+    /// Unlike `linear_ancestors_stream`, which yields bare `ChangesetId`s,
+    /// this yields the `ChangesetNode` already fetched for each changeset
+    /// (carrying its generation and linear depth), so that callers like log
+    /// rendering and changelog export don't need a second per-node round
+    /// trip to look that metadata up. Edges are still prefetched in large
+    /// chunks via `PrefetchTarget::LinearAncestors`, same as for
+    /// `linear_ancestors_stream`.
+    pub async fn first_parent_history(
+        &self,
+        ctx: &CoreContext,
+        head: ChangesetId,
+        limit: u64,
+    ) -> Result<BoxStream<'static, Result<ChangesetNode>>> {
+        if limit == 0 {
+            return Ok(stream::empty().boxed());
+        }
+
+        struct FirstParentHistoryState {
+            commit_graph: CommitGraph,
+            ctx: CoreContext,
+            next: Option<ChangesetId>,
+            remaining: u64,
+        }
+
+        Ok(stream::try_unfold(
+            FirstParentHistoryState {
+                commit_graph: self.clone(),
+                ctx: ctx.clone(),
+                next: Some(head),
+                remaining: limit,
+            },
+            move |state| async move {
+                let FirstParentHistoryState {
+                    commit_graph,
+                    ctx,
+                    next,
+                    remaining,
+                } = state;
+
+                let cs_id = match next {
+                    Some(cs_id) => cs_id,
+                    None => return Ok(None),
+                };
+
+                if remaining == 0 {
+                    return Ok(None);
+                }
+
+                let edges = commit_graph
+                    .storage
+                    .fetch_many_edges(
+                        &ctx,
+                        &[cs_id],
+                        Prefetch::Hint(PrefetchTarget::LinearAncestors {
+                            generation: FIRST_GENERATION,
+                            steps: remaining,
+                        }),
+                    )
+                    .await?
+                    .remove(&cs_id)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Missing changeset from commit graph storage: {} (first_parent_history)",
+                            cs_id
+                        )
+                    })?
+                    .edges();
+
+                let node = edges.node;
+                let next = edges.parents.into_iter().next().map(|parent| parent.cs_id);
+
+                Ok(Some((
+                    node,
+                    FirstParentHistoryState {
+                        commit_graph,
+                        ctx,
+                        next,
+                        remaining: remaining - 1,
+                    },
+                )))
+            },
+        )
+        .boxed())
+    }
 }
 
 /// A builder for a stream of linear ancestors of a changeset.
@@ -704,6 +704,50 @@ impl CommitGraph {
         sorted_segments
     }
 
+    /// Returns the number of changesets that are ancestors of heads, excluding
+    /// all ancestors of common, without materializing any of the changeset ids.
+    ///
+    /// This is cheaper than `ancestors_difference` or
+    /// `ancestors_difference_segments` followed by counting, since segments
+    /// already carry their own length.
+    pub async fn ancestors_difference_segments_count(
+        &self,
+        ctx: &CoreContext,
+        heads: Vec<ChangesetId>,
+        common: Vec<ChangesetId>,
+    ) -> Result<u64> {
+        let segments = self
+            .ancestors_difference_segments(ctx, heads, common)
+            .await?;
+        Ok(segments.iter().map(|segment| segment.length).sum())
+    }
+
+    /// Returns all changesets that are ancestors of heads, excluding all
+    /// ancestors of common, by materializing the compact segments returned by
+    /// `ancestors_difference_segments`. The result is in the same reverse
+    /// topological order as `ancestors_difference`, but avoids a second
+    /// traversal of the commit graph to discover the segments themselves.
+    pub async fn materialized_ancestors_difference_segments(
+        &self,
+        ctx: &CoreContext,
+        heads: Vec<ChangesetId>,
+        common: Vec<ChangesetId>,
+    ) -> Result<Vec<ChangesetId>> {
+        let segments = self
+            .ancestors_difference_segments(ctx, heads, common)
+            .await?;
+        let segments = Self::dfs_order_segments(ctx, segments);
+
+        let mut all_changesets = vec![];
+        for segment in segments {
+            all_changesets.extend(
+                self.segment_changesets(ctx, segment.head, segment.base)
+                    .await?,
+            );
+        }
+        Ok(all_changesets)
+    }
+
     /// Returns all changesets in a segment in reverse topological order, verifying
     /// that there are no merge changesets in the segment except potentially base,
     /// and that base is an ancestor of head.
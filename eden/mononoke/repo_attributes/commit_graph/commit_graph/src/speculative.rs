@@ -0,0 +1,42 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use crate::writer::BaseCommitGraphWriter;
+use crate::CommitGraph;
+
+/// A commit graph overlay for speculative, not-yet-persisted changesets
+/// (e.g. pushrebase candidates, or ephemeral snapshots).
+///
+/// Changesets added through `writer()` are kept purely in memory, layered
+/// on top of the underlying commit graph, so that ancestry and merge-base
+/// queries can see them before (or without ever) writing them to persistent
+/// storage. The overlay, and any speculative changesets added to it, is
+/// discarded once this value is dropped.
+#[derive(Clone)]
+pub struct SpeculativeCommitGraph {
+    commit_graph: CommitGraph,
+}
+
+impl SpeculativeCommitGraph {
+    /// Create a speculative overlay on top of `commit_graph`.
+    pub fn new(commit_graph: &CommitGraph) -> Self {
+        Self {
+            commit_graph: commit_graph.clone().with_memwrites_storage(),
+        }
+    }
+
+    /// The overlaid commit graph, which can be used to run ancestry and
+    /// merge-base queries that also see speculative changesets.
+    pub fn commit_graph(&self) -> &CommitGraph {
+        &self.commit_graph
+    }
+
+    /// A writer that adds changesets only to this speculative overlay.
+    pub fn writer(&self) -> BaseCommitGraphWriter {
+        BaseCommitGraphWriter::new(self.commit_graph.clone())
+    }
+}
@@ -17,6 +17,7 @@ use cloned::cloned;
 use commit_graph::BaseCommitGraphWriter;
 use commit_graph::CommitGraph;
 use commit_graph::CommitGraphWriter;
+use commit_graph::SpeculativeCommitGraph;
 use commit_graph_types::storage::CommitGraphStorage;
 use commit_graph_types::storage::Prefetch;
 use context::CoreContext;
@@ -63,8 +64,12 @@ macro_rules! impl_commit_graph_tests {
             test_add_recursive_many_changesets,
             test_add_many_changesets,
             test_ancestors_frontier_with,
+            test_ancestors_frontier_with_batch,
             test_range_stream,
+            test_range,
             test_common_base,
+            test_gca_many,
+            test_bisect_midpoints,
             test_slice_ancestors,
             test_segmented_slice_ancestors,
             test_children,
@@ -72,12 +77,16 @@ macro_rules! impl_commit_graph_tests {
             test_ancestors_difference_segments_1,
             test_ancestors_difference_segments_2,
             test_ancestors_difference_segments_3,
+            test_ancestors_difference_segments_count_and_materialize,
+            test_speculative_commit_graph,
+            test_many_changeset_annotations,
             test_locations_to_changeset_ids,
             test_changeset_ids_to_locations,
             test_process_topologically,
             test_minimize_frontier,
             test_ancestors_within_distance,
             test_linear_ancestors_stream,
+            test_first_parent_history,
         );
     };
 }
@@ -1277,6 +1286,74 @@ pub async fn test_ancestors_frontier_with(
     Ok(())
 }
 
+pub async fn test_ancestors_frontier_with_batch(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+         A-B-C-D-G-H---J-K
+            \   /   \ /
+             E-F     I
+
+         L-M-N-O-P-Q-R-S-T-U
+         ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    let set1 = ["A", "B", "C", "D", "E", "F", "G", "H", "I"]
+        .into_iter()
+        .map(name_cs_id)
+        .collect::<HashSet<_>>();
+
+    assert_ancestors_frontier_with_batch(
+        &graph,
+        &ctx,
+        vec!["K", "U"],
+        move |cs_ids| {
+            cloned!(set1);
+            async move {
+                Ok(cs_ids
+                    .into_iter()
+                    .filter(|cs_id| set1.contains(cs_id))
+                    .collect::<HashSet<_>>())
+            }
+        },
+        vec!["H", "I"],
+    )
+    .await?;
+
+    let set2 = ["A", "B", "C", "E"]
+        .into_iter()
+        .map(name_cs_id)
+        .collect::<HashSet<_>>();
+
+    assert_ancestors_frontier_with_batch(
+        &graph,
+        &ctx,
+        vec!["D", "F"],
+        {
+            cloned!(set2);
+            move |cs_ids| {
+                cloned!(set2);
+                async move {
+                    Ok(cs_ids
+                        .into_iter()
+                        .filter(|cs_id| set2.contains(cs_id))
+                        .collect::<HashSet<_>>())
+                }
+            }
+        },
+        vec!["C", "E"],
+    )
+    .await?;
+
+    Ok(())
+}
+
 pub async fn test_range_stream(
     ctx: CoreContext,
     storage: Arc<dyn CommitGraphStorageTest>,
@@ -1344,6 +1421,101 @@ pub async fn test_common_base(
     Ok(())
 }
 
+pub async fn test_gca_many(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+         A-B-C-D-G-H---J-K
+            \   /   \ /
+             E-F     I
+
+         L-M-N-O-P-Q-R-S-T-U
+         ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    assert_gca_many(&graph, &ctx, vec!["D"], vec!["D"]).await?;
+    assert_gca_many(&graph, &ctx, vec!["D", "F"], vec!["B"]).await?;
+    assert_gca_many(&graph, &ctx, vec!["D", "F", "H"], vec!["B"]).await?;
+    assert_gca_many(&graph, &ctx, vec!["G", "H", "K"], vec!["G"]).await?;
+    assert_gca_many(&graph, &ctx, vec!["K", "U"], vec![]).await?;
+
+    Ok(())
+}
+
+pub async fn test_range(ctx: CoreContext, storage: Arc<dyn CommitGraphStorageTest>) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+         A-B-C-D-G-H---J-K
+            \   /   \ /
+             E-F     I
+
+         L-M-N-O-P-Q-R-S-T-U
+         ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    assert_range(
+        &graph,
+        &ctx,
+        vec!["A"],
+        vec!["K"],
+        vec!["A", "B", "C", "D", "E", "F", "G", "H", "I", "J", "K"],
+    )
+    .await?;
+    assert_range(
+        &graph,
+        &ctx,
+        vec!["D", "E"],
+        vec!["K"],
+        vec!["D", "E", "F", "G", "H", "I", "J", "K"],
+    )
+    .await?;
+    assert_range(&graph, &ctx, vec!["A"], vec!["U"], vec![]).await?;
+    assert_range(
+        &graph,
+        &ctx,
+        vec!["E", "G"],
+        vec!["K", "H"],
+        vec!["E", "F", "G", "H", "I", "J", "K"],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn test_bisect_midpoints(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(&ctx, "A-B-C-D-E-F-G-H-I-J-K", storage.clone()).await?;
+    storage.flush();
+
+    assert_bisect_midpoints(&graph, &ctx, "A", "K", 3, vec!["D", "F", "H"]).await?;
+    assert_bisect_midpoints(&graph, &ctx, "A", "K", 1, vec!["F"]).await?;
+    assert_bisect_midpoints(&graph, &ctx, "A", "B", 3, vec![]).await?;
+    assert_bisect_midpoints(&graph, &ctx, "A", "K", 0, vec![]).await?;
+    assert_bisect_midpoints(
+        &graph,
+        &ctx,
+        "A",
+        "K",
+        100,
+        vec!["B", "C", "D", "E", "F", "G", "H", "I", "J"],
+    )
+    .await?;
+
+    Ok(())
+}
+
 pub async fn test_slice_ancestors(
     ctx: CoreContext,
     storage: Arc<dyn CommitGraphStorageTest>,
@@ -1710,6 +1882,127 @@ pub async fn test_ancestors_difference_segments_3(
     Ok(())
 }
 
+pub async fn test_ancestors_difference_segments_count_and_materialize(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+        A-B-C-D-E---L------N----O
+           \         \    /
+            F-G-H     M  /
+             \       /  /
+              I-J---K--/---Q---R
+                 \
+                  \---------P
+        ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    assert_ancestors_difference_segments_count_and_materialize(&ctx, &graph, vec!["N"], vec![])
+        .await?;
+    assert_ancestors_difference_segments_count_and_materialize(&ctx, &graph, vec!["N"], vec!["D"])
+        .await?;
+    assert_ancestors_difference_segments_count_and_materialize(
+        &ctx,
+        &graph,
+        vec!["O", "P"],
+        vec!["D", "I"],
+    )
+    .await?;
+    assert_ancestors_difference_segments_count_and_materialize(&ctx, &graph, vec!["F"], vec!["H"])
+        .await?;
+    assert_ancestors_difference_segments_count_and_materialize(
+        &ctx,
+        &graph,
+        vec!["N", "R"],
+        vec![],
+    )
+    .await?;
+
+    Ok(())
+}
+
+pub async fn test_speculative_commit_graph(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+        A-B-C
+        ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    let speculative = SpeculativeCommitGraph::new(&graph);
+    let speculative_cs_id = name_cs_id("speculative");
+    speculative
+        .writer()
+        .add(&ctx, speculative_cs_id, smallvec![name_cs_id("C")])
+        .await?;
+
+    // The speculative changeset is visible through the overlay...
+    assert!(
+        speculative
+            .commit_graph()
+            .is_ancestor(&ctx, name_cs_id("A"), speculative_cs_id)
+            .await?
+    );
+
+    // ...but was never persisted to the underlying storage.
+    assert!(!graph.exists(&ctx, speculative_cs_id).await?);
+
+    Ok(())
+}
+
+pub async fn test_many_changeset_annotations(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+         A-B-C-D-G-H---J-K
+            \   /   \ /
+             E-F     I
+        ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    let cs_ids = ["A", "C", "H", "K"].map(name_cs_id);
+    let annotations = graph.many_changeset_annotations(&ctx, &cs_ids).await?;
+
+    for cs_id in cs_ids {
+        let annotation = annotations.get(&cs_id).unwrap();
+        assert_eq!(
+            annotation.generation,
+            graph.changeset_generation(&ctx, cs_id).await?
+        );
+        assert_eq!(
+            annotation.p1_linear_depth,
+            graph.changeset_linear_depth(&ctx, cs_id).await?
+        );
+    }
+
+    assert_eq!(
+        annotations
+            .get(&name_cs_id("H"))
+            .unwrap()
+            .skip_tree_skew_ancestor,
+        Some(name_cs_id("A"))
+    );
+
+    Ok(())
+}
+
 pub async fn test_locations_to_changeset_ids(
     ctx: CoreContext,
     storage: Arc<dyn CommitGraphStorageTest>,
@@ -2019,6 +2312,55 @@ pub async fn test_ancestors_within_distance(
     Ok(())
 }
 
+pub async fn test_first_parent_history(
+    ctx: CoreContext,
+    storage: Arc<dyn CommitGraphStorageTest>,
+) -> Result<()> {
+    let graph = from_dag(
+        &ctx,
+        r"
+        P     O
+        |     |
+        N     |
+        |\    |
+        | \   M
+        |  \  |
+        J   \ |   L
+        |    \|  /
+        |     K /
+        |  Q  |/
+        | /   I
+        |/ G  |
+        E  |  H
+        |  F /
+        D  |/
+        |  |
+        C /
+        |/
+        B
+        |
+        A
+        ",
+        storage.clone(),
+    )
+    .await?;
+    storage.flush();
+
+    assert_first_parent_history(
+        &ctx,
+        &graph,
+        "P",
+        100,
+        vec!["P", "N", "J", "E", "D", "C", "B", "A"],
+    )
+    .await?;
+    assert_first_parent_history(&ctx, &graph, "P", 3, vec!["P", "N", "J"]).await?;
+    assert_first_parent_history(&ctx, &graph, "P", 0, vec![]).await?;
+    assert_first_parent_history(&ctx, &graph, "A", 100, vec!["A"]).await?;
+
+    Ok(())
+}
+
 pub async fn test_linear_ancestors_stream(
     ctx: CoreContext,
     storage: Arc<dyn CommitGraphStorageTest>,
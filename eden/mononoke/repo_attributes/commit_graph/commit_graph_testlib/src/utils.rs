@@ -342,6 +342,33 @@ where
     Ok(())
 }
 
+pub async fn assert_ancestors_frontier_with_batch<BatchProperty, Out>(
+    graph: &CommitGraph,
+    ctx: &CoreContext,
+    heads: Vec<&str>,
+    batch_property_fn: BatchProperty,
+    ancestors_frontier: Vec<&str>,
+) -> Result<()>
+where
+    BatchProperty: Fn(Vec<ChangesetId>) -> Out + Send + Sync + 'static,
+    Out: Future<Output = Result<HashSet<ChangesetId>>>,
+{
+    let heads = heads.into_iter().map(name_cs_id).collect();
+
+    assert_eq!(
+        graph
+            .ancestors_frontier_with_batch(ctx, heads, batch_property_fn)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>(),
+        ancestors_frontier
+            .into_iter()
+            .map(name_cs_id)
+            .collect::<HashSet<_>>()
+    );
+    Ok(())
+}
+
 pub async fn assert_p1_linear_skew_ancestor(
     storage: &Arc<dyn CommitGraphStorageTest>,
     ctx: &CoreContext,
@@ -415,6 +442,65 @@ pub async fn assert_common_base(
     Ok(())
 }
 
+pub async fn assert_gca_many(
+    graph: &CommitGraph,
+    ctx: &CoreContext,
+    heads: Vec<&str>,
+    gca: Vec<&str>,
+) -> Result<()> {
+    let heads = heads.into_iter().map(name_cs_id).collect();
+
+    assert_eq!(
+        graph
+            .gca_many(ctx, heads)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>(),
+        gca.into_iter().map(name_cs_id).collect::<HashSet<_>>()
+    );
+    Ok(())
+}
+
+pub async fn assert_range(
+    graph: &CommitGraph,
+    ctx: &CoreContext,
+    roots: Vec<&str>,
+    heads: Vec<&str>,
+    range: Vec<&str>,
+) -> Result<()> {
+    let roots = roots.into_iter().map(name_cs_id).collect();
+    let heads = heads.into_iter().map(name_cs_id).collect();
+
+    let range_cs_ids = graph.range(ctx, roots, heads).await?;
+
+    assert_topological_order(graph, ctx, &range_cs_ids).await?;
+
+    assert_eq!(
+        range_cs_ids.into_iter().collect::<HashSet<_>>(),
+        range.into_iter().map(name_cs_id).collect::<HashSet<_>>()
+    );
+    Ok(())
+}
+
+pub async fn assert_bisect_midpoints(
+    graph: &CommitGraph,
+    ctx: &CoreContext,
+    good: &str,
+    bad: &str,
+    count: u64,
+    midpoints: Vec<&str>,
+) -> Result<()> {
+    let midpoints_cs_ids = graph
+        .bisect_midpoints(ctx, name_cs_id(good), name_cs_id(bad), count)
+        .await?;
+
+    assert_eq!(
+        midpoints_cs_ids,
+        midpoints.into_iter().map(name_cs_id).collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
 pub async fn assert_slice_ancestors<NeedsProcessing, Out>(
     graph: &CommitGraph,
     ctx: &CoreContext,
@@ -546,6 +632,35 @@ pub async fn assert_ancestors_difference_segments(
     Ok(())
 }
 
+pub async fn assert_ancestors_difference_segments_count_and_materialize(
+    ctx: &CoreContext,
+    graph: &CommitGraph,
+    heads: Vec<&str>,
+    common: Vec<&str>,
+) -> Result<()> {
+    let heads: Vec<_> = heads.into_iter().map(name_cs_id).collect();
+    let common: Vec<_> = common.into_iter().map(name_cs_id).collect();
+
+    let expected = graph
+        .ancestors_difference(ctx, heads.clone(), common.clone())
+        .await?
+        .into_iter()
+        .collect::<HashSet<_>>();
+
+    let count = graph
+        .ancestors_difference_segments_count(ctx, heads.clone(), common.clone())
+        .await?;
+    assert_eq!(count as usize, expected.len());
+
+    let materialized = graph
+        .materialized_ancestors_difference_segments(ctx, heads, common)
+        .await?;
+    assert_eq!(materialized.len(), expected.len());
+    assert_eq!(materialized.into_iter().collect::<HashSet<_>>(), expected);
+
+    Ok(())
+}
+
 pub async fn assert_locations_to_changeset_ids(
     ctx: &CoreContext,
     graph: &CommitGraph,
@@ -794,3 +909,28 @@ pub async fn assert_linear_ancestors_stream(
 
     Ok(())
 }
+
+pub async fn assert_first_parent_history(
+    ctx: &CoreContext,
+    graph: &CommitGraph,
+    head: &str,
+    limit: u64,
+    expected_output: Vec<&str>,
+) -> Result<()> {
+    let head = name_cs_id(head);
+    let expected_output = expected_output
+        .into_iter()
+        .map(name_cs_id)
+        .collect::<Vec<_>>();
+
+    let history = graph
+        .first_parent_history(ctx, head, limit)
+        .await?
+        .map_ok(|node| node.cs_id)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+    assert_eq!(history, expected_output);
+
+    Ok(())
+}
@@ -54,6 +54,41 @@ impl ChangesetNode {
         })
     }
 }
+/// A bundle of the per-changeset annotations that are cheapest to serve
+/// together, since they all live on the same `ChangesetEdges` record:
+/// the generation number, the first-parent linear depth, and the
+/// skew-binary ancestor pointers used to jump backwards through history
+/// in O(log n) steps.
+///
+/// This is intended for bulk callers (e.g. "commits behind master"
+/// counters, or graph renderers) that would otherwise need a separate
+/// batch query per annotation.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ChangesetAnnotations {
+    /// The changeset's generation number.
+    pub generation: Generation,
+
+    /// The changeset's first-parent linear depth.
+    pub p1_linear_depth: u64,
+
+    /// The nearest skew-binary ancestor in the skip tree, if any.
+    pub skip_tree_skew_ancestor: Option<ChangesetId>,
+
+    /// The nearest skew-binary ancestor in the p1-linear tree, if any.
+    pub p1_linear_skew_ancestor: Option<ChangesetId>,
+}
+
+impl From<&ChangesetEdges> for ChangesetAnnotations {
+    fn from(edges: &ChangesetEdges) -> Self {
+        Self {
+            generation: edges.node.generation,
+            p1_linear_depth: edges.node.p1_linear_depth,
+            skip_tree_skew_ancestor: edges.skip_tree_skew_ancestor.map(|node| node.cs_id),
+            p1_linear_skew_ancestor: edges.p1_linear_skew_ancestor.map(|node| node.cs_id),
+        }
+    }
+}
+
 /// The parents of a changeset.
 ///
 /// This uses a smallvec, as there is usually exactly one.
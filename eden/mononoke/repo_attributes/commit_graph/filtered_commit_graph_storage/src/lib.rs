@@ -0,0 +1,170 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Filtered Commit Graph Storage
+//!
+//! A `CommitGraphStorage` decorator that hides a configured set of
+//! changesets (e.g. purged or embargoed commits) from traversal, so that
+//! unauthorized callers can't reach them through the commit graph. Hiding
+//! is enforced once, here, rather than relying on every API built on top
+//! of the commit graph to post-filter its own results.
+
+mod errors;
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use anyhow::bail;
+use anyhow::Result;
+use async_trait::async_trait;
+use commit_graph_types::edges::ChangesetEdges;
+use commit_graph_types::storage::CommitGraphStorage;
+use commit_graph_types::storage::FetchedChangesetEdges;
+use commit_graph_types::storage::Prefetch;
+use context::CoreContext;
+use mononoke_types::ChangesetId;
+use mononoke_types::ChangesetIdPrefix;
+use mononoke_types::ChangesetIdsResolvedFromPrefix;
+use mononoke_types::RepositoryId;
+use vec1::Vec1;
+
+pub use crate::errors::ErrorKind;
+
+#[cfg(test)]
+mod tests;
+
+/// The set of changesets that are hidden from this view of the commit
+/// graph.
+///
+/// This is expected to already be the fully expanded set of hidden
+/// changesets (e.g. the configured roots together with all of their
+/// descendants), rather than just the configured roots, so that membership
+/// can be checked with a single lookup.
+#[derive(Clone, Debug, Default)]
+pub struct HiddenChangesets(Arc<HashSet<ChangesetId>>);
+
+impl HiddenChangesets {
+    pub fn new(hidden: HashSet<ChangesetId>) -> Self {
+        Self(Arc::new(hidden))
+    }
+
+    pub fn is_hidden(&self, cs_id: ChangesetId) -> bool {
+        self.0.contains(&cs_id)
+    }
+}
+
+/// A storage backend for the commit graph that hides a configured set of
+/// changesets from an underlying storage backend.
+pub struct FilteredCommitGraphStorage {
+    inner: Arc<dyn CommitGraphStorage>,
+    hidden: HiddenChangesets,
+}
+
+impl FilteredCommitGraphStorage {
+    pub fn new(inner: Arc<dyn CommitGraphStorage>, hidden: HiddenChangesets) -> Self {
+        Self { inner, hidden }
+    }
+}
+
+#[async_trait]
+impl CommitGraphStorage for FilteredCommitGraphStorage {
+    fn repo_id(&self) -> RepositoryId {
+        self.inner.repo_id()
+    }
+
+    async fn add(&self, ctx: &CoreContext, edges: ChangesetEdges) -> Result<bool> {
+        self.inner.add(ctx, edges).await
+    }
+
+    async fn add_many(&self, ctx: &CoreContext, many_edges: Vec1<ChangesetEdges>) -> Result<usize> {
+        self.inner.add_many(ctx, many_edges).await
+    }
+
+    async fn fetch_edges(&self, ctx: &CoreContext, cs_id: ChangesetId) -> Result<ChangesetEdges> {
+        if self.hidden.is_hidden(cs_id) {
+            bail!(ErrorKind::Hidden(cs_id));
+        }
+        self.inner.fetch_edges(ctx, cs_id).await
+    }
+
+    async fn maybe_fetch_edges(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Option<ChangesetEdges>> {
+        if self.hidden.is_hidden(cs_id) {
+            return Ok(None);
+        }
+        self.inner.maybe_fetch_edges(ctx, cs_id).await
+    }
+
+    async fn fetch_many_edges(
+        &self,
+        ctx: &CoreContext,
+        cs_ids: &[ChangesetId],
+        prefetch: Prefetch,
+    ) -> Result<HashMap<ChangesetId, FetchedChangesetEdges>> {
+        if let Some(cs_id) = cs_ids.iter().find(|cs_id| self.hidden.is_hidden(**cs_id)) {
+            bail!(ErrorKind::Hidden(*cs_id));
+        }
+        self.inner.fetch_many_edges(ctx, cs_ids, prefetch).await
+    }
+
+    async fn maybe_fetch_many_edges(
+        &self,
+        ctx: &CoreContext,
+        cs_ids: &[ChangesetId],
+        prefetch: Prefetch,
+    ) -> Result<HashMap<ChangesetId, FetchedChangesetEdges>> {
+        let visible_ids = cs_ids
+            .iter()
+            .copied()
+            .filter(|cs_id| !self.hidden.is_hidden(*cs_id))
+            .collect::<Vec<_>>();
+        self.inner
+            .maybe_fetch_many_edges(ctx, &visible_ids, prefetch)
+            .await
+    }
+
+    async fn find_by_prefix(
+        &self,
+        ctx: &CoreContext,
+        cs_prefix: ChangesetIdPrefix,
+        limit: usize,
+    ) -> Result<ChangesetIdsResolvedFromPrefix> {
+        let resolved = self.inner.find_by_prefix(ctx, cs_prefix, limit).await?;
+        let is_too_many = matches!(resolved, ChangesetIdsResolvedFromPrefix::TooMany(_));
+        let visible = resolved
+            .to_vec()
+            .into_iter()
+            .filter(|cs_id| !self.hidden.is_hidden(*cs_id))
+            .collect::<Vec<_>>();
+        Ok(if is_too_many {
+            ChangesetIdsResolvedFromPrefix::TooMany(visible)
+        } else {
+            ChangesetIdsResolvedFromPrefix::from_vec_and_limit(visible, limit)
+        })
+    }
+
+    async fn fetch_children(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Vec<ChangesetId>> {
+        if self.hidden.is_hidden(cs_id) {
+            bail!(ErrorKind::Hidden(cs_id));
+        }
+        Ok(self
+            .inner
+            .fetch_children(ctx, cs_id)
+            .await?
+            .into_iter()
+            .filter(|cs_id| !self.hidden.is_hidden(*cs_id))
+            .collect())
+    }
+}
@@ -0,0 +1,109 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::future::Future;
+use std::sync::Arc;
+
+use anyhow::Result;
+use commit_graph_testlib::*;
+use commit_graph_types::storage::CommitGraphStorage;
+use commit_graph_types::storage::Prefetch;
+use context::CoreContext;
+use fbinit::FacebookInit;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use rendezvous::RendezVousOptions;
+use sql_commit_graph_storage::SqlCommitGraphStorageBuilder;
+use sql_construct::SqlConstruct;
+
+use crate::FilteredCommitGraphStorage;
+use crate::HiddenChangesets;
+
+impl CommitGraphStorageTest for FilteredCommitGraphStorage {}
+
+async fn run_test<Fut>(
+    fb: FacebookInit,
+    test_function: impl FnOnce(CoreContext, Arc<dyn CommitGraphStorageTest>) -> Fut,
+) -> Result<()>
+where
+    Fut: Future<Output = Result<()>>,
+{
+    let ctx = CoreContext::test_mock(fb);
+    let storage = Arc::new(FilteredCommitGraphStorage::new(
+        Arc::new(
+            SqlCommitGraphStorageBuilder::with_sqlite_in_memory()
+                .unwrap()
+                .build(RendezVousOptions::for_test(), RepositoryId::new(1)),
+        ),
+        HiddenChangesets::default(),
+    ));
+    test_function(ctx, storage).await
+}
+
+impl_commit_graph_tests!(run_test);
+
+fn cs_id(name: &str) -> ChangesetId {
+    let mut bytes = [0; 32];
+    bytes[..name.len()].copy_from_slice(name.as_bytes());
+    ChangesetId::from_bytes(bytes).expect("Changeset ID should be valid")
+}
+
+fn storage(hidden: HiddenChangesets) -> FilteredCommitGraphStorage {
+    FilteredCommitGraphStorage::new(
+        Arc::new(
+            SqlCommitGraphStorageBuilder::with_sqlite_in_memory()
+                .unwrap()
+                .build(RendezVousOptions::for_test(), RepositoryId::new(1)),
+        ),
+        hidden,
+    )
+}
+
+#[fbinit::test]
+async fn test_fetch_edges_hides_hidden_changeset(fb: FacebookInit) -> Result<()> {
+    let ctx = CoreContext::test_mock(fb);
+    let hidden_cs_id = cs_id("hidden");
+    let storage = storage(HiddenChangesets::new(maplit::hashset! { hidden_cs_id }));
+
+    assert!(storage.fetch_edges(&ctx, hidden_cs_id).await.is_err());
+    assert_eq!(storage.maybe_fetch_edges(&ctx, hidden_cs_id).await?, None);
+
+    Ok(())
+}
+
+#[fbinit::test]
+async fn test_fetch_many_edges_hides_hidden_changeset(fb: FacebookInit) -> Result<()> {
+    let ctx = CoreContext::test_mock(fb);
+    let hidden_cs_id = cs_id("hidden");
+    let visible_cs_id = cs_id("visible");
+    let storage = storage(HiddenChangesets::new(maplit::hashset! { hidden_cs_id }));
+
+    assert!(
+        storage
+            .fetch_many_edges(&ctx, &[hidden_cs_id, visible_cs_id], Prefetch::None)
+            .await
+            .is_err()
+    );
+
+    let fetched = storage
+        .maybe_fetch_many_edges(&ctx, &[hidden_cs_id, visible_cs_id], Prefetch::None)
+        .await?;
+    assert!(!fetched.contains_key(&hidden_cs_id));
+
+    Ok(())
+}
+
+#[fbinit::test]
+async fn test_fetch_children_hides_hidden_changeset(fb: FacebookInit) -> Result<()> {
+    let ctx = CoreContext::test_mock(fb);
+    let hidden_cs_id = cs_id("hidden");
+    let storage = storage(HiddenChangesets::new(maplit::hashset! { hidden_cs_id }));
+
+    assert!(storage.fetch_children(&ctx, hidden_cs_id).await.is_err());
+
+    Ok(())
+}
@@ -9,6 +9,7 @@
 //! become ancestors of a particular public bookmark.  The hook manager
 //! ensures that commits meet the constraints that the hooks require.
 
+pub mod bypass_token;
 pub mod errors;
 pub mod manager;
 pub mod provider;
@@ -30,6 +31,9 @@ use mononoke_types::BonsaiChangeset;
 use mononoke_types::ChangesetId;
 use mononoke_types::NonRootMPath;
 
+pub use crate::bypass_token::issue_bypass_token;
+pub use crate::bypass_token::verify_bypass_token;
+pub use crate::bypass_token::BypassTokenClaims;
 pub use crate::errors::HookManagerError;
 pub use crate::errors::HookStateProviderError;
 pub use crate::manager::HookManager;
@@ -7,19 +7,27 @@
 
 use std::collections::HashMap;
 use std::str;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
 use anyhow::Error;
 use anyhow::Result;
 use bookmarks_types::BookmarkKey;
 use bytes::Bytes;
 use context::CoreContext;
+use dashmap::DashMap;
 use fbinit::FacebookInit;
-use futures::stream::futures_unordered::FuturesUnordered;
+use futures::stream;
 use futures::stream::TryStreamExt;
 use futures::try_join;
 use futures::Future;
+use futures::StreamExt;
 use futures::TryFutureExt;
-use futures_stats::TimedFutureExt;
+use hook_outcome_store::ArcHookOutcomeStore;
+use hook_outcome_store::HookOutcomeRecord;
+use hook_outcome_store::HookOutcomeStore;
+use hook_outcome_store::NullHookOutcomeStore;
 use metaconfig_types::BookmarkOrRegex;
 use metaconfig_types::HookBypass;
 use metaconfig_types::HookConfig;
@@ -28,6 +36,7 @@ use mononoke_types::BasicFileChange;
 use mononoke_types::BonsaiChangeset;
 use mononoke_types::ChangesetId;
 use mononoke_types::NonRootMPath;
+use mononoke_types::Timestamp;
 use permission_checker::AclProvider;
 use permission_checker::ArcMembershipChecker;
 use permission_checker::NeverMember;
@@ -35,6 +44,7 @@ use regex::Regex;
 use scuba::builder::ServerData;
 use scuba_ext::MononokeScubaSampleBuilder;
 use slog::debug;
+use slog::warn;
 
 use crate::errors::HookManagerError;
 use crate::provider::HookStateProvider;
@@ -47,6 +57,24 @@ use crate::HookExecution;
 use crate::HookOutcome;
 use crate::PushAuthoredBy;
 
+/// Default cap on the number of hook executions that are allowed to run
+/// concurrently for a single `run_hooks_for_bookmark` call, used when
+/// `HookManagerParams::max_concurrent_hook_executions` is not set.
+const DEFAULT_MAX_CONCURRENT_HOOK_EXECUTIONS: usize = 100;
+
+/// How long a tripped circuit breaker stays fully open before a hook is
+/// given a single probe execution to check whether it has recovered.
+const CIRCUIT_BREAKER_COOLDOWN: Duration = Duration::from_secs(300);
+
+/// Per-hook state tracked by the circuit breaker: how many consecutive
+/// executions have failed, and (once tripped) when the breaker opened, so
+/// that it can be half-opened again for a probe after `CIRCUIT_BREAKER_COOLDOWN`.
+#[derive(Default)]
+struct CircuitBreakerState {
+    failure_count: u32,
+    tripped_at: Option<Instant>,
+}
+
 /// Manages hooks and allows them to be installed and uninstalled given a name
 /// Knows how to run hooks
 
@@ -62,6 +90,18 @@ pub struct HookManager {
     scuba: MononokeScubaSampleBuilder,
     all_hooks_bypassed: bool,
     scuba_bypassed_commits: MononokeScubaSampleBuilder,
+    max_concurrent_hook_executions: usize,
+    circuit_breaker_failure_threshold: Option<u32>,
+    /// Per-hook circuit breaker state for non-critical hooks, used to
+    /// disable persistently failing hooks. Cleared whenever the hook
+    /// succeeds, including on the probe execution after the cooldown.
+    hook_failure_counts: DashMap<String, CircuitBreakerState>,
+    /// Where hook execution outcomes are persisted so they can be queried
+    /// after the fact.
+    hook_outcome_store: ArcHookOutcomeStore,
+    /// Key used to verify signed bypass tokens passed via the
+    /// `hook_bypass_token` pushvar. `None` means bypass tokens are rejected.
+    bypass_token_signing_key: Option<Vec<u8>>,
 }
 
 impl HookManager {
@@ -72,6 +112,7 @@ impl HookManager {
         hook_manager_params: HookManagerParams,
         mut scuba: MononokeScubaSampleBuilder,
         repo_name: String,
+        hook_outcome_store: ArcHookOutcomeStore,
     ) -> Result<HookManager> {
         let hooks = HashMap::new();
 
@@ -106,6 +147,16 @@ impl HookManager {
             scuba,
             all_hooks_bypassed: hook_manager_params.all_hooks_bypassed,
             scuba_bypassed_commits,
+            max_concurrent_hook_executions: hook_manager_params
+                .max_concurrent_hook_executions
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_HOOK_EXECUTIONS),
+            circuit_breaker_failure_threshold: hook_manager_params
+                .circuit_breaker_failure_threshold,
+            hook_failure_counts: DashMap::new(),
+            hook_outcome_store,
+            bypass_token_signing_key: hook_manager_params
+                .bypass_token_signing_key
+                .map(String::into_bytes),
         })
     }
 
@@ -122,6 +173,11 @@ impl HookManager {
             scuba: MononokeScubaSampleBuilder::with_discard(),
             all_hooks_bypassed: false,
             scuba_bypassed_commits: MononokeScubaSampleBuilder::with_discard(),
+            max_concurrent_hook_executions: DEFAULT_MAX_CONCURRENT_HOOK_EXECUTIONS,
+            circuit_breaker_failure_threshold: None,
+            hook_failure_counts: DashMap::new(),
+            hook_outcome_store: Arc::new(NullHookOutcomeStore),
+            bypass_token_signing_key: None,
         }
     }
 
@@ -218,7 +274,7 @@ impl HookManager {
 
         let hooks = self.hooks_for_bookmark(bookmark);
 
-        let futs = FuturesUnordered::new();
+        let mut futs = Vec::new();
 
         let mut scuba = self.scuba.clone();
         let username = ctx.metadata().unix_name();
@@ -248,6 +304,17 @@ impl HookManager {
                 continue;
             }
 
+            if let Some(bypass_reason) = get_token_bypass_reason(
+                self.bypass_token_signing_key.as_deref(),
+                hook_name,
+                bookmark,
+                maybe_pushvars,
+            ) {
+                scuba.add("bypass_reason", bypass_reason);
+                scuba.log();
+                continue;
+            }
+
             for future in hook.get_futures(
                 ctx,
                 bookmark,
@@ -258,11 +325,19 @@ impl HookManager {
                 cross_repo_push_source,
                 push_authored_by,
                 hook.get_config().log_only,
+                hook.get_config().timeout,
+                hook.get_config().critical,
+                &self.hook_failure_counts,
+                self.circuit_breaker_failure_threshold,
+                &self.hook_outcome_store,
             ) {
                 futs.push(future);
             }
         }
-        futs.try_collect().await
+        stream::iter(futs)
+            .buffer_unordered(self.max_concurrent_hook_executions)
+            .try_collect()
+            .await
     }
 }
 
@@ -296,6 +371,50 @@ fn get_bypass_reason(
     None
 }
 
+/// Check whether the `hook_bypass_token` pushvar carries a valid, unexpired
+/// bypass token that authorizes skipping `hook_name` for `bookmark`.
+fn get_token_bypass_reason(
+    signing_key: Option<&[u8]>,
+    hook_name: &str,
+    bookmark: &BookmarkKey,
+    maybe_pushvars: Option<&HashMap<String, Bytes>>,
+) -> Option<String> {
+    let signing_key = signing_key?;
+    let pushvars = maybe_pushvars?;
+    let token_bytes = pushvars.get("hook_bypass_token")?;
+    let token = str::from_utf8(token_bytes).ok()?;
+
+    let claims = crate::bypass_token::verify_bypass_token(signing_key, token)?;
+    if !claims.permits(hook_name, &bookmark.to_string(), &Timestamp::now()) {
+        return None;
+    }
+
+    Some(format!("bypass token (issuer={})", claims.issuer))
+}
+
+/// Run `fut` to completion, or fail it early with an error if `timeout` is
+/// set and elapses first.
+async fn run_with_optional_timeout<F>(
+    fut: F,
+    timeout: Option<Duration>,
+    hook_name: &str,
+) -> Result<HookOutcome, Error>
+where
+    F: Future<Output = Result<HookOutcome, Error>>,
+{
+    match timeout {
+        Some(duration) => match tokio::time::timeout(duration, fut).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow::anyhow!(
+                "hook {} timed out after {:?}",
+                hook_name,
+                duration
+            )),
+        },
+        None => fut.await,
+    }
+}
+
 enum Hook {
     Changeset(Box<dyn ChangesetHook>, HookConfig),
     File(Box<dyn FileHook>, HookConfig),
@@ -311,6 +430,27 @@ enum HookInstance<'a> {
 }
 
 impl<'a> HookInstance<'a> {
+    fn accepted_outcome(&self, hook_name: &str, cs_id: ChangesetId) -> HookOutcome {
+        match self {
+            Self::Changeset(_) => HookOutcome::ChangesetHook(
+                ChangesetHookExecutionId {
+                    cs_id,
+                    hook_name: hook_name.to_string(),
+                },
+                HookExecution::Accepted,
+            ),
+            Self::File(_, path, _) => HookOutcome::FileHook(
+                FileHookExecutionId {
+                    cs_id,
+                    path: (*path).clone(),
+                    hook_name: hook_name.to_string(),
+                },
+                HookExecution::Accepted,
+            ),
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn run(
         self,
         ctx: &CoreContext,
@@ -323,49 +463,80 @@ impl<'a> HookInstance<'a> {
         cross_repo_push_source: CrossRepoPushSource,
         push_authored_by: PushAuthoredBy,
         log_only: bool,
+        timeout: Option<Duration>,
+        critical: bool,
+        failure_counts: &DashMap<String, CircuitBreakerState>,
+        circuit_breaker_failure_threshold: Option<u32>,
+        hook_outcome_store: ArcHookOutcomeStore,
     ) -> Result<HookOutcome, Error> {
-        let (stats, mut result) = match self {
+        if !critical {
+            if let Some(threshold) = circuit_breaker_failure_threshold {
+                // Once the breaker has been open for the cooldown period, let a
+                // single probe execution through to check whether the hook has
+                // recovered, rather than keeping it disabled forever.
+                let breaker_open = failure_counts.get(hook_name).is_some_and(|state| {
+                    state.failure_count >= threshold
+                        && state.tripped_at.is_some_and(|tripped_at| {
+                            tripped_at.elapsed() < CIRCUIT_BREAKER_COOLDOWN
+                        })
+                });
+                if breaker_open {
+                    scuba.add("circuit_breaker_tripped", true).log();
+                    return Ok(self.accepted_outcome(hook_name, cs_id));
+                }
+            }
+        }
+
+        let started = Instant::now();
+        let fallback_outcome = self.accepted_outcome(hook_name, cs_id);
+        let mut result = match self {
             Self::Changeset(hook) => {
-                hook.run(
-                    ctx,
-                    bookmark,
-                    cs,
-                    content_provider,
-                    cross_repo_push_source,
-                    push_authored_by,
-                )
-                .map_ok(|exec| {
-                    HookOutcome::ChangesetHook(
-                        ChangesetHookExecutionId {
-                            cs_id,
-                            hook_name: hook_name.to_string(),
-                        },
-                        exec,
+                run_with_optional_timeout(
+                    hook.run(
+                        ctx,
+                        bookmark,
+                        cs,
+                        content_provider,
+                        cross_repo_push_source,
+                        push_authored_by,
                     )
-                })
-                .timed()
+                    .map_ok(|exec| {
+                        HookOutcome::ChangesetHook(
+                            ChangesetHookExecutionId {
+                                cs_id,
+                                hook_name: hook_name.to_string(),
+                            },
+                            exec,
+                        )
+                    }),
+                    timeout,
+                    hook_name,
+                )
                 .await
             }
             Self::File(hook, path, change) => {
-                hook.run(
-                    ctx,
-                    content_provider,
-                    change,
-                    path,
-                    cross_repo_push_source,
-                    push_authored_by,
-                )
-                .map_ok(|exec| {
-                    HookOutcome::FileHook(
-                        FileHookExecutionId {
-                            cs_id,
-                            path: path.clone(),
-                            hook_name: hook_name.to_string(),
-                        },
-                        exec,
+                run_with_optional_timeout(
+                    hook.run(
+                        ctx,
+                        content_provider,
+                        change,
+                        path,
+                        cross_repo_push_source,
+                        push_authored_by,
                     )
-                })
-                .timed()
+                    .map_ok(|exec| {
+                        HookOutcome::FileHook(
+                            FileHookExecutionId {
+                                cs_id,
+                                path: path.clone(),
+                                hook_name: hook_name.to_string(),
+                            },
+                            exec,
+                        )
+                    }),
+                    timeout,
+                    hook_name,
+                )
                 .await
             }
         };
@@ -373,33 +544,59 @@ impl<'a> HookInstance<'a> {
         let mut errorcode = 0;
         let mut failed_hooks = 0;
         let mut stderr = None;
+        let mut circuit_breaker_tripped_now = false;
 
         match result.as_mut() {
-            Ok(outcome) => match outcome.get_execution() {
-                HookExecution::Accepted => {
-                    // Nothing to do
+            Ok(outcome) => {
+                failure_counts.remove(hook_name);
+                match outcome.get_execution() {
+                    HookExecution::Accepted => {
+                        // Nothing to do
+                    }
+                    HookExecution::Rejected(info) if log_only => {
+                        scuba.add("log_only_rejection", info.long_description.clone());
+                        // Convert to accepted as we are only logging.
+                        outcome.set_execution(HookExecution::Accepted);
+                    }
+                    HookExecution::Rejected(info) => {
+                        failed_hooks = 1;
+                        stderr = Some(info.long_description.clone());
+                    }
                 }
-                HookExecution::Rejected(info) if log_only => {
-                    scuba.add("log_only_rejection", info.long_description.clone());
-                    // Convert to accepted as we are only logging.
-                    outcome.set_execution(HookExecution::Accepted);
-                }
-                HookExecution::Rejected(info) => {
-                    failed_hooks = 1;
-                    stderr = Some(info.long_description.clone());
-                }
-            },
+            }
             Err(e) => {
                 errorcode = 1;
                 stderr = Some(format!("{:?}", e));
+
+                if !critical {
+                    let mut state = failure_counts
+                        .entry(hook_name.to_string())
+                        .or_insert_with(CircuitBreakerState::default);
+                    state.failure_count += 1;
+                    if let Some(threshold) = circuit_breaker_failure_threshold {
+                        if state.failure_count >= threshold {
+                            // Re-arm the cooldown, whether this is the failure
+                            // that first tripped the breaker or a failed probe
+                            // after a previous cooldown elapsed.
+                            state.tripped_at = Some(Instant::now());
+                            circuit_breaker_tripped_now = true;
+                        }
+                    }
+                }
             }
         };
 
-        if let Some(stderr) = stderr {
+        if circuit_breaker_tripped_now {
+            scuba.add("circuit_breaker_tripped_now", true);
+            errorcode = 0;
+            result = Ok(fallback_outcome);
+        }
+
+        if let Some(stderr) = stderr.clone() {
             scuba.add("stderr", stderr);
         }
 
-        let elapsed = stats.completion_time.as_millis() as i64;
+        let elapsed = started.elapsed().as_millis() as i64;
         scuba
             .add("elapsed", elapsed)
             .add("total_time", elapsed)
@@ -407,6 +604,34 @@ impl<'a> HookInstance<'a> {
             .add("failed_hooks", failed_hooks)
             .log();
 
+        let outcome_record = HookOutcomeRecord {
+            cs_id,
+            hook_name: hook_name.to_string(),
+            bookmark: bookmark.to_string(),
+            accepted: errorcode == 0 && failed_hooks == 0,
+            rejection_message: stderr,
+            duration_ms: elapsed,
+            timestamp: Timestamp::now(),
+        };
+        // A commit can touch thousands of files, each running its file hooks
+        // separately, so awaiting this write inline would serialize one SQL
+        // insert per file hook per file onto the push critical path. Persist
+        // it in the background instead; a dropped or delayed outcome record
+        // doesn't affect whether the push itself is accepted or rejected.
+        let ctx = ctx.clone();
+        let hook_name_owned = hook_name.to_string();
+        tokio::spawn(async move {
+            if let Err(e) = hook_outcome_store
+                .record_outcome(&ctx, &outcome_record)
+                .await
+            {
+                warn!(
+                    ctx.logger(),
+                    "Failed to persist outcome for hook {}: {:?}", hook_name_owned, e
+                );
+            }
+        });
+
         result.map_err(|e| e.context(format!("while executing hook {}", hook_name)))
     }
 }
@@ -427,6 +652,7 @@ impl Hook {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn get_futures<'a: 'cs, 'cs>(
         &'a self,
         ctx: &'a CoreContext,
@@ -438,6 +664,11 @@ impl Hook {
         cross_repo_push_source: CrossRepoPushSource,
         push_authored_by: PushAuthoredBy,
         log_only: bool,
+        timeout: Option<Duration>,
+        critical: bool,
+        failure_counts: &'a DashMap<String, CircuitBreakerState>,
+        circuit_breaker_failure_threshold: Option<u32>,
+        hook_outcome_store: &'a ArcHookOutcomeStore,
     ) -> impl Iterator<Item = impl Future<Output = Result<HookOutcome, Error>> + 'cs> + 'cs {
         let mut futures = Vec::new();
 
@@ -455,6 +686,11 @@ impl Hook {
                 cross_repo_push_source,
                 push_authored_by,
                 log_only,
+                timeout,
+                critical,
+                failure_counts,
+                circuit_breaker_failure_threshold,
+                Arc::clone(hook_outcome_store),
             )),
             Self::File(hook, _) => {
                 futures.extend(cs.simplified_file_changes().map(move |(path, change)| {
@@ -469,6 +705,11 @@ impl Hook {
                         cross_repo_push_source,
                         push_authored_by,
                         log_only,
+                        timeout,
+                        critical,
+                        failure_counts,
+                        circuit_breaker_failure_threshold,
+                        Arc::clone(hook_outcome_store),
                     )
                 }))
             }
@@ -479,7 +720,15 @@ impl Hook {
 
 #[cfg(test)]
 mod test {
+    use std::sync::atomic::AtomicU32;
+    use std::sync::atomic::Ordering;
+
+    use async_trait::async_trait;
+    use mononoke_types::BonsaiChangesetMut;
+    use mononoke_types::DateTime;
+
     use super::*;
+    use crate::InMemoryHookStateProvider;
 
     #[test]
     fn test_commit_message_bypass() {
@@ -516,4 +765,289 @@ mod test {
         let r = get_bypass_reason(Some(&bypass), "", Some(&m));
         assert!(r.is_some());
     }
+
+    #[test]
+    fn test_token_bypass() {
+        use crate::bypass_token::issue_bypass_token;
+        use crate::bypass_token::BypassTokenClaims;
+
+        let key = b"test signing key";
+        let bookmark = BookmarkKey::new("main").unwrap();
+        let claims = BypassTokenClaims {
+            hook_name: Some("myhook".to_string()),
+            bookmark: Some("main".to_string()),
+            expires_at: 2_000_000_000,
+            issuer: "alice".to_string(),
+        };
+        let token = issue_bypass_token(key, &claims).unwrap();
+
+        // No signing key configured: tokens are always rejected.
+        let r = get_token_bypass_reason(None, "myhook", &bookmark, None);
+        assert!(r.is_none());
+
+        // No pushvars: nothing to check.
+        let r = get_token_bypass_reason(Some(key), "myhook", &bookmark, None);
+        assert!(r.is_none());
+
+        let mut m = HashMap::new();
+        m.insert("hook_bypass_token".into(), token.as_bytes().into());
+
+        // Wrong hook name: token is scoped to "myhook".
+        let r = get_token_bypass_reason(Some(key), "otherhook", &bookmark, Some(&m));
+        assert!(r.is_none());
+
+        // Wrong signing key.
+        let r = get_token_bypass_reason(Some(b"wrong key"), "myhook", &bookmark, Some(&m));
+        assert!(r.is_none());
+
+        // Correct hook, bookmark, and key.
+        let r = get_token_bypass_reason(Some(key), "myhook", &bookmark, Some(&m));
+        assert!(r.is_some());
+    }
+
+    /// A changeset hook that always fails, counting how many times it was
+    /// actually invoked so tests can tell a real execution apart from a
+    /// circuit-breaker short-circuit.
+    struct AlwaysFailingHook {
+        calls: Arc<AtomicU32>,
+    }
+
+    #[async_trait]
+    impl ChangesetHook for AlwaysFailingHook {
+        async fn run<'this: 'cs, 'ctx: 'this, 'cs, 'fetcher: 'cs>(
+            &'this self,
+            _ctx: &'ctx CoreContext,
+            _bookmark: &BookmarkKey,
+            _changeset: &'cs BonsaiChangeset,
+            _content_manager: &'fetcher dyn HookStateProvider,
+            _cross_repo_push_source: CrossRepoPushSource,
+            _push_authored_by: PushAuthoredBy,
+        ) -> Result<HookExecution> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(Error::msg("induced failure"))
+        }
+    }
+
+    /// A changeset hook that always accepts, standing in for a hook that
+    /// has recovered once it gets its post-cooldown probe execution.
+    struct AlwaysAcceptingHook;
+
+    #[async_trait]
+    impl ChangesetHook for AlwaysAcceptingHook {
+        async fn run<'this: 'cs, 'ctx: 'this, 'cs, 'fetcher: 'cs>(
+            &'this self,
+            _ctx: &'ctx CoreContext,
+            _bookmark: &BookmarkKey,
+            _changeset: &'cs BonsaiChangeset,
+            _content_manager: &'fetcher dyn HookStateProvider,
+            _cross_repo_push_source: CrossRepoPushSource,
+            _push_authored_by: PushAuthoredBy,
+        ) -> Result<HookExecution> {
+            Ok(HookExecution::Accepted)
+        }
+    }
+
+    fn test_changeset() -> BonsaiChangeset {
+        BonsaiChangesetMut {
+            parents: Vec::new(),
+            author: "Test Author <test@example.com>".to_string(),
+            author_date: DateTime::from_timestamp(0, 0).expect("Getting timestamp"),
+            committer: None,
+            committer_date: None,
+            message: "test commit".to_string(),
+            hg_extra: Default::default(),
+            git_extra_headers: None,
+            git_tree_hash: None,
+            file_changes: Default::default(),
+            is_snapshot: false,
+            git_annotated_tag: None,
+        }
+        .freeze()
+        .expect("Created changeset")
+    }
+
+    #[fbinit::test]
+    async fn test_circuit_breaker_trip_no_recovery_and_reset(fb: FacebookInit) {
+        let ctx = CoreContext::test_mock(fb);
+        let bookmark = BookmarkKey::new("main").unwrap();
+        let content_provider = InMemoryHookStateProvider::new();
+        let cs = test_changeset();
+        let cs_id = cs.get_changeset_id();
+        let calls = Arc::new(AtomicU32::new(0));
+        let hook = AlwaysFailingHook {
+            calls: calls.clone(),
+        };
+        let failure_counts: DashMap<String, CircuitBreakerState> = DashMap::new();
+        let threshold = 2;
+
+        #[allow(clippy::too_many_arguments)]
+        async fn run_once(
+            ctx: &CoreContext,
+            bookmark: &BookmarkKey,
+            content_provider: &dyn HookStateProvider,
+            hook: &AlwaysFailingHook,
+            cs: &BonsaiChangeset,
+            cs_id: ChangesetId,
+            failure_counts: &DashMap<String, CircuitBreakerState>,
+            threshold: u32,
+        ) -> Result<HookOutcome, Error> {
+            HookInstance::Changeset(hook)
+                .run(
+                    ctx,
+                    bookmark,
+                    content_provider,
+                    "myhook",
+                    MononokeScubaSampleBuilder::with_discard(),
+                    cs,
+                    cs_id,
+                    CrossRepoPushSource::NativeToThisRepo,
+                    PushAuthoredBy::User,
+                    false,
+                    None,
+                    false,
+                    failure_counts,
+                    Some(threshold),
+                    Arc::new(NullHookOutcomeStore),
+                )
+                .await
+        }
+
+        // First failure: below the threshold, so the real error propagates
+        // and the hook was actually invoked.
+        let result = run_once(
+            &ctx,
+            &bookmark,
+            &content_provider,
+            &hook,
+            &cs,
+            cs_id,
+            &failure_counts,
+            threshold,
+        )
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // Second failure: reaches the threshold, tripping the breaker. This
+        // call itself is reported as accepted rather than failing the push.
+        let result = run_once(
+            &ctx,
+            &bookmark,
+            &content_provider,
+            &hook,
+            &cs,
+            cs_id,
+            &failure_counts,
+            threshold,
+        )
+        .await
+        .expect("tripping call should report accepted, not error");
+        assert_eq!(result.get_execution(), &HookExecution::Accepted);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // No recovery: while the breaker is open, further calls are
+        // short-circuited to accepted without ever invoking the hook again.
+        let result = run_once(
+            &ctx,
+            &bookmark,
+            &content_provider,
+            &hook,
+            &cs,
+            cs_id,
+            &failure_counts,
+            threshold,
+        )
+        .await
+        .expect("breaker should short-circuit to accepted");
+        assert_eq!(result.get_execution(), &HookExecution::Accepted);
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            2,
+            "hook must not run while the breaker is open"
+        );
+
+        // Reset: once the cooldown has elapsed, back-date `tripped_at` to
+        // simulate that without sleeping for real, the next call is let
+        // through as a probe. Here the hook still fails, so the breaker
+        // re-arms its cooldown rather than closing.
+        failure_counts.get_mut("myhook").unwrap().tripped_at =
+            Instant::now().checked_sub(CIRCUIT_BREAKER_COOLDOWN + Duration::from_secs(1));
+        let result = run_once(
+            &ctx,
+            &bookmark,
+            &content_provider,
+            &hook,
+            &cs,
+            cs_id,
+            &failure_counts,
+            threshold,
+        )
+        .await
+        .expect("failed probe should still report accepted, not error");
+        assert_eq!(result.get_execution(), &HookExecution::Accepted);
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            3,
+            "cooldown elapsed, so the probe should have actually run the hook"
+        );
+        assert!(
+            failure_counts
+                .get("myhook")
+                .unwrap()
+                .tripped_at
+                .unwrap()
+                .elapsed()
+                < Duration::from_secs(5),
+            "a failed probe should re-arm the cooldown"
+        );
+    }
+
+    #[fbinit::test]
+    async fn test_circuit_breaker_closes_after_successful_probe(fb: FacebookInit) {
+        let ctx = CoreContext::test_mock(fb);
+        let bookmark = BookmarkKey::new("main").unwrap();
+        let content_provider = InMemoryHookStateProvider::new();
+        let cs = test_changeset();
+        let cs_id = cs.get_changeset_id();
+        let threshold = 1;
+        let failure_counts: DashMap<String, CircuitBreakerState> = DashMap::new();
+
+        // Simulate a breaker that tripped a cooldown ago: the hook itself now
+        // accepts, standing in for a hook that has recovered.
+        failure_counts.insert(
+            "myhook".to_string(),
+            CircuitBreakerState {
+                failure_count: threshold,
+                tripped_at: Instant::now()
+                    .checked_sub(CIRCUIT_BREAKER_COOLDOWN + Duration::from_secs(1)),
+            },
+        );
+        let hook = AlwaysAcceptingHook;
+
+        let result = HookInstance::Changeset(&hook)
+            .run(
+                &ctx,
+                &bookmark,
+                &content_provider,
+                "myhook",
+                MononokeScubaSampleBuilder::with_discard(),
+                &cs,
+                cs_id,
+                CrossRepoPushSource::NativeToThisRepo,
+                PushAuthoredBy::User,
+                false,
+                None,
+                false,
+                &failure_counts,
+                Some(threshold),
+                Arc::new(NullHookOutcomeStore),
+            )
+            .await
+            .expect("probe should run and succeed");
+        assert_eq!(result.get_execution(), &HookExecution::Accepted);
+        assert!(
+            failure_counts.get("myhook").is_none(),
+            "a successful probe should fully close the breaker"
+        );
+    }
 }
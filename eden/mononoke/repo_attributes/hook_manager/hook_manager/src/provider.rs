@@ -19,6 +19,7 @@ use mononoke_types::hash::GitSha1;
 use mononoke_types::ChangesetId;
 use mononoke_types::ContentId;
 use mononoke_types::ContentMetadataV2;
+use mononoke_types::FileType;
 use mononoke_types::MPath;
 use mononoke_types::NonRootMPath;
 
@@ -71,6 +72,19 @@ pub trait HookStateProvider: Send + Sync {
         id: ContentId,
     ) -> Result<Option<Bytes>, HookStateProviderError>;
 
+    /// Like `get_file_text`, but never reads more than `size` bytes of the
+    /// underlying content into memory, even if the file is larger. Returns
+    /// the (possibly truncated) prefix of the file if the content exists, or
+    /// `None` if it doesn't. Useful for hooks such as secret scanning or
+    /// large-file rejection that only need to inspect a bounded prefix of
+    /// potentially huge files.
+    async fn get_file_text_capped<'a>(
+        &'a self,
+        ctx: &'a CoreContext,
+        id: ContentId,
+        size: u64,
+    ) -> Result<Option<Bytes>, HookStateProviderError>;
+
     /// The state of a bookmark at the time the push is being run. Note that this
     /// is best effort since the bookmark can move as a result of another push
     /// happening concurrently
@@ -133,12 +147,28 @@ pub trait HookStateProvider: Send + Sync {
 #[derive(Clone, Debug)]
 pub enum PathContent {
     Directory,
-    File(ContentId),
+    File(ContentId, FileType),
 }
 
+/// A file change between two changesets, as found by `file_changes`.
+///
+/// Unlike `mononoke_types::FileChange`, this carries both the old and new
+/// file type for modified files, so hooks can detect mode-only changes
+/// (e.g. the executable bit being flipped) without a second round trip.
 #[derive(Clone, Debug)]
 pub enum FileChange {
-    Added(ContentId),
-    Changed(ContentId, ContentId),
-    Removed,
+    Added(ContentId, FileType),
+    Changed(ContentId, FileType, ContentId, FileType),
+    Removed(FileType),
+}
+
+impl FileChange {
+    /// True if this is a modification where the file type (e.g. regular,
+    /// executable, symlink) differs between the old and new versions.
+    pub fn is_mode_change(&self) -> bool {
+        match self {
+            FileChange::Changed(_, old_type, _, new_type) => old_type != new_type,
+            FileChange::Added(..) | FileChange::Removed(..) => false,
+        }
+    }
 }
@@ -98,6 +98,24 @@ impl HookStateProvider for InMemoryHookStateProvider {
             })
     }
 
+    async fn get_file_text_capped<'a>(
+        &'a self,
+        _ctx: &'a CoreContext,
+        id: ContentId,
+        size: u64,
+    ) -> Result<Option<Bytes>, HookStateProviderError> {
+        Ok(self
+            .id_to_text
+            .get(&id)
+            .and_then(|maybe_bytes| match maybe_bytes {
+                InMemoryFileText::Present(bytes) => {
+                    let cap = std::cmp::min(bytes.len() as u64, size) as usize;
+                    Some(bytes.slice(0..cap))
+                }
+                InMemoryFileText::Elided(_) => None,
+            }))
+    }
+
     async fn find_content<'a>(
         &'a self,
         _ctx: &'a CoreContext,
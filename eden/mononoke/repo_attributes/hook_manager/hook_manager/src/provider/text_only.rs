@@ -69,6 +69,21 @@ impl<T: HookStateProvider + 'static> HookStateProvider for TextOnlyHookStateProv
         Ok(file_bytes.filter(|bytes| !bytes.contains(&0)))
     }
 
+    /// Override the inner store's get_file_text_capped by filtering out
+    /// content that contains null bytes (those are assumed to be binary),
+    /// and never requesting more than our own `max_size` cap.
+    async fn get_file_text_capped<'a>(
+        &'a self,
+        ctx: &'a CoreContext,
+        id: ContentId,
+        size: u64,
+    ) -> Result<Option<Bytes>, HookStateProviderError> {
+        let size = std::cmp::min(size, self.max_size);
+        let file_bytes = self.inner.get_file_text_capped(ctx, id, size).await?;
+
+        Ok(file_bytes.filter(|bytes| !bytes.contains(&0)))
+    }
+
     async fn find_content<'a>(
         &'a self,
         ctx: &'a CoreContext,
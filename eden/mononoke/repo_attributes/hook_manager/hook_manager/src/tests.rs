@@ -8,6 +8,7 @@
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::sync::Arc;
 
 use anyhow::Error;
 use async_trait::async_trait;
@@ -17,6 +18,7 @@ use fbinit::FacebookInit;
 use futures::future;
 use futures::stream::futures_unordered;
 use futures::stream::TryStreamExt;
+use hook_outcome_store::NullHookOutcomeStore;
 use maplit::hashmap;
 use maplit::hashset;
 use metaconfig_types::HookManagerParams;
@@ -405,6 +407,7 @@ async fn hook_manager_inmem(fb: FacebookInit) -> HookManager {
         },
         MononokeScubaSampleBuilder::with_discard(),
         "zoo".to_string(),
+        Arc::new(NullHookOutcomeStore),
     )
     .await
     .expect("Failed to construct HookManager")
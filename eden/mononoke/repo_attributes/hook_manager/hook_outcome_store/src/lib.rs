@@ -0,0 +1,63 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+mod sql_queries;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use context::CoreContext;
+use mononoke_types::ChangesetId;
+use mononoke_types::Timestamp;
+pub use sql_queries::SqlHookOutcomeStore;
+pub use sql_queries::SqlHookOutcomeStoreConnection;
+
+/// A single recorded outcome of running one hook against one changeset as
+/// part of a push attempt.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HookOutcomeRecord {
+    pub cs_id: ChangesetId,
+    pub hook_name: String,
+    pub bookmark: String,
+    pub accepted: bool,
+    pub rejection_message: Option<String>,
+    pub duration_ms: i64,
+    pub timestamp: Timestamp,
+}
+
+/// Persists hook execution outcomes so that they can be queried after the
+/// fact, e.g. to tell a user why their push was rejected, or to let admins
+/// analyze hook flakiness.
+#[async_trait]
+#[facet::facet]
+pub trait HookOutcomeStore: Send + Sync {
+    async fn record_outcome(&self, ctx: &CoreContext, record: &HookOutcomeRecord) -> Result<()>;
+
+    async fn get_outcomes_for_changeset(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Vec<HookOutcomeRecord>>;
+}
+
+/// A no-op store, used where hook outcome persistence hasn't been configured,
+/// e.g. in lightweight test setups.
+pub struct NullHookOutcomeStore;
+
+#[async_trait]
+impl HookOutcomeStore for NullHookOutcomeStore {
+    async fn record_outcome(&self, _ctx: &CoreContext, _record: &HookOutcomeRecord) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_outcomes_for_changeset(
+        &self,
+        _ctx: &CoreContext,
+        _cs_id: ChangesetId,
+    ) -> Result<Vec<HookOutcomeRecord>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,242 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use anyhow::Result;
+use async_trait::async_trait;
+use context::CoreContext;
+use context::PerfCounterType;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use mononoke_types::Timestamp;
+use sql::Connection;
+use sql_construct::SqlConstruct;
+use sql_construct::SqlConstructFromMetadataDatabaseConfig;
+use sql_ext::mononoke_queries;
+use sql_ext::SqlConnections;
+
+use crate::HookOutcomeRecord;
+use crate::HookOutcomeStore;
+
+mononoke_queries! {
+    read SelectOutcomesForChangeset(
+        repo_id: RepositoryId,
+        cs_id: ChangesetId,
+    ) -> (
+        ChangesetId,
+        String,
+        String,
+        bool,
+        Option<String>,
+        i64,
+        i64,
+    ) {
+        "SELECT cs_id, hook_name, bookmark, accepted, rejection_message, duration_ms, timestamp_ns
+        FROM hook_outcomes
+        WHERE repo_id = {repo_id} AND cs_id = {cs_id}"
+    }
+
+    write InsertOutcome(values: (
+        repo_id: RepositoryId,
+        cs_id: ChangesetId,
+        hook_name: String,
+        bookmark: String,
+        accepted: bool,
+        rejection_message: Option<String>,
+        duration_ms: i64,
+        timestamp_ns: i64,
+    )) {
+        none,
+        "INSERT INTO hook_outcomes
+        (repo_id, cs_id, hook_name, bookmark, accepted, rejection_message, duration_ms, timestamp_ns)
+        VALUES {values}"
+    }
+}
+
+pub struct SqlHookOutcomeStore {
+    repo_id: RepositoryId,
+    sql_conn: SqlHookOutcomeStoreConnection,
+}
+
+impl SqlHookOutcomeStore {
+    pub fn new(repo_id: RepositoryId, sql_conn: SqlHookOutcomeStoreConnection) -> Self {
+        Self { repo_id, sql_conn }
+    }
+}
+
+#[derive(Clone)]
+pub struct SqlHookOutcomeStoreConnection {
+    write_connection: Connection,
+    read_connection: Connection,
+    read_master_connection: Connection,
+}
+
+impl SqlHookOutcomeStoreConnection {
+    pub fn with_repo_id(self, repo_id: RepositoryId) -> SqlHookOutcomeStore {
+        SqlHookOutcomeStore::new(repo_id, self)
+    }
+
+    async fn record_outcome(
+        &self,
+        repo_id: RepositoryId,
+        record: &HookOutcomeRecord,
+    ) -> Result<()> {
+        InsertOutcome::query(
+            &self.write_connection,
+            &[(
+                &repo_id,
+                &record.cs_id,
+                &record.hook_name,
+                &record.bookmark,
+                &record.accepted,
+                &record.rejection_message,
+                &record.duration_ms,
+                &record.timestamp.timestamp_nanos(),
+            )],
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn get_outcomes_for_changeset(
+        &self,
+        ctx: &CoreContext,
+        repo_id: RepositoryId,
+        cs_id: ChangesetId,
+    ) -> Result<Vec<HookOutcomeRecord>> {
+        ctx.perf_counters()
+            .increment_counter(PerfCounterType::SqlReadsReplica);
+        let mut rows =
+            SelectOutcomesForChangeset::query(&self.read_connection, &repo_id, &cs_id).await?;
+        if rows.is_empty() {
+            ctx.perf_counters()
+                .increment_counter(PerfCounterType::SqlReadsMaster);
+            rows =
+                SelectOutcomesForChangeset::query(&self.read_master_connection, &repo_id, &cs_id)
+                    .await?;
+        }
+        Ok(rows
+            .into_iter()
+            .map(
+                |(
+                    cs_id,
+                    hook_name,
+                    bookmark,
+                    accepted,
+                    rejection_message,
+                    duration_ms,
+                    timestamp_ns,
+                )| {
+                    HookOutcomeRecord {
+                        cs_id,
+                        hook_name,
+                        bookmark,
+                        accepted,
+                        rejection_message,
+                        duration_ms,
+                        timestamp: Timestamp::from_timestamp_nanos(timestamp_ns),
+                    }
+                },
+            )
+            .collect())
+    }
+}
+
+impl SqlConstruct for SqlHookOutcomeStoreConnection {
+    const LABEL: &'static str = "hook_outcomes";
+
+    const CREATION_QUERY: &'static str = include_str!("../schemas/sqlite-hook-outcomes.sql");
+
+    fn from_sql_connections(connections: SqlConnections) -> Self {
+        Self {
+            write_connection: connections.write_connection,
+            read_connection: connections.read_connection,
+            read_master_connection: connections.read_master_connection,
+        }
+    }
+}
+
+impl SqlConstructFromMetadataDatabaseConfig for SqlHookOutcomeStoreConnection {}
+
+#[async_trait]
+impl HookOutcomeStore for SqlHookOutcomeStore {
+    async fn record_outcome(&self, _ctx: &CoreContext, record: &HookOutcomeRecord) -> Result<()> {
+        self.sql_conn.record_outcome(self.repo_id, record).await
+    }
+
+    async fn get_outcomes_for_changeset(
+        &self,
+        ctx: &CoreContext,
+        cs_id: ChangesetId,
+    ) -> Result<Vec<HookOutcomeRecord>> {
+        self.sql_conn
+            .get_outcomes_for_changeset(ctx, self.repo_id, cs_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use fbinit::FacebookInit;
+    use mononoke_types_mocks::changesetid::ONES_CSID;
+    use mononoke_types_mocks::changesetid::TWOS_CSID;
+
+    use super::*;
+
+    fn outcome(cs_id: ChangesetId, hook_name: &str, accepted: bool) -> HookOutcomeRecord {
+        HookOutcomeRecord {
+            cs_id,
+            hook_name: hook_name.to_string(),
+            bookmark: "main".to_string(),
+            accepted,
+            rejection_message: if accepted {
+                None
+            } else {
+                Some("rejected by hook".to_string())
+            },
+            duration_ms: 42,
+            timestamp: Timestamp::from_timestamp_secs(1),
+        }
+    }
+
+    #[fbinit::test]
+    async fn test_record_and_get_outcomes_round_trip(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let store = SqlHookOutcomeStoreConnection::with_sqlite_in_memory()?
+            .with_repo_id(RepositoryId::new(1));
+
+        let accepted = outcome(ONES_CSID, "hook_one", true);
+        let rejected = outcome(ONES_CSID, "hook_two", false);
+        let other_cs = outcome(TWOS_CSID, "hook_one", true);
+
+        for record in [&accepted, &rejected, &other_cs] {
+            store.record_outcome(&ctx, record).await?;
+        }
+
+        let mut outcomes = store.get_outcomes_for_changeset(&ctx, ONES_CSID).await?;
+        outcomes.sort_by(|a, b| a.hook_name.cmp(&b.hook_name));
+        assert_eq!(outcomes, vec![accepted, rejected]);
+
+        let other_outcomes = store.get_outcomes_for_changeset(&ctx, TWOS_CSID).await?;
+        assert_eq!(other_outcomes, vec![other_cs]);
+
+        Ok(())
+    }
+
+    #[fbinit::test]
+    async fn test_get_outcomes_for_unknown_changeset_is_empty(fb: FacebookInit) -> Result<()> {
+        let ctx = CoreContext::test_mock(fb);
+        let store = SqlHookOutcomeStoreConnection::with_sqlite_in_memory()?
+            .with_repo_id(RepositoryId::new(1));
+
+        assert_eq!(
+            store.get_outcomes_for_changeset(&ctx, ONES_CSID).await?,
+            vec![]
+        );
+
+        Ok(())
+    }
+}
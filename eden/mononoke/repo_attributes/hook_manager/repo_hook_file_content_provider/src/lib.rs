@@ -89,6 +89,16 @@ impl HookStateProvider for RepoHookStateProvider {
             .map(Option::Some)
     }
 
+    async fn get_file_text_capped<'a>(
+        &'a self,
+        ctx: &'a CoreContext,
+        id: ContentId,
+        size: u64,
+    ) -> Result<Option<Bytes>, HookStateProviderError> {
+        let size: usize = size.try_into()?;
+        Ok(filestore::peek(&self.repo_blobstore, ctx, &id.into(), size).await?)
+    }
+
     async fn find_content<'a>(
         &'a self,
         ctx: &'a CoreContext,
@@ -154,8 +164,8 @@ impl HookStateProvider for RepoHookStateProvider {
                     Diff::Added(path, entry) => match Option::<NonRootMPath>::from(path) {
                         Some(path) => {
                             match resolve_content_id(ctx, &self.repo_blobstore, entry).await? {
-                                PathContent::File(content) => {
-                                    Ok(Some((path, FileChange::Added(content))))
+                                PathContent::File(content, file_type) => {
+                                    Ok(Some((path, FileChange::Added(content, file_type))))
                                 }
                                 PathContent::Directory => Ok(None),
                             }
@@ -171,11 +181,16 @@ impl HookStateProvider for RepoHookStateProvider {
 
                                 match future::try_join(old_content, content).await? {
                                     (
-                                        PathContent::File(old_content_id),
-                                        PathContent::File(content_id),
+                                        PathContent::File(old_content_id, old_file_type),
+                                        PathContent::File(content_id, file_type),
                                     ) => Ok(Some((
                                         path,
-                                        FileChange::Changed(old_content_id, content_id),
+                                        FileChange::Changed(
+                                            old_content_id,
+                                            old_file_type,
+                                            content_id,
+                                            file_type,
+                                        ),
                                     ))),
                                     _ => Ok(None),
                                 }
@@ -185,8 +200,8 @@ impl HookStateProvider for RepoHookStateProvider {
                     }
                     Diff::Removed(path, entry) => match Option::<NonRootMPath>::from(path) {
                         Some(path) => {
-                            if let Entry::Leaf(_) = entry {
-                                Ok(Some((path, FileChange::Removed)))
+                            if let Entry::Leaf((file_type, _)) = entry {
+                                Ok(Some((path, FileChange::Removed(file_type))))
                             } else {
                                 Ok(None)
                             }
@@ -416,9 +431,9 @@ async fn resolve_content_id(
             // there is no content for trees
             Ok(PathContent::Directory)
         }
-        Entry::Leaf((_type, file_node_id)) => file_node_id
+        Entry::Leaf((file_type, file_node_id)) => file_node_id
             .load(ctx, blobstore)
-            .map_ok(|file_env| PathContent::File(file_env.content_id()))
+            .map_ok(|file_env| PathContent::File(file_env.content_id(), file_type))
             .await
             .with_context(|| format!("Error loading filenode: {}", file_node_id))
             .map_err(HookStateProviderError::from),
@@ -6,6 +6,7 @@
  */
 
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use anyhow::Error;
 use async_trait::async_trait;
@@ -25,6 +26,7 @@ use hook_manager::HookRejectionInfo;
 use hook_manager::HookStateProvider;
 use hook_manager::PathContent;
 use hook_manager::PushAuthoredBy;
+use hook_outcome_store::NullHookOutcomeStore;
 use maplit::hashmap;
 use metaconfig_types::HookManagerParams;
 use mononoke_types::BonsaiChangeset;
@@ -72,7 +74,7 @@ impl ChangesetHook for FindFilesChangesetHook {
 
         match res {
             Ok(contents) => Ok(match contents.get(&path) {
-                Some(PathContent::File(_)) => HookExecution::Accepted,
+                Some(PathContent::File(..)) => HookExecution::Accepted,
                 _ => HookExecution::Rejected(HookRejectionInfo::new("there is no such file")),
             }),
             Err(err) => {
@@ -114,9 +116,9 @@ impl ChangesetHook for FileChangesChangesetHook {
             let (mut added, mut changed, mut removed) = (0, 0, 0);
             for (_path, change) in file_changes.into_iter() {
                 match change {
-                    FileDiff::Added(_) => added += 1,
-                    FileDiff::Changed(_, _) => changed += 1,
-                    FileDiff::Removed => removed += 1,
+                    FileDiff::Added(..) => added += 1,
+                    FileDiff::Changed(..) => changed += 1,
+                    FileDiff::Removed(_) => removed += 1,
                 }
             }
             Result::<_, Error>::Ok((added, changed, removed))
@@ -437,6 +439,7 @@ async fn hook_manager_repo(fb: FacebookInit, repo: &BasicTestRepo) -> HookManage
         },
         MononokeScubaSampleBuilder::with_discard(),
         "zoo".to_string(),
+        Arc::new(NullHookOutcomeStore),
     )
     .await
     .expect("Failed to construct HookManager")
@@ -22,6 +22,7 @@ use context::CoreContext;
 use derived_data_manager::BonsaiDerivable;
 use derived_data_manager::DerivationError;
 use derived_data_manager::DerivedDataManager;
+use derived_data_manager::DeriveWithDeadlineOutcome;
 use derived_data_manager::SharedDerivationError;
 use derived_data_remote::DerivationClient;
 use ephemeral_blobstore::Bubble;
@@ -331,6 +332,23 @@ impl RepoDerivedData {
         self.manager().derive::<Derivable>(ctx, csid, None).await
     }
 
+    /// Derive a derived data type using the default manager, but give up after `deadline`
+    /// instead of blocking until derivation of the changeset and all its underived ancestors
+    /// completes.
+    pub async fn derive_with_deadline<Derivable>(
+        &self,
+        ctx: &CoreContext,
+        csid: ChangesetId,
+        deadline: std::time::Duration,
+    ) -> Result<DeriveWithDeadlineOutcome<Derivable>, SharedDerivationError>
+    where
+        Derivable: BonsaiDerivable,
+    {
+        self.manager()
+            .derive_with_deadline::<Derivable>(ctx, csid, None, deadline)
+            .await
+    }
+
     /// Fetch an already derived derived data type using the default manager.
     pub async fn fetch_derived<Derivable>(
         &self,
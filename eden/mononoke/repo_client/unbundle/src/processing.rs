@@ -359,7 +359,7 @@ async fn run_pushrebase(
     };
 
     repo.phases()
-        .add_reachable_as_public(ctx, vec![pushrebased_rev.clone()])
+        .add_reachable_as_public_with_bookmark(ctx, vec![pushrebased_rev.clone()], bookmark.clone())
         .await
         .context("While marking pushrebased changeset as public")?;
 
@@ -5,23 +5,31 @@
  * GNU General Public License version 2.
  */
 
+mod cache;
+
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::format_err;
 use anyhow::Context;
 use anyhow::Error;
 use backsyncer::backsync_latest;
+use backsyncer::backsync_status;
+use bytes::Bytes;
 use backsyncer::ensure_backsynced;
 use backsyncer::BacksyncLimit;
+use backsyncer::BacksyncStatus;
 use blobstore::Loadable;
 use bookmarks::BookmarkKey;
 use bookmarks::BookmarkUpdateLogId;
 use bookmarks::BookmarkUpdateLogRef;
 use bookmarks::Freshness;
 use cacheblob::LeaseOps;
+use caching_ext::CacheHandlerFactory;
 use cloned::cloned;
 use context::CoreContext;
 use cross_repo_sync::create_commit_syncers;
@@ -31,6 +39,7 @@ use cross_repo_sync::CommitSyncOutcome;
 use cross_repo_sync::CommitSyncer;
 use cross_repo_sync::SubmoduleDeps;
 use cross_repo_sync::Target;
+use cross_repo_sync::verify_working_copy;
 use futures::future;
 use futures::future::try_join_all;
 use futures::future::FutureExt;
@@ -39,7 +48,13 @@ use hook_manager::manager::HookManagerRef;
 use hook_manager::CrossRepoPushSource;
 use hook_manager::HookRejection;
 use live_commit_sync_config::LiveCommitSyncConfig;
+use maplit::hashset;
 use mercurial_derivation::DeriveHgChangeset;
+use metaconfig_types::redirection_mode_for_bookmark;
+use metaconfig_types::BookmarkRedirectionMode;
+use metaconfig_types::BookmarkRedirectionNamespace;
+use metaconfig_types::CommitSyncConfigVersion;
+use metaconfig_types::PushvarPassthroughPolicy;
 use mononoke_types::BonsaiChangeset;
 use mononoke_types::ChangesetId;
 use pushrebase::PushrebaseChangesetPair;
@@ -48,6 +63,8 @@ use topo_sort::sort_topological;
 use wireproto_handler::TargetRepoDbs;
 
 use crate::hook_running::HookRejectionRemapper;
+use crate::push_redirector::cache::CacheHandlers;
+use crate::push_redirector::cache::CommitEquivalentKey;
 use crate::resolver::HgHookRejection;
 use crate::run_post_resolve_action;
 use crate::BundleResolverError;
@@ -79,6 +96,9 @@ pub struct PushRedirectorArgs<R> {
     source_repo: Arc<R>,
     synced_commit_mapping: Arc<dyn SyncedCommitMapping>,
     target_repo_dbs: Arc<TargetRepoDbs>,
+    bookmark_redirection_namespaces: Vec<BookmarkRedirectionNamespace>,
+    pushvar_passthrough_policy: PushvarPassthroughPolicy,
+    cache_handler_factory: Option<Arc<CacheHandlerFactory>>,
 }
 
 // Implement clone manually because a derived implementation would require
@@ -91,6 +111,9 @@ impl<R> Clone for PushRedirectorArgs<R> {
             source_repo: self.source_repo.clone(),
             synced_commit_mapping: self.synced_commit_mapping.clone(),
             target_repo_dbs: self.target_repo_dbs.clone(),
+            bookmark_redirection_namespaces: self.bookmark_redirection_namespaces.clone(),
+            pushvar_passthrough_policy: self.pushvar_passthrough_policy.clone(),
+            cache_handler_factory: self.cache_handler_factory.clone(),
         }
     }
 }
@@ -101,12 +124,18 @@ impl<R: Repo> PushRedirectorArgs<R> {
         source_repo: Arc<R>,
         synced_commit_mapping: Arc<dyn SyncedCommitMapping>,
         target_repo_dbs: Arc<TargetRepoDbs>,
+        bookmark_redirection_namespaces: Vec<BookmarkRedirectionNamespace>,
+        pushvar_passthrough_policy: PushvarPassthroughPolicy,
+        cache_handler_factory: Option<Arc<CacheHandlerFactory>>,
     ) -> Self {
         Self {
             target_repo,
             source_repo,
             synced_commit_mapping,
             target_repo_dbs,
+            bookmark_redirection_namespaces,
+            pushvar_passthrough_policy,
+            cache_handler_factory,
         }
     }
 
@@ -125,6 +154,9 @@ impl<R: Repo> PushRedirectorArgs<R> {
             source_repo,
             synced_commit_mapping,
             target_repo_dbs,
+            bookmark_redirection_namespaces,
+            pushvar_passthrough_policy,
+            cache_handler_factory,
             ..
         } = self;
 
@@ -148,12 +180,20 @@ impl<R: Repo> PushRedirectorArgs<R> {
         let small_to_large_commit_syncer = syncers.small_to_large;
         let large_to_small_commit_syncer = syncers.large_to_small;
 
+        let cache_handlers = cache_handler_factory
+            .as_deref()
+            .map(CacheHandlers::new)
+            .transpose()?;
+
         Ok(PushRedirector {
             repo: target_repo,
             small_repo: source_repo,
             small_to_large_commit_syncer,
             large_to_small_commit_syncer,
             target_repo_dbs,
+            bookmark_redirection_namespaces,
+            pushvar_passthrough_policy,
+            cache_handlers,
         })
     }
 }
@@ -170,8 +210,18 @@ pub struct PushRedirector<R> {
     pub small_to_large_commit_syncer: CommitSyncer<Arc<dyn SyncedCommitMapping>, R>,
     // `CommitSyncer` struct for the backsyncer
     pub large_to_small_commit_syncer: CommitSyncer<Arc<dyn SyncedCommitMapping>, R>,
+    // Rules classifying which bookmark namespaces in the small repo are
+    // redirected to the large repo vs handled locally. Empty means
+    // everything is redirected.
+    bookmark_redirection_namespaces: Vec<BookmarkRedirectionNamespace>,
+    // Which pushvars are allowed to cross from the small repo op into the
+    // redirected large repo op.
+    pushvar_passthrough_policy: PushvarPassthroughPolicy,
     // A struct, needed to backsync commits
     pub target_repo_dbs: Arc<TargetRepoDbs>,
+    // Caches cross-repo commit equivalents looked up via the commit
+    // syncers above. `None` if caching is disabled for this repo.
+    cache_handlers: Option<CacheHandlers>,
 }
 
 impl<R: Repo> PushRedirector<R> {
@@ -506,23 +556,77 @@ impl<R: Repo> PushRedirector<R> {
         &self,
         ctx: &CoreContext,
         log_id: BookmarkUpdateLogId,
+    ) -> Result<(), Error> {
+        self.ensure_backsynced_with_timeout(ctx, log_id, None).await
+    }
+
+    /// Like `ensure_backsynced`, but lets the caller override the default
+    /// justknob-controlled wait timeout, e.g. for callers that would rather
+    /// fail fast and surface a `BacksyncError::Timeout` than wait the
+    /// default 60s. The override only applies when
+    /// `scm/mononoke:defer_to_backsyncer_for_backsync` is enabled; otherwise
+    /// the backsync runs inline via `backsync_latest` and no timeout applies.
+    pub async fn ensure_backsynced_with_timeout(
+        &self,
+        ctx: &CoreContext,
+        log_id: BookmarkUpdateLogId,
+        timeout_override: Option<Duration>,
     ) -> Result<(), Error> {
         let defer_to_backsyncer_for_backsync =
             justknobs::eval("scm/mononoke:defer_to_backsyncer_for_backsync", None, None)
                 .unwrap_or(false);
         if defer_to_backsyncer_for_backsync {
-            ensure_backsynced(
+            Ok(ensure_backsynced(
                 ctx.clone(),
                 self.large_to_small_commit_syncer.clone(),
                 self.target_repo_dbs.clone(),
                 log_id,
+                timeout_override,
             )
-            .await
+            .await?)
         } else {
             self.backsync_latest(ctx).await
         }
     }
 
+    /// Report how far behind the backsyncer is for the small repo this
+    /// redirector syncs from, so that clients and dashboards can see why
+    /// `ensure_backsynced` is taking a long time instead of just seeing it
+    /// hang.
+    pub async fn backsync_status(&self, ctx: &CoreContext) -> Result<BacksyncStatus, Error> {
+        backsync_status(
+            ctx,
+            &self.large_to_small_commit_syncer,
+            &self.target_repo_dbs,
+        )
+        .await
+    }
+
+    /// Verify that the small-repo commit the backsyncer produced for
+    /// `large_repo_cs_id` has the same working copy as `large_repo_cs_id`
+    /// itself, i.e. that redirecting the bookmark move through the large
+    /// repo and backsyncing it didn't lose or corrupt any paths.
+    ///
+    /// This is meant to be called as an optional safety check after
+    /// `ensure_backsynced` has confirmed the backsync happened, and before
+    /// acking the client - it's not on the critical path of every push, so
+    /// callers should gate it behind a justknob.
+    pub async fn verify_bookmark_move_working_copy(
+        &self,
+        ctx: &CoreContext,
+        large_repo_cs_id: ChangesetId,
+    ) -> Result<(), Error> {
+        verify_working_copy(
+            ctx,
+            &self.large_to_small_commit_syncer,
+            large_repo_cs_id,
+            self.large_to_small_commit_syncer
+                .live_commit_sync_config
+                .clone(),
+        )
+        .await
+    }
+
     async fn backsync_latest(&self, ctx: &CoreContext) -> Result<(), Error> {
         // backsync_latest returns a tokio-spawned future which contains the
         // non-blocking extra syncing done. We don't need to wait for it.
@@ -539,6 +643,7 @@ impl<R: Repo> PushRedirector<R> {
                 true,
                 // We don't chain successive backsyncs here.
                 Box::new(future::ready(())),
+                None,
             )
             .await?,
         );
@@ -560,6 +665,38 @@ impl<R: Repo> PushRedirector<R> {
             })
     }
 
+    /// Returns whether pushes to `bookmark` (in the small repo) should be
+    /// redirected to the large repo, according to the configured bookmark
+    /// redirection namespaces. Errors if `bookmark` doesn't match exactly
+    /// one namespace's mode.
+    pub fn should_redirect_bookmark(&self, bookmark: &BookmarkKey) -> Result<bool, Error> {
+        let mode = redirection_mode_for_bookmark(&self.bookmark_redirection_namespaces, bookmark)?;
+        Ok(mode == BookmarkRedirectionMode::Redirected)
+    }
+
+    /// Filter `pushvars` according to the configured
+    /// `pushvar_passthrough_policy`, stripping any pushvar (e.g. a
+    /// hook-bypass token) that isn't allowed to cross from the small repo op
+    /// into the redirected large repo op.
+    pub fn filter_pushvars<'a>(
+        &self,
+        pushvars: Option<&'a HashMap<String, Bytes>>,
+    ) -> Option<Cow<'a, HashMap<String, Bytes>>> {
+        let pushvars = pushvars?;
+        if matches!(
+            self.pushvar_passthrough_policy,
+            PushvarPassthroughPolicy::ForwardAll
+        ) {
+            return Some(Cow::Borrowed(pushvars));
+        }
+        let filtered: HashMap<String, Bytes> = pushvars
+            .iter()
+            .filter(|(name, _)| self.pushvar_passthrough_policy.allows(name))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        Some(Cow::Owned(filtered))
+    }
+
     /// Convert `UnbundlePushRebaseResponse` struct in a large-to-small
     /// direction to be suitable for response generation in the small repo
     async fn convert_unbundle_pushrebase_response(
@@ -703,13 +840,42 @@ impl<R: Repo> PushRedirector<R> {
     /// either with paths moved, or preserved. What is unacceptable is that
     /// the changeset is not yet synced, or rewritten into nothingness, or
     /// preserved from a different repo.
+    ///
+    /// The mapping from `cs_id` to its rewritten equivalent is cached, keyed
+    /// by (source repo, target repo, `cs_id`): see `cache` for details of why
+    /// only positive results are ever cached.
     async fn remap_changeset_expect_rewritten_or_preserved(
         &self,
         ctx: &CoreContext,
         syncer: &CommitSyncer<Arc<dyn SyncedCommitMapping>, R>,
         cs_id: ChangesetId,
     ) -> Result<ChangesetId, Error> {
-        let maybe_commit_sync_outcome = syncer.get_commit_sync_outcome(ctx, cs_id).await?;
+        let maybe_commit_sync_outcome = match &self.cache_handlers {
+            None => syncer.get_commit_sync_outcome(ctx, cs_id).await?,
+            Some(cache_handlers) => {
+                let key = CommitEquivalentKey::new(
+                    syncer.get_source_repo_id(),
+                    syncer.get_target_repo_id(),
+                    cs_id,
+                );
+                let keys = hashset![key.clone()];
+
+                let cache = cache_handlers.commit_equivalent(syncer, ctx);
+                let mut res = caching_ext::get_or_fill(&cache, keys).await?;
+
+                match res.remove(&key) {
+                    Some(cached) => Some(CommitSyncOutcome::RewrittenAs(
+                        cached.cs_id,
+                        CommitSyncConfigVersion(cached.version),
+                    )),
+                    // `get_or_fill` already queried the mapping on a cache
+                    // miss (via `CachedCommitEquivalentStore::get_from_db`);
+                    // it just didn't cache a non-`RewrittenAs` result. Read
+                    // that outcome back out instead of querying it again.
+                    None => cache.take_uncached_outcome(cs_id),
+                }
+            }
+        };
         maybe_commit_sync_outcome
             .ok_or_else(|| {
                 format_err!(
@@ -0,0 +1,229 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use abomonation_derive::Abomonation;
+use anyhow::Error;
+use async_trait::async_trait;
+use bytes::Bytes;
+use caching_ext::CacheDisposition;
+use caching_ext::CacheHandlerFactory;
+use caching_ext::CacheTtl;
+use caching_ext::CachelibHandler;
+use caching_ext::EntityStore;
+use caching_ext::KeyedEntityStore;
+use caching_ext::McErrorKind;
+use caching_ext::McResult;
+use caching_ext::MemcacheEntity;
+use caching_ext::MemcacheHandler;
+use context::CoreContext;
+use cross_repo_sync::CommitSyncOutcome;
+use cross_repo_sync::CommitSyncer;
+use memcache::KeyGen;
+use mononoke_types::ChangesetId;
+use mononoke_types::RepositoryId;
+use synced_commit_mapping::SyncedCommitMapping;
+
+use crate::push_redirector::Repo;
+
+/// Code version used in memcache keys.  This should be changed whenever
+/// the layout of memcache entries is changed in an incompatible way.
+/// The corresponding sitever, which can be used to flush memcache, is
+/// in the JustKnob scm/mononoke_memcache_sitevers:push_redirector.
+pub const MC_CODEVER: u32 = 0;
+
+#[derive(Clone)]
+pub struct CacheHandlers {
+    memcache: MemcacheHandler,
+    commit_equivalent_cachelib: CachelibHandler<CachedCommitEquivalent>,
+    commit_equivalent_keygen: KeyGen,
+}
+
+impl CacheHandlers {
+    pub fn new(cache_handler_factory: &CacheHandlerFactory) -> Result<Self, Error> {
+        let sitever =
+            justknobs::get_as::<u32>("scm/mononoke_memcache_sitevers:push_redirector", None)?;
+        let commit_equivalent_keygen = KeyGen::new(
+            "scm.mononoke.push_redirector.commit_equivalent",
+            MC_CODEVER,
+            sitever,
+        );
+        Ok(Self {
+            memcache: cache_handler_factory.memcache(),
+            commit_equivalent_cachelib: cache_handler_factory.cachelib(),
+            commit_equivalent_keygen,
+        })
+    }
+
+    pub fn new_test() -> Self {
+        Self::new(&CacheHandlerFactory::Mocked)
+            .expect("Test construction of CacheHandlers should succeed")
+    }
+
+    /// Cache accessor for the commit equivalent looked up via `syncer`, which
+    /// determines both the direction of the mapping and the source/target
+    /// repos the cache key is scoped to.
+    pub fn commit_equivalent<'a, R: Repo>(
+        &'a self,
+        syncer: &'a CommitSyncer<Arc<dyn SyncedCommitMapping>, R>,
+        ctx: &'a CoreContext,
+    ) -> CachedCommitEquivalentStore<'a, R> {
+        CachedCommitEquivalentStore {
+            syncer,
+            cachelib: &self.commit_equivalent_cachelib,
+            memcache: &self.memcache,
+            keygen: &self.commit_equivalent_keygen,
+            ctx,
+            uncached_outcomes: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+/// A cross-repo commit equivalent, as found by a prior sync. Only positive
+/// (`CommitSyncOutcome::RewrittenAs`) results are ever cached: the mapping is
+/// append-only, so a commit that is not yet synced under the mapping version
+/// current at lookup time will simply miss the cache and fall through to the
+/// database, rather than risk serving a stale negative result.
+#[derive(Abomonation, Clone)]
+pub struct CachedCommitEquivalent {
+    pub cs_id: ChangesetId,
+    pub version: String,
+}
+
+impl MemcacheEntity for CachedCommitEquivalent {
+    fn serialize(&self) -> Bytes {
+        let mut buf = Vec::with_capacity(32 + self.version.len());
+        buf.extend_from_slice(self.cs_id.as_ref());
+        buf.extend_from_slice(self.version.as_bytes());
+        Bytes::from(buf)
+    }
+
+    fn deserialize(bytes: Bytes) -> McResult<Self> {
+        if bytes.len() < 32 {
+            return Err(McErrorKind::Deserialization);
+        }
+        let cs_id = ChangesetId::from_bytes(bytes.slice(0..32))
+            .map_err(|_| McErrorKind::Deserialization)?;
+        let version = String::from_utf8(bytes.slice(32..).to_vec())
+            .map_err(|_| McErrorKind::Deserialization)?;
+        Ok(Self { cs_id, version })
+    }
+}
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct CommitEquivalentKey {
+    source_repo_id: RepositoryId,
+    target_repo_id: RepositoryId,
+    source_cs_id: ChangesetId,
+}
+
+impl CommitEquivalentKey {
+    pub fn new(
+        source_repo_id: RepositoryId,
+        target_repo_id: RepositoryId,
+        source_cs_id: ChangesetId,
+    ) -> Self {
+        Self {
+            source_repo_id,
+            target_repo_id,
+            source_cs_id,
+        }
+    }
+}
+
+pub struct CachedCommitEquivalentStore<'a, R> {
+    syncer: &'a CommitSyncer<Arc<dyn SyncedCommitMapping>, R>,
+    cachelib: &'a CachelibHandler<CachedCommitEquivalent>,
+    memcache: &'a MemcacheHandler,
+    keygen: &'a KeyGen,
+    ctx: &'a CoreContext,
+    /// `CommitSyncOutcome`s fetched by `get_from_db` that turned out not to
+    /// be cacheable (i.e. not `RewrittenAs`), keyed by the source changeset
+    /// id that was looked up. `get_from_db` is the only place that queries
+    /// the mapping, so stashing the full outcome here lets callers read a
+    /// miss back out via `take_uncached_outcome` instead of issuing a
+    /// second, identical query.
+    uncached_outcomes: RefCell<HashMap<ChangesetId, CommitSyncOutcome>>,
+}
+
+impl<'a, R: Repo> EntityStore<CachedCommitEquivalent> for CachedCommitEquivalentStore<'a, R> {
+    fn cachelib(&self) -> &CachelibHandler<CachedCommitEquivalent> {
+        self.cachelib
+    }
+
+    fn keygen(&self) -> &KeyGen {
+        self.keygen
+    }
+
+    fn memcache(&self) -> &MemcacheHandler {
+        self.memcache
+    }
+
+    fn cache_determinator(&self, _v: &CachedCommitEquivalent) -> CacheDisposition {
+        // Once a commit has been synced under a given mapping version, its
+        // equivalent never changes, so this can be cached indefinitely.
+        CacheDisposition::Cache(CacheTtl::NoTtl)
+    }
+
+    caching_ext::impl_singleton_stats!("push_redirector.commit_equivalent");
+}
+
+#[async_trait]
+impl<'a, R: Repo> KeyedEntityStore<CommitEquivalentKey, CachedCommitEquivalent>
+    for CachedCommitEquivalentStore<'a, R>
+{
+    fn get_cache_key(&self, key: &CommitEquivalentKey) -> String {
+        format!(
+            "push_redirector.commit_equivalent.{}.{}.{}",
+            key.source_repo_id, key.target_repo_id, key.source_cs_id,
+        )
+    }
+
+    async fn get_from_db(
+        &self,
+        keys: HashSet<CommitEquivalentKey>,
+    ) -> Result<HashMap<CommitEquivalentKey, CachedCommitEquivalent>, Error> {
+        let mut res = HashMap::new();
+        for key in keys {
+            match self
+                .syncer
+                .get_commit_sync_outcome(self.ctx, key.source_cs_id)
+                .await?
+            {
+                Some(CommitSyncOutcome::RewrittenAs(cs_id, version)) => {
+                    res.insert(
+                        key,
+                        CachedCommitEquivalent {
+                            cs_id,
+                            version: version.0,
+                        },
+                    );
+                }
+                Some(outcome) => {
+                    self.uncached_outcomes
+                        .borrow_mut()
+                        .insert(key.source_cs_id, outcome);
+                }
+                None => {}
+            }
+        }
+        Ok(res)
+    }
+}
+
+impl<'a, R> CachedCommitEquivalentStore<'a, R> {
+    /// Take back the `CommitSyncOutcome` for `source_cs_id` that `get_from_db`
+    /// already fetched but couldn't cache, if any. Callers should check this
+    /// on a cache miss instead of re-querying the mapping themselves.
+    pub fn take_uncached_outcome(&self, source_cs_id: ChangesetId) -> Option<CommitSyncOutcome> {
+        self.uncached_outcomes.borrow_mut().remove(&source_cs_id)
+    }
+}
@@ -114,6 +114,8 @@ use git_symbolic_refs::SqlGitSymbolicRefsBuilder;
 use hook_manager::manager::ArcHookManager;
 use hook_manager::manager::HookManager;
 use hook_manager::TextOnlyHookStateProvider;
+use hook_outcome_store::ArcHookOutcomeStore;
+use hook_outcome_store::SqlHookOutcomeStoreConnection;
 use hooks::hook_loader::load_hooks;
 use live_commit_sync_config::CfgrLiveCommitSyncConfig;
 use memcache::KeyGen;
@@ -738,6 +740,9 @@ pub enum RepoFactoryError {
 
     #[error("Error opening push redirector DB")]
     PushRedirectConfig,
+
+    #[error("Error opening hook outcome store")]
+    HookOutcomeStore,
 }
 
 #[facet::factory(name: String, repo_config_param: RepoConfig, common_config_param: CommonConfig)]
@@ -1004,6 +1009,17 @@ impl RepoFactory {
         Ok(Arc::new(conn.with_repo_id(repo_config.repoid)))
     }
 
+    pub async fn hook_outcome_store(
+        &self,
+        repo_config: &ArcRepoConfig,
+    ) -> Result<ArcHookOutcomeStore> {
+        let conn = self
+            .open_sql::<SqlHookOutcomeStoreConnection>(repo_config)
+            .await
+            .context(RepoFactoryError::HookOutcomeStore)?;
+        Ok(Arc::new(conn.with_repo_id(repo_config.repoid)))
+    }
+
     pub async fn permission_checker(
         &self,
         repo_config: &ArcRepoConfig,
@@ -1352,6 +1368,7 @@ impl RepoFactory {
         repo_blobstore: &ArcRepoBlobstore,
         bonsai_tag_mapping: &ArcBonsaiTagMapping,
         bonsai_git_mapping: &ArcBonsaiGitMapping,
+        hook_outcome_store: &ArcHookOutcomeStore,
     ) -> Result<ArcHookManager> {
         let name = repo_identity.name();
 
@@ -1394,6 +1411,7 @@ impl RepoFactory {
                 repo_config.hook_manager_params.clone().unwrap_or_default(),
                 hooks_scuba,
                 name.to_string(),
+                hook_outcome_store.clone(),
             )
             .await?;
 
@@ -1574,6 +1592,9 @@ impl RepoFactory {
             .get_common_config_if_exists(repo_identity.id())
             .context(RepoFactoryError::PushRedirectorBase)?;
         let synced_commit_mapping = repo_cross_repo.synced_commit_mapping();
+        let cache_handler_factory = self
+            .cache_handler_factory("push_redirector")?
+            .map(Arc::new);
 
         let push_redirector_mode = match common_commit_sync_config {
             Some(common_commit_sync_config)
@@ -1583,6 +1604,7 @@ impl RepoFactory {
                     common_commit_sync_config,
                     synced_commit_mapping: synced_commit_mapping.clone(),
                     target_repo_dbs: target_repo_dbs.clone(),
+                    cache_handler_factory,
                 }))
             }
             _ => PushRedirectorMode::Disabled,
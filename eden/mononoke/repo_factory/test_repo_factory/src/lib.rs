@@ -30,6 +30,7 @@ use bookmarks::ArcBookmarks;
 use bookmarks_cache::ArcBookmarksCache;
 use cacheblob::InProcessLease;
 use cacheblob::LeaseOps;
+use caching_ext::CacheHandlerFactory;
 use changesets::ArcChangesets;
 use changesets_impl::SqlChangesetsBuilder;
 use commit_cloud::sql::builder::SqlCommitCloudBuilder;
@@ -55,6 +56,8 @@ use git_symbolic_refs::ArcGitSymbolicRefs;
 use git_symbolic_refs::SqlGitSymbolicRefsBuilder;
 use hook_manager::manager::ArcHookManager;
 use hook_manager::manager::HookManager;
+use hook_outcome_store::ArcHookOutcomeStore;
+use hook_outcome_store::SqlHookOutcomeStoreConnection;
 use live_commit_sync_config::LiveCommitSyncConfig;
 use live_commit_sync_config::TestLiveCommitSyncConfig;
 use maplit::hashmap;
@@ -262,6 +265,7 @@ impl TestRepoFactory {
         metadata_con.execute_batch(SqlGitSymbolicRefsBuilder::CREATION_QUERY)?;
         metadata_con.execute_batch(SqlPhasesBuilder::CREATION_QUERY)?;
         metadata_con.execute_batch(SqlPushrebaseMutationMappingConnection::CREATION_QUERY)?;
+        metadata_con.execute_batch(SqlHookOutcomeStoreConnection::CREATION_QUERY)?;
         metadata_con.execute_batch(SqlLongRunningRequestsQueue::CREATION_QUERY)?;
         metadata_con.execute_batch(SqlMutableRenamesStore::CREATION_QUERY)?;
         metadata_con.execute_batch(SqlSyncedCommitMapping::CREATION_QUERY)?;
@@ -541,6 +545,17 @@ impl TestRepoFactory {
         ))
     }
 
+    /// Construct a Hook Outcome Store using the in-memory metadata database.
+    pub fn hook_outcome_store(
+        &self,
+        repo_identity: &ArcRepoIdentity,
+    ) -> Result<ArcHookOutcomeStore> {
+        Ok(Arc::new(
+            SqlHookOutcomeStoreConnection::from_sql_connections(self.metadata_db.clone())
+                .with_repo_id(repo_identity.id()),
+        ))
+    }
+
     /// Construct permission checker.  By default this allows all access.
     pub fn permission_checker(&self) -> Result<ArcRepoPermissionChecker> {
         if let Some(permission_checker) = &self.permission_checker {
@@ -699,6 +714,7 @@ impl TestRepoFactory {
                     common_commit_sync_config,
                     target_repo_dbs,
                     synced_commit_mapping: synced_commit_mapping.clone(),
+                    cache_handler_factory: Some(Arc::new(CacheHandlerFactory::Mocked)),
                 })
             });
         Ok(Arc::new(RepoHandlerBase {
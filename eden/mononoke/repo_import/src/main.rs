@@ -505,6 +505,7 @@ async fn move_bookmark(
                 CommitSyncContext::RepoImport,
                 false,
                 Box::new(future::ready(())),
+                None,
             )
             .await?
             .await;
@@ -584,11 +584,15 @@ mod tests {
                     bookmark_prefix: AsciiString::from_str("large_repo_bookmark/")
                         .unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 },
                 RepositoryId::new(2) => SmallRepoPermanentConfig {
                     bookmark_prefix: AsciiString::from_str("large_repo_bookmark_2/")
                         .unwrap(),
                     common_pushrebase_bookmarks_map: HashMap::new(),
+                    bookmark_redirection_namespaces: Vec::new(),
+                    pushvar_passthrough_policy: Default::default(),
                 },
             },
             large_repo_id: commit_sync_config.large_repo_id,
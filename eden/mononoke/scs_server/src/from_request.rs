@@ -40,6 +40,7 @@ use mononoke_api::FileType;
 use mononoke_api::HgChangesetId;
 use mononoke_api::HgChangesetIdPrefix;
 use mononoke_api::TreeId;
+use mononoke_types::hash::Blake3;
 use mononoke_types::hash::Sha1;
 use mononoke_types::hash::Sha256;
 use mononoke_types::path::MPath;
@@ -292,6 +293,7 @@ impl_from_request_binary_id!(FileId, "file id");
 impl_from_request_binary_id!(Sha1, "sha-1");
 impl_from_request_binary_id!(Sha256, "sha-256");
 impl_from_request_binary_id!(GitSha1, "git-sha-1");
+impl_from_request_binary_id!(Blake3, "seeded-blake3");
 
 impl FromRequest<thrift::RepoCreateCommitParamsFileType> for FileType {
     fn from_request(
@@ -43,6 +43,7 @@ use mononoke_api::SessionContainer;
 use mononoke_api::TreeContext;
 use mononoke_api::TreeId;
 use mononoke_configs::MononokeConfigs;
+use mononoke_types::hash::Blake3;
 use mononoke_types::hash::Sha1;
 use mononoke_types::hash::Sha256;
 use permission_checker::MononokeIdentity;
@@ -573,6 +574,15 @@ impl SourceControlServiceImpl {
                     .ok_or_else(|| errors::file_not_found(file.description()))?;
                 (repo, Some(file))
             }
+            thrift::FileSpecifier::by_seeded_blake3_content_hash(hash) => {
+                let repo = self.repo(ctx, &hash.repo).await?;
+                let file_blake3 = Blake3::from_request(&hash.content_hash)?;
+                let file = repo
+                    .file_by_content_seeded_blake3(file_blake3)
+                    .await?
+                    .ok_or_else(|| errors::file_not_found(file.description()))?;
+                (repo, Some(file))
+            }
             thrift::FileSpecifier::UnknownField(id) => {
                 return Err(errors::invalid_request(format!(
                     "file specifier type not supported: {}",
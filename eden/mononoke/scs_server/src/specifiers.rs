@@ -128,6 +128,11 @@ impl SpecifierExt for thrift::FileSpecifier {
                 hash.repo.name,
                 hex_string(&hash.content_hash),
             ),
+            thrift::FileSpecifier::by_seeded_blake3_content_hash(hash) => format!(
+                "repo={} file_seeded_blake3={}",
+                hash.repo.name,
+                hex_string(&hash.content_hash),
+            ),
             thrift::FileSpecifier::UnknownField(n) => format!("unknown file specifier type {}", n),
         }
     }
@@ -138,6 +143,9 @@ impl SpecifierExt for thrift::FileSpecifier {
             thrift::FileSpecifier::by_id(file_id) => file_id.repo.scuba_reponame(),
             thrift::FileSpecifier::by_sha1_content_hash(hash) => hash.repo.scuba_reponame(),
             thrift::FileSpecifier::by_sha256_content_hash(hash) => hash.repo.scuba_reponame(),
+            thrift::FileSpecifier::by_seeded_blake3_content_hash(hash) => {
+                hash.repo.scuba_reponame()
+            }
             thrift::FileSpecifier::UnknownField(_) => None,
         }
     }
@@ -147,6 +155,7 @@ impl SpecifierExt for thrift::FileSpecifier {
             thrift::FileSpecifier::by_id(_file_id) => None,
             thrift::FileSpecifier::by_sha1_content_hash(_hash) => None,
             thrift::FileSpecifier::by_sha256_content_hash(_hash) => None,
+            thrift::FileSpecifier::by_seeded_blake3_content_hash(_hash) => None,
             thrift::FileSpecifier::UnknownField(_) => None,
         }
     }
@@ -160,6 +169,9 @@ impl SpecifierExt for thrift::FileSpecifier {
             thrift::FileSpecifier::by_sha256_content_hash(hash) => {
                 Some(hex_string(&hash.content_hash))
             }
+            thrift::FileSpecifier::by_seeded_blake3_content_hash(hash) => {
+                Some(hex_string(&hash.content_hash))
+            }
             thrift::FileSpecifier::UnknownField(_) => None,
         }
     }
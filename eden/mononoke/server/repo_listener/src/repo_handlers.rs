@@ -42,11 +42,26 @@ pub fn repo_handler(mononoke: Arc<Mononoke>, repo_name: &str) -> anyhow::Result<
             let target_repo = mononoke
                 .raw_repo_by_id(large_repo_id.id())
                 .ok_or(ErrorKind::LargeRepoNotFound(large_repo_id))?;
+            let bookmark_redirection_namespaces = push_redirector_base
+                .common_commit_sync_config
+                .small_repos
+                .get(&source_repo.repoid())
+                .map(|small_repo_config| small_repo_config.bookmark_redirection_namespaces.clone())
+                .unwrap_or_default();
+            let pushvar_passthrough_policy = push_redirector_base
+                .common_commit_sync_config
+                .small_repos
+                .get(&source_repo.repoid())
+                .map(|small_repo_config| small_repo_config.pushvar_passthrough_policy.clone())
+                .unwrap_or_default();
             Some(PushRedirectorArgs::new(
                 target_repo,
                 Arc::clone(&source_repo),
                 push_redirector_base.synced_commit_mapping.clone(),
                 Arc::clone(&push_redirector_base.target_repo_dbs),
+                bookmark_redirection_namespaces,
+                pushvar_passthrough_policy,
+                push_redirector_base.cache_handler_factory.clone(),
             ))
         }
         None => None,
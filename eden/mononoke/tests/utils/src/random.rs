@@ -50,7 +50,7 @@ pub async fn create_random_stack(
     Ok((changeset_id, manifest))
 }
 
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct GenSettings {
     /// probablity of descending one level deeper when generating change
     pub p_dir_descend: f64,
@@ -60,6 +60,8 @@ pub struct GenSettings {
     pub p_file_create: f64,
     /// probability to delete file instead of modifying
     pub p_file_delete: f64,
+    /// distribution of the size (in bytes) of generated file content
+    pub file_size: Uniform<usize>,
 }
 
 impl Default for GenSettings {
@@ -69,6 +71,7 @@ impl Default for GenSettings {
             p_dir_create: 0.2,
             p_file_create: 0.3,
             p_file_delete: 0.1,
+            file_size: Uniform::new_inclusive(8, 24),
         }
     }
 }
@@ -218,7 +221,7 @@ impl GenManifest {
                 self.files.remove(&filename);
                 None
             } else {
-                let data = gen_ascii(16, rng);
+                let data = gen_ascii(rng.sample(settings.file_size), rng);
                 self.files.insert(filename, data.clone());
                 Some(data)
             };
@@ -230,6 +233,111 @@ impl GenManifest {
     }
 }
 
+/// Settings controlling the shape of a repo generated by [`create_random_repo`].
+#[derive(Clone)]
+pub struct RepoGenSettings {
+    /// Settings used to generate the file changes of each non-merge commit.
+    pub gen_settings: GenSettings,
+    /// Number of file changes to make in each non-merge commit.
+    pub changes_per_commit: usize,
+    /// Probability that a commit merges two existing branch tips instead of
+    /// extending one of them. Ignored until there are at least two tips.
+    pub p_merge: f64,
+}
+
+impl Default for RepoGenSettings {
+    fn default() -> Self {
+        Self {
+            gen_settings: GenSettings::default(),
+            changes_per_commit: 3,
+            p_merge: 0.1,
+        }
+    }
+}
+
+/// Generate a repo with `commit_count` commits, deterministically from `rng`.
+///
+/// Commits are built up as a forest of branches: each new commit either
+/// extends a randomly chosen existing branch tip, or (with probability
+/// `settings.p_merge`, once there are at least two tips) merges two randomly
+/// chosen tips together. Once `commit_count` commits have been created, any
+/// remaining branch tips are merged together so that the returned changeset
+/// is a descendant of every generated commit.
+pub async fn create_random_repo(
+    ctx: &CoreContext,
+    repo: &(impl RepoBlobstoreRef + CommitGraphRef + CommitGraphWriterRef + RepoIdentityRef),
+    rng: &mut impl Rng,
+    commit_count: usize,
+    settings: &RepoGenSettings,
+) -> Result<ChangesetId, Error> {
+    if commit_count == 0 {
+        return Err(Error::msg("commit_count must be at least 1"));
+    }
+
+    let mut tips = Vec::new();
+    for _ in 0..commit_count {
+        let cs_id = if tips.len() >= 2 && rng.gen_bool(settings.p_merge) {
+            tips.shuffle(rng);
+            let p2 = tips.pop().expect("checked tips.len() >= 2 above");
+            let p1 = tips.pop().expect("checked tips.len() >= 2 above");
+            create_merge_commit(ctx, repo, p1, p2).await?
+        } else {
+            let parent = if tips.is_empty() {
+                None
+            } else {
+                tips.remove(rng.gen_range(0..tips.len()))
+            }
+            .into();
+            let (cs_id, _manifest) = create_random_stack(
+                ctx,
+                repo,
+                rng,
+                parent,
+                std::iter::once(settings.changes_per_commit),
+            )
+            .await?;
+            cs_id
+        };
+        tips.push(cs_id);
+    }
+
+    let mut tips = tips.into_iter();
+    let mut head = tips.next().expect("commit_count is at least 1");
+    for tip in tips {
+        head = create_merge_commit(ctx, repo, head, tip).await?;
+    }
+    Ok(head)
+}
+
+/// Create a trivial merge commit (no file changes of its own) joining `p1`
+/// and `p2`, mirroring how the other fixture helpers in this crate build
+/// merge commits (see `fixtures::save_diamond_commits`).
+async fn create_merge_commit(
+    ctx: &CoreContext,
+    repo: &(impl RepoBlobstoreRef + CommitGraphRef + CommitGraphWriterRef + RepoIdentityRef),
+    p1: ChangesetId,
+    p2: ChangesetId,
+) -> Result<ChangesetId, Error> {
+    let bonsai = BonsaiChangesetMut {
+        parents: vec![p1, p2],
+        author: "author".to_string(),
+        author_date: DateTime::from_timestamp(0, 0).unwrap(),
+        committer: None,
+        committer_date: None,
+        message: "merge".to_string(),
+        hg_extra: Default::default(),
+        git_extra_headers: None,
+        git_tree_hash: None,
+        file_changes: Default::default(),
+        is_snapshot: false,
+        git_annotated_tag: None,
+    }
+    .freeze()?;
+    let cs_id = bonsai.get_changeset_id();
+    save_changesets(ctx, repo, vec![bonsai]).await?;
+    Ok(cs_id)
+}
+
 fn gen_ascii(len: usize, rng: &mut impl Rng) -> String {
     let chars = b"_abcdefghijklmnopqrstuvwxyz";
     let bytes = rng
@@ -14,6 +14,7 @@ mod range_stream;
 mod segments;
 mod slice_ancestors;
 mod update_preloaded;
+mod verify_against_changesets;
 
 use ancestors_difference::AncestorsDifferenceArgs;
 use anyhow::Result;
@@ -38,6 +39,7 @@ use repo_identity::RepoIdentity;
 use segments::SegmentsArgs;
 use slice_ancestors::SliceAncestorsArgs;
 use update_preloaded::UpdatePreloadedArgs;
+use verify_against_changesets::VerifyAgainstChangesetsArgs;
 
 /// Query and manage the commit graph
 #[derive(Parser)]
@@ -72,6 +74,10 @@ pub enum CommitGraphSubcommand {
     Segments(SegmentsArgs),
     /// Check if a commit is an ancestor of another commit.
     IsAncestor(IsAncestorArgs),
+    /// Cross-check the commit graph's parent edges against bonsai changeset
+    /// parents over a commit range, for use after migrations between
+    /// changeset/commit-graph storage implementations.
+    VerifyAgainstChangesets(VerifyAgainstChangesetsArgs),
 }
 
 #[facet::container]
@@ -132,5 +138,8 @@ pub async fn run(app: MononokeApp, args: CommandArgs) -> Result<()> {
         CommitGraphSubcommand::IsAncestor(args) => {
             is_ancestor::is_ancestor(&ctx, &repo, args).await
         }
+        CommitGraphSubcommand::VerifyAgainstChangesets(args) => {
+            verify_against_changesets::verify_against_changesets(&ctx, &repo, args).await
+        }
     }
 }
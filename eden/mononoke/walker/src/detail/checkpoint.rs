@@ -8,6 +8,7 @@
 use std::cmp::Ordering;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 use anyhow::bail;
 use anyhow::Error;
@@ -34,7 +35,56 @@ pub struct Checkpoint {
     pub last_finish_timestamp: Option<Timestamp>,
 }
 
+/// How far a checkpoint has progressed through the full repo bounds, and an
+/// estimate of how long is left to completion at the rate seen so far this
+/// run, so that long-running chunked walks can be monitored without having
+/// to wait for them to finish.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheckpointProgress {
+    pub fraction_complete: f64,
+    pub eta: Option<Duration>,
+}
+
 impl Checkpoint {
+    /// Estimate progress and ETA for this checkpoint against the full repo
+    /// bounds, based on how much of the bound range has been consumed since
+    /// `create_timestamp` (i.e. since the start of the current run).
+    pub fn progress(
+        &self,
+        repo_lower: u64,
+        repo_upper: u64,
+        direction: Direction,
+    ) -> CheckpointProgress {
+        let total = repo_upper.saturating_sub(repo_lower);
+        if total == 0 {
+            return CheckpointProgress {
+                fraction_complete: 1.0,
+                eta: Some(Duration::ZERO),
+            };
+        }
+
+        let remaining = if direction == Direction::NewestFirst {
+            self.lower_bound.saturating_sub(repo_lower)
+        } else {
+            repo_upper.saturating_sub(self.upper_bound)
+        };
+        let done = total.saturating_sub(remaining);
+        let fraction_complete = done as f64 / total as f64;
+
+        let elapsed_secs = self.create_timestamp.since_seconds();
+        let eta = if done > 0 && elapsed_secs > 0 {
+            let rate = done as f64 / elapsed_secs as f64;
+            Some(Duration::from_secs_f64(remaining as f64 / rate))
+        } else {
+            None
+        };
+
+        CheckpointProgress {
+            fraction_complete,
+            eta,
+        }
+    }
+
     /// Get the bounds for a catchup stream for new Changesets, plus the main stream for continuing from this checkpoint
     pub fn stream_bounds(
         &self,
@@ -199,6 +249,19 @@ impl CheckpointsByName {
     pub fn name(&self) -> &str {
         self.checkpoint_name.as_str()
     }
+
+    /// Load the checkpoint for `repo_id` and report its progress and ETA
+    /// against the given repo bounds, for monitoring long-running walks.
+    pub async fn progress(
+        &self,
+        repo_id: RepositoryId,
+        repo_lower: u64,
+        repo_upper: u64,
+        direction: Direction,
+    ) -> Result<Option<CheckpointProgress>, Error> {
+        let checkpoint = self.load(repo_id).await?;
+        Ok(checkpoint.map(|cp| cp.progress(repo_lower, repo_upper, direction)))
+    }
 }
 
 impl fmt::Debug for CheckpointsByName {
@@ -572,4 +635,35 @@ mod tests {
     async fn test_sql_roundtrip_v2(_fb: FacebookInit) -> Result<(), Error> {
         test_sql_roundtrip_impl(CheckpointsVersion::V2).await
     }
+
+    #[test]
+    fn test_checkpoint_progress() {
+        let now = Timestamp::now();
+        let checkpoint = Checkpoint {
+            lower_bound: 40,
+            upper_bound: 100,
+            create_timestamp: Timestamp::from_timestamp_secs(now.timestamp_seconds() - 60)
+                .unwrap(),
+            update_timestamp: now,
+            update_run_number: 1,
+            update_chunk_number: 3,
+            last_finish_timestamp: None,
+        };
+
+        // OldestFirst has walked from 0 up to 100 out of a total range of (0, 100),
+        // i.e. fully done.
+        let progress = checkpoint.progress(0, 100, Direction::OldestFirst);
+        assert_eq!(progress.fraction_complete, 1.0);
+
+        // NewestFirst has walked down from 100 to 40 out of a total range of (0, 100),
+        // i.e. 60% done, with the remaining 40 estimated to take another 40s at the
+        // rate of 1 unit/s seen so far.
+        let progress = checkpoint.progress(0, 100, Direction::NewestFirst);
+        assert_eq!(progress.fraction_complete, 0.6);
+        assert_eq!(progress.eta, Some(Duration::from_secs(40)));
+
+        // An empty repo is trivially complete.
+        let progress = checkpoint.progress(50, 50, Direction::OldestFirst);
+        assert_eq!(progress.fraction_complete, 1.0);
+    }
 }
@@ -11,6 +11,7 @@ use std::collections::HashSet;
 use std::fmt;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
 use anyhow::Error;
@@ -89,6 +90,102 @@ impl fmt::Display for SizingStats {
     }
 }
 
+// Number of power-of-two buckets to track. A u64 value can't need more than this.
+const HISTOGRAM_BUCKETS: usize = 64;
+
+/// A simple power-of-two bucketed histogram, used to get a sense of the
+/// distribution of a value (e.g. node size or fan-out) without having to
+/// retain every sampled value.
+#[derive(Clone, Debug, Default)]
+struct Histogram {
+    // counts[0] is the number of samples v == 0, and counts[i] for i >= 1 the number with
+    // 2^(i-1) <= v < 2^i; samples of 2^63 and above are also counted in counts[63]
+    counts: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl Histogram {
+    fn record(&mut self, value: u64) {
+        let bucket = if value == 0 {
+            0
+        } else {
+            min(63, 64 - value.leading_zeros()) as usize
+        };
+        self.counts[bucket] += 1;
+    }
+
+    // Only report buckets that actually received samples, to keep the report small.
+    fn to_json(&self) -> serde_json::Value {
+        let buckets: serde_json::Map<String, serde_json::Value> = self
+            .counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count > 0)
+            .map(|(bucket, count)| {
+                let lower = if bucket == 0 { 0 } else { 1u64 << (bucket - 1) };
+                let upper = (1u64 << bucket).saturating_sub(1);
+                (format!("{}-{}", lower, upper), serde_json::json!(count))
+            })
+            .collect();
+        serde_json::Value::Object(buckets)
+    }
+}
+
+#[derive(Default)]
+struct TypeHistograms {
+    count: u64,
+    size: Histogram,
+    fanout: Histogram,
+}
+
+/// Accumulates per node type size and fan-out histograms for a single repo
+/// walk, so that capacity planning can be done from a sampled walk instead
+/// of requiring a full exhaustive one.
+#[derive(Default)]
+pub struct HistogramAccumulator {
+    by_type: Mutex<HashMap<NodeType, TypeHistograms>>,
+}
+
+impl HistogramAccumulator {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn record_size(&self, node_type: NodeType, size: u64) {
+        let mut by_type = self.by_type.lock().expect("poisoned lock");
+        let entry = by_type.entry(node_type).or_default();
+        entry.size.record(size);
+    }
+
+    fn record_fanout(&self, node_type: NodeType, fanout: u64) {
+        let mut by_type = self.by_type.lock().expect("poisoned lock");
+        let entry = by_type.entry(node_type).or_default();
+        entry.count += 1;
+        entry.fanout.record(fanout);
+    }
+
+    /// Render the accumulated histograms as a machine-readable report for this repo.
+    fn to_report(&self, repo_name: &str) -> serde_json::Value {
+        let by_type = self.by_type.lock().expect("poisoned lock");
+        let types: serde_json::Map<String, serde_json::Value> = by_type
+            .iter()
+            .map(|(node_type, histograms)| {
+                (
+                    node_type.to_string(),
+                    serde_json::json!({
+                        "count": histograms.count,
+                        "size_histogram": histograms.size.to_json(),
+                        "fanout_histogram": histograms.fanout.to_json(),
+                    }),
+                )
+            })
+            .collect();
+        serde_json::json!({
+            "repo": repo_name,
+            "types": types,
+        })
+    }
+}
+
 fn try_compress(raw_data: &Bytes, level: i32) -> Result<SizingStats, Error> {
     let raw = raw_data.len() as u64;
     let compressed_data = zstd::stream::encode_all(raw_data.as_ref(), level)?;
@@ -344,26 +441,43 @@ async fn run_one(
     command: SizingCommand,
     cancellation_requested: Arc<AtomicBool>,
 ) -> Result<(), Error> {
+    let repo_name = repo_params.repo.repo_identity().name().to_string();
+    let logger = repo_params.logger.clone();
+
     let sizing_progress_state =
         ProgressStateMutex::new(ProgressStateCountByType::<SizingStats, SizingStats>::new(
             fb,
             repo_params.logger.clone(),
             COMPRESSION_BENEFIT,
-            repo_params.repo.repo_identity().name().to_string(),
+            repo_name.clone(),
             command.sampling_options.node_types.clone(),
             command.progress_options,
         ));
 
+    let histogram = Arc::new(HistogramAccumulator::new());
+
     let make_sink = {
-        cloned!(command, job_params.quiet, sub_params.progress_state,);
+        cloned!(
+            command,
+            job_params.quiet,
+            sub_params.progress_state,
+            histogram
+        );
         move |ctx: &CoreContext, repo_params: &RepoWalkParams| {
-            cloned!(ctx, repo_params.scheduled_max);
+            cloned!(ctx, repo_params.scheduled_max, histogram);
             move |walk_output, _run_start, _chunk_num, _checkpoint_name| async move {
-                cloned!(ctx, sizing_progress_state);
+                cloned!(ctx, sizing_progress_state, histogram);
                 // Sizing doesn't use mtime, so remove it from payload
-                let walk_progress = progress_stream(quiet, &progress_state, walk_output).map_ok(
-                    |(key, payload, stats): (_, WalkPayloadMtime, _)| (key, payload.data, stats),
-                );
+                let walk_progress = progress_stream(quiet, &progress_state, walk_output).map_ok({
+                    cloned!(histogram);
+                    move |(key, payload, stats): (_, WalkPayloadMtime, _)| {
+                        if let Some(stats) = stats.as_ref() {
+                            histogram
+                                .record_fanout(key.node.get_type(), stats.num_expanded_new as u64);
+                        }
+                        (key, payload.data, stats)
+                    }
+                });
 
                 let compressor = size_sampling_stream(
                     scheduled_max,
@@ -371,7 +485,16 @@ async fn run_one(
                     command.compression_level,
                     command.sampler,
                 );
-                let report_sizing = progress_stream(quiet, &sizing_progress_state, compressor);
+                let report_sizing = progress_stream(quiet, &sizing_progress_state, compressor)
+                    .map_ok({
+                        cloned!(histogram);
+                        move |(node, data, stats): (Node, Option<NodeData>, Option<SizingStats>)| {
+                            if let Some(stats) = stats.as_ref() {
+                                histogram.record_size(node.get_type(), stats.raw);
+                            }
+                            (node, data, stats)
+                        }
+                    });
 
                 report_state(ctx, report_sizing).await?;
                 sizing_progress_state.report_progress();
@@ -412,5 +535,13 @@ async fn run_one(
         make_sink,
         cancellation_requested,
     )
-    .await
+    .await?;
+
+    info!(
+        logger,
+        "Size/fan-out histogram report: {}",
+        histogram.to_report(&repo_name)
+    );
+
+    Ok(())
 }
@@ -290,6 +290,18 @@ where
 
             info!(repo_params.logger, #log::CHUNKING, "Repo bounds: ({}, {})", lower, upper);
 
+            if let Some(cp) = checkpoint.as_ref() {
+                let progress = cp.progress(lower, upper, chunking.direction);
+                info!(
+                    repo_params.logger, #log::CHUNKING,
+                    "Checkpoint progress: {:.2}% complete, ETA {}",
+                    progress.fraction_complete * 100.0,
+                    progress
+                        .eta
+                        .map_or_else(|| "unknown".to_string(), |eta| format!("{}s", eta.as_secs())),
+                );
+            }
+
             let (contiguous_bounds, best_bound, catchup_bounds, main_bounds) = if let Some(
                 ref mut checkpoint,
             ) = checkpoint
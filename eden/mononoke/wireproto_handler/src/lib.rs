@@ -11,6 +11,7 @@ use bonsai_hg_mapping::BonsaiHgMapping;
 use bonsai_hg_mapping::BonsaiHgMappingArc;
 use bookmarks::ArcBookmarkUpdateLog;
 use bookmarks::ArcBookmarks;
+use caching_ext::CacheHandlerFactory;
 use facet::facet;
 use metaconfig_types::BackupRepoConfig;
 use metaconfig_types::CommonCommitSyncConfig;
@@ -38,6 +39,9 @@ pub struct PushRedirectorBase {
     pub common_commit_sync_config: CommonCommitSyncConfig,
     pub synced_commit_mapping: Arc<dyn SyncedCommitMapping>,
     pub target_repo_dbs: Arc<TargetRepoDbs>,
+    /// Factory for the cache backing the cross-repo commit equivalence
+    /// cache. `None` if caching is disabled for this repo.
+    pub cache_handler_factory: Option<Arc<CacheHandlerFactory>>,
 }
 
 #[derive(Clone)]
@@ -0,0 +1,120 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Adaptive batch sizing for tree/file requests.
+//!
+//! The number of keys we pack into a single EdenAPI request is a tradeoff: too small and we pay
+//! per-request overhead on a fast link, too big and a single slow/unreliable hop turns into a
+//! huge request that's expensive to retry. [`AdaptiveBatcher`] starts at a configured size and
+//! shrinks it on errors/high latency, growing it back up when things are healthy, replacing the
+//! fixed `maxfiles`/`maxtrees` constants for callers that opt in.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// Latency above which a batch is considered "slow" and the batcher shrinks.
+const SLOW_THRESHOLD: Duration = Duration::from_secs(2);
+
+/// Shrinks the batch size by this factor on error or high latency.
+const SHRINK_FACTOR: usize = 2;
+
+/// Grows the batch size by this amount after a healthy request.
+const GROW_STEP: usize = 16;
+
+pub(crate) struct AdaptiveBatcher {
+    current: AtomicUsize,
+    min: usize,
+    max: usize,
+}
+
+impl AdaptiveBatcher {
+    pub(crate) fn new(initial: usize, min: usize, max: usize) -> Self {
+        let initial = initial.clamp(min, max);
+        Self {
+            current: AtomicUsize::new(initial),
+            min,
+            max,
+        }
+    }
+
+    /// Current recommended batch size.
+    pub(crate) fn batch_size(&self) -> usize {
+        self.current.load(Ordering::Relaxed)
+    }
+
+    /// Records that a batch of the current size failed outright (e.g. a 5xx or timeout),
+    /// shrinking future batches.
+    pub(crate) fn record_error(&self) {
+        self.shrink();
+    }
+
+    /// Records the outcome of a completed batch, shrinking on high latency or growing the
+    /// batch size otherwise.
+    pub(crate) fn record_latency(&self, latency: Duration) {
+        if latency > SLOW_THRESHOLD {
+            self.shrink();
+        } else {
+            self.grow();
+        }
+    }
+
+    fn shrink(&self) {
+        self.current
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |cur| {
+                Some((cur / SHRINK_FACTOR).max(self.min))
+            })
+            .ok();
+    }
+
+    fn grow(&self) {
+        self.current
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |cur| {
+                Some((cur + GROW_STEP).min(self.max))
+            })
+            .ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrinks_on_error() {
+        let batcher = AdaptiveBatcher::new(100, 10, 1000);
+        batcher.record_error();
+        assert_eq!(batcher.batch_size(), 50);
+        batcher.record_error();
+        assert_eq!(batcher.batch_size(), 25);
+    }
+
+    #[test]
+    fn test_shrinks_on_high_latency() {
+        let batcher = AdaptiveBatcher::new(100, 10, 1000);
+        batcher.record_latency(Duration::from_secs(5));
+        assert_eq!(batcher.batch_size(), 50);
+    }
+
+    #[test]
+    fn test_grows_on_low_latency() {
+        let batcher = AdaptiveBatcher::new(100, 10, 1000);
+        batcher.record_latency(Duration::from_millis(50));
+        assert_eq!(batcher.batch_size(), 116);
+    }
+
+    #[test]
+    fn test_respects_bounds() {
+        let batcher = AdaptiveBatcher::new(20, 10, 30);
+        batcher.record_error();
+        assert_eq!(batcher.batch_size(), 10);
+        for _ in 0..10 {
+            batcher.record_latency(Duration::from_millis(1));
+        }
+        assert_eq!(batcher.batch_size(), 30);
+    }
+}
@@ -117,12 +117,17 @@ pub struct HttpClientBuilder {
     headers: HashMap<String, String>,
     try_route_consistently: bool,
     augmented_trees: bool,
+    adaptive_batching: bool,
+    offline: bool,
+    retry_overrides: HashMap<String, usize>,
+    encoding_overrides: HashMap<String, Encoding>,
     max_files_per_batch: Option<usize>,
     max_trees_per_batch: Option<usize>,
     max_history_per_batch: Option<usize>,
     max_location_to_hash_per_batch: Option<usize>,
     max_commit_mutations_per_batch: Option<usize>,
     max_commit_translate_id_per_batch: Option<usize>,
+    max_commit_graph_segments_per_batch: Option<usize>,
     min_batch_size: Option<usize>,
     timeout: Option<Duration>,
     debug: bool,
@@ -191,6 +196,32 @@ impl HttpClientBuilder {
             get_config(config, "edenapi", "try-route-consistently")?.unwrap_or_default();
 
         let augmented_trees = get_config(config, "edenapi", "augmented-trees")?.unwrap_or_default();
+        let adaptive_batching =
+            get_config(config, "edenapi", "adaptive-batching")?.unwrap_or_default();
+        let offline = get_config(config, "edenapi", "offline")?.unwrap_or_default();
+
+        // Per-endpoint retry overrides, e.g. `edenapi.maxretries-history=1`, since one global
+        // retry count misbehaves for both tiny lookups and huge history fetches.
+        let mut retry_overrides = HashMap::new();
+        for key in config.keys_prefixed("edenapi", "maxretries-") {
+            if let Some(endpoint) = key.strip_prefix("maxretries-") {
+                if let Some(count) = get_config::<usize>(config, "edenapi", &key)? {
+                    retry_overrides.insert(endpoint.to_string(), count);
+                }
+            }
+        }
+
+        // Per-endpoint content-encoding overrides, e.g. `edenapi.encoding-trees=zstd`, so
+        // compression can be tuned (or disabled) independently for endpoints whose payloads
+        // compress very differently, instead of a single global `edenapi.encoding`.
+        let mut encoding_overrides = HashMap::new();
+        for key in config.keys_prefixed("edenapi", "encoding-") {
+            if let Some(endpoint) = key.strip_prefix("encoding-") {
+                if let Some(value) = get_config::<String>(config, "edenapi", &key)? {
+                    encoding_overrides.insert(endpoint.to_string(), Encoding::from(&*value));
+                }
+            }
+        }
 
         let min_batch_size = get_config(config, "edenapi", "min-batch-size")?;
         let max_files_per_batch = get_config(config, "edenapi", "maxfiles")?;
@@ -200,6 +231,8 @@ impl HttpClientBuilder {
         let max_commit_mutations_per_batch = get_config(config, "edenapi", "maxcommitmutations")?;
         let max_commit_translate_id_per_batch =
             get_config(config, "edenapi", "maxcommittranslateid")?;
+        let max_commit_graph_segments_per_batch =
+            get_config(config, "edenapi", "maxcommitgraphsegments")?;
 
         let timeout = get_config(config, "edenapi", "timeout")?.map(Duration::from_secs);
         let debug = get_config(config, "edenapi", "debug")?.unwrap_or_default();
@@ -241,12 +274,17 @@ impl HttpClientBuilder {
             headers,
             try_route_consistently,
             augmented_trees,
+            adaptive_batching,
+            offline,
+            retry_overrides,
+            encoding_overrides,
             max_files_per_batch,
             max_trees_per_batch,
             max_history_per_batch,
             max_location_to_hash_per_batch,
             max_commit_mutations_per_batch,
             max_commit_translate_id_per_batch,
+            max_commit_graph_segments_per_batch,
             min_batch_size,
             timeout,
             debug,
@@ -361,6 +399,21 @@ impl HttpClientBuilder {
         self.http_config.convert_cert = enable;
         self
     }
+
+    /// If enabled, tree/file batch sizes are adjusted at runtime based on observed latency and
+    /// error rates instead of using the fixed `maxtrees`/`maxfiles` config values.
+    pub fn adaptive_batching(mut self, enable: bool) -> Self {
+        self.adaptive_batching = enable;
+        self
+    }
+
+    /// If enabled, read requests (trees/files/history) fail fast with
+    /// [`SaplingRemoteApiError::Offline`] instead of attempting the network, and mutation
+    /// requests (e.g. bookmark moves) are queued on the client for later replay.
+    pub fn offline(mut self, enable: bool) -> Self {
+        self.offline = enable;
+        self
+    }
 }
 
 fn get_config<T: FromConfigValue>(
@@ -393,12 +446,17 @@ pub(crate) struct Config {
     pub(crate) headers: HashMap<String, String>,
     pub(crate) try_route_consistently: bool,
     pub(crate) augmented_trees: bool,
+    pub(crate) adaptive_batching: bool,
+    pub(crate) offline: bool,
+    pub(crate) retry_overrides: HashMap<String, usize>,
+    pub(crate) encoding_overrides: HashMap<String, Encoding>,
     pub(crate) max_files_per_batch: Option<usize>,
     pub(crate) max_trees_per_batch: Option<usize>,
     pub(crate) max_history_per_batch: Option<usize>,
     pub(crate) max_location_to_hash_per_batch: Option<usize>,
     pub(crate) max_commit_mutations_per_batch: Option<usize>,
     pub(crate) max_commit_translate_id_per_batch: Option<usize>,
+    pub(crate) max_commit_graph_segments_per_batch: Option<usize>,
     pub(crate) min_batch_size: Option<usize>,
     pub(crate) timeout: Option<Duration>,
     #[allow(dead_code)]
@@ -421,12 +479,17 @@ impl TryFrom<HttpClientBuilder> for Config {
             headers,
             try_route_consistently,
             augmented_trees,
+            adaptive_batching,
+            offline,
+            retry_overrides,
+            encoding_overrides,
             max_files_per_batch,
             max_trees_per_batch,
             max_history_per_batch,
             max_location_to_hash_per_batch,
             max_commit_mutations_per_batch,
             max_commit_translate_id_per_batch,
+            max_commit_graph_segments_per_batch,
             min_batch_size,
             timeout,
             debug,
@@ -460,12 +523,17 @@ impl TryFrom<HttpClientBuilder> for Config {
             headers,
             try_route_consistently,
             augmented_trees,
+            adaptive_batching,
+            offline,
+            retry_overrides,
+            encoding_overrides,
             max_files_per_batch,
             max_trees_per_batch,
             max_history_per_batch,
             max_location_to_hash_per_batch,
             max_commit_mutations_per_batch,
             max_commit_translate_id_per_batch,
+            max_commit_graph_segments_per_batch,
             min_batch_size,
             timeout,
             debug,
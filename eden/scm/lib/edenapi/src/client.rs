@@ -7,15 +7,20 @@
 
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::fs::create_dir_all;
 use std::future::ready;
 use std::num::NonZeroU64;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
+use std::time::SystemTime;
 
 use anyhow::format_err;
 use async_trait::async_trait;
+use edenapi_trait::response::StatsFuture;
+use edenapi_trait::CertErrorKind;
 use clientinfo::ClientInfo;
 use clientinfo_async::get_client_request_info_task_local;
 use edenapi_types::cloud::SmartlogDataResponse;
@@ -30,6 +35,7 @@ use edenapi_types::BlameResult;
 use edenapi_types::BonsaiChangesetContent;
 use edenapi_types::BookmarkEntry;
 use edenapi_types::BookmarkRequest;
+use edenapi_types::BookmarksSubscribeRequest;
 use edenapi_types::CloneData;
 use edenapi_types::CloudShareWorkspaceRequest;
 use edenapi_types::CloudShareWorkspaceResponse;
@@ -72,9 +78,12 @@ use edenapi_types::HistoryResponseChunk;
 use edenapi_types::IndexableId;
 use edenapi_types::LandStackRequest;
 use edenapi_types::LandStackResponse;
+use edenapi_types::LandStackResponseItem;
 use edenapi_types::LookupRequest;
 use edenapi_types::LookupResponse;
 use edenapi_types::LookupResult;
+use edenapi_types::PathHistoryRequest;
+use edenapi_types::PathHistoryResponse;
 use edenapi_types::PushVar;
 use edenapi_types::ReferencesDataResponse;
 use edenapi_types::SaplingRemoteApiServerError;
@@ -116,6 +125,7 @@ use metrics::EntranceGuard;
 use minibytes::Bytes as RawBytes;
 use minibytes::Bytes;
 use parking_lot::Once;
+use parking_lot::RwLock;
 use progress_model::AggregatingProgressBar;
 use progress_model::ProgressBar;
 use repo_name::encode_repo_name;
@@ -123,13 +133,17 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use types::HgId;
 use types::Key;
+use types::RepoPathBuf;
 use url::Url;
 
+use crate::adaptive_batch::AdaptiveBatcher;
 use crate::api::SaplingRemoteApi;
 use crate::builder::Config;
 use crate::errors::SaplingRemoteApiError;
 use crate::response::Response;
 use crate::response::ResponseMeta;
+use crate::endpoint_metrics::EndpointMetrics;
+use crate::endpoint_metrics::EndpointMetricsSnapshot;
 use crate::retryable::RetryableFileAttrs;
 use crate::retryable::RetryableStreamRequest;
 use crate::retryable::RetryableTrees;
@@ -146,6 +160,11 @@ const MAX_ERROR_MSG_LEN: usize = 500;
 
 static REQUESTS_INFLIGHT: Counter = Counter::new_counter("edenapi.req_inflight");
 static FILES_ATTRS_INFLIGHT: Counter = Counter::new_counter("edenapi.files_attrs_inflight");
+/// Number of file contents skipped by `process_files_upload` because the server already
+/// had them, across the lifetime of the process.
+static UPLOAD_DEDUP_FILES_SKIPPED: Counter = Counter::new_counter("edenapi.upload_dedup_files");
+/// Bytes of file content not sent over the wire as a result of the same dedup.
+static UPLOAD_DEDUP_BYTES_SKIPPED: Counter = Counter::new_counter("edenapi.upload_dedup_bytes");
 
 mod paths {
     pub const HEALTH_CHECK: &str = "health_check";
@@ -185,6 +204,8 @@ mod paths {
     pub const CLOUD_SMARTLOG: &str = "cloud/smartlog";
     pub const CLOUD_SHARE_WORKSPACE: &str = "cloud/share_workspace";
     pub const SUFFIXQUERY: &str = "suffix_query";
+    pub const PATH_HISTORY: &str = "path_history";
+    pub const BOOKMARKS_SUBSCRIBE: &str = "bookmarks/subscribe";
 }
 
 #[derive(Clone)]
@@ -194,9 +215,52 @@ pub struct Client {
 
 pub struct ClientInner {
     config: Config,
-    client: HttpClient,
+    /// Wrapped in a lock (rather than rebuilding the `Client` outright) so that a cert renewed
+    /// on disk after startup can be picked up by [`Client::reload_tls_if_changed`] without
+    /// disturbing any other client state (batchers, queued mutations, metrics).
+    client: RwLock<HttpClient>,
+    /// mtimes of `http_config.{cert,key}_path` as of the last time `client` was built, used to
+    /// tell whether the files on disk have changed since. `None` if the corresponding path isn't
+    /// configured, or the file couldn't be stat'd.
+    tls_file_mtimes: Mutex<(Option<SystemTime>, Option<SystemTime>)>,
     tree_progress: Arc<AggregatingProgressBar>,
     file_progress: Arc<AggregatingProgressBar>,
+    tree_batcher: AdaptiveBatcher,
+    file_batcher: AdaptiveBatcher,
+    /// Mutations attempted while `config.offline` is set, queued here for later replay once
+    /// the client is back online. See [`Client::replay_queued_mutations`].
+    queued_mutations: Mutex<VecDeque<QueuedMutation>>,
+    /// Per-endpoint attempt/retry/latency metrics, keyed by endpoint label (e.g. "lookup",
+    /// "history"). Populated lazily as endpoints using [`Client::with_retry_for`] are called;
+    /// no byte counts are recorded.
+    endpoint_metrics: Mutex<HashMap<&'static str, Arc<EndpointMetrics>>>,
+}
+
+/// A mutation request that couldn't be sent because the client is in offline mode.
+enum QueuedMutation {
+    SetBookmark(SetBookmarkRequest),
+    LandStack(LandStackRequest),
+}
+
+/// Batch sizes used to seed/bound the adaptive batchers when `edenapi.adaptive-batching` is set,
+/// chosen to keep a single batch reasonably sized on both fast and slow links.
+const ADAPTIVE_BATCH_MIN: usize = 8;
+const ADAPTIVE_BATCH_MAX: usize = 2000;
+const ADAPTIVE_BATCH_INITIAL: usize = 100;
+
+#[derive(Clone, Copy)]
+enum AdaptiveBatchKind {
+    Trees,
+    Files,
+}
+
+impl AdaptiveBatchKind {
+    fn batcher(self, client: &Client) -> &AdaptiveBatcher {
+        match self {
+            AdaptiveBatchKind::Trees => &client.inner.tree_batcher,
+            AdaptiveBatchKind::Files => &client.inner.file_batcher,
+        }
+    }
 }
 
 static LOG_SERVER_INFO_ONCE: Once = Once::new();
@@ -205,19 +269,189 @@ impl Client {
     /// Create an SaplingRemoteAPI client with the given configuration.
     pub(crate) fn with_config(config: Config) -> Self {
         let client = http_client("edenapi", config.http_config.clone());
+        let tls_file_mtimes = Mutex::new((
+            mtime_of(config.http_config.cert_path.as_deref()),
+            mtime_of(config.http_config.key_path.as_deref()),
+        ));
         let inner = Arc::new(ClientInner {
             config,
-            client,
+            client: RwLock::new(client),
+            tls_file_mtimes,
             tree_progress: AggregatingProgressBar::new("fetching", "trees"),
             file_progress: AggregatingProgressBar::new("fetching", "files"),
+            tree_batcher: AdaptiveBatcher::new(
+                ADAPTIVE_BATCH_INITIAL,
+                ADAPTIVE_BATCH_MIN,
+                ADAPTIVE_BATCH_MAX,
+            ),
+            file_batcher: AdaptiveBatcher::new(
+                ADAPTIVE_BATCH_INITIAL,
+                ADAPTIVE_BATCH_MIN,
+                ADAPTIVE_BATCH_MAX,
+            ),
+            queued_mutations: Mutex::new(VecDeque::new()),
+            endpoint_metrics: Mutex::new(HashMap::new()),
         });
         Self { inner }
     }
 
+    /// The current `HttpClient`. Cheap to call: `HttpClient` is just a connection pool handle,
+    /// so this is a lock + clone, not a rebuild. See [`Client::reload_tls_if_changed`] for when
+    /// the underlying client actually gets swapped out.
+    fn http_client(&self) -> HttpClient {
+        self.inner.client.read().clone()
+    }
+
+    /// If the configured TLS client cert or key file has a newer mtime than the one the current
+    /// `HttpClient` was built with, rebuild the client so new connections pick up the renewed
+    /// credentials. Connections already in the pool are unaffected, but curl re-resolves the
+    /// cert/key path on every new connection anyway, so this mostly matters for forcing a fresh
+    /// connection (rather than reusing a keep-alive one established with a now-stale cert) right
+    /// after a renewal.
+    ///
+    /// Cheap to call when nothing has changed (a couple of `stat`s), so callers trigger it
+    /// opportunistically whenever a request comes back with a cert-shaped error rather than
+    /// polling on a timer.
+    fn reload_tls_if_changed(&self) {
+        let http_config = &self.config().http_config;
+        let cert_mtime = mtime_of(http_config.cert_path.as_deref());
+        let key_mtime = mtime_of(http_config.key_path.as_deref());
+
+        let mut last_seen = self.inner.tls_file_mtimes.lock().unwrap();
+        if (cert_mtime, key_mtime) == *last_seen {
+            return;
+        }
+        tracing::info!("TLS client cert or key changed on disk, rebuilding HTTP client");
+        *self.inner.client.write() = http_client("edenapi", http_config.clone());
+        *last_seen = (cert_mtime, key_mtime);
+    }
+
+    fn metrics_for(&self, endpoint: &'static str) -> Arc<EndpointMetrics> {
+        self.inner
+            .endpoint_metrics
+            .lock()
+            .unwrap()
+            .entry(endpoint)
+            .or_insert_with(|| Arc::new(EndpointMetrics::default()))
+            .clone()
+    }
+
+    /// Structured metrics (attempts, retries, latency percentiles) observed so far for the
+    /// given endpoint label, or `None` if nothing has been recorded for it. The client records
+    /// no transferred bytes, so the snapshot's `bytes` is always 0.
+    pub fn endpoint_metrics(&self, endpoint: &str) -> Option<EndpointMetricsSnapshot> {
+        self.inner
+            .endpoint_metrics
+            .lock()
+            .unwrap()
+            .get(endpoint)
+            .map(|m| m.snapshot())
+    }
+
+    /// Like [`Client::with_retry`], but records per-endpoint attempt/retry/latency metrics and
+    /// allows `edenapi.maxretries-<endpoint>` to override the global `max-retry-per-request`.
+    async fn with_retry_for<'t, T>(
+        &'t self,
+        endpoint: &'static str,
+        func: impl Fn(&'t Self) -> BoxFuture<'t, Result<T, SaplingRemoteApiError>>,
+    ) -> Result<T, SaplingRemoteApiError> {
+        let max_retry_count = self
+            .config()
+            .retry_overrides
+            .get(endpoint)
+            .copied()
+            .unwrap_or(self.config().max_retry_per_request);
+        let metrics = self.metrics_for(endpoint);
+
+        let mut attempt = 0usize;
+        loop {
+            metrics.record_attempt();
+            let start = std::time::Instant::now();
+            let result = func(self).await;
+            metrics.record_latency(start.elapsed());
+
+            if let Err(ref error) = result {
+                if error.cert_error_kind().is_some() {
+                    self.reload_tls_if_changed();
+                }
+            }
+
+            if attempt >= max_retry_count {
+                return result;
+            }
+            match result {
+                Ok(result) => return Ok(result),
+                Err(ref error) => match error.retry_after(attempt, max_retry_count) {
+                    Some(sleep_time) => {
+                        tracing::warn!("Retrying http error {:?}", error);
+                        metrics.record_retry();
+                        tokio::time::sleep(sleep_time).await;
+                    }
+                    None => return result,
+                },
+            }
+            attempt += 1;
+        }
+    }
+
     pub(crate) fn config(&self) -> &Config {
         &self.inner.config
     }
 
+    /// If offline mode is enabled, fail fast instead of attempting a network round-trip for a
+    /// read request. `what` is a short description of the request, used in the error message.
+    fn fail_fast_if_offline(&self, what: &str) -> Result<(), SaplingRemoteApiError> {
+        if self.config().offline {
+            Err(SaplingRemoteApiError::Offline(what.to_string()))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Number of mutation requests queued while offline, awaiting replay.
+    pub fn queued_mutation_count(&self) -> usize {
+        self.inner.queued_mutations.lock().unwrap().len()
+    }
+
+    /// Replay mutations that were queued while the client was offline, in the order they were
+    /// originally requested. Queued items are removed as soon as they're handed off to their
+    /// retry-wrapped attempt, so a request that fails part-way through leaves the rest queued.
+    pub async fn replay_queued_mutations(&self) -> Result<(), SaplingRemoteApiError> {
+        loop {
+            let next = self.inner.queued_mutations.lock().unwrap().pop_front();
+            let mutation = match next {
+                Some(mutation) => mutation,
+                None => return Ok(()),
+            };
+            match mutation {
+                QueuedMutation::SetBookmark(req) => {
+                    self.with_retry(|this| {
+                        this.set_bookmark_attempt(req.bookmark.clone(), req.to, req.from, {
+                            req.pushvars
+                                .iter()
+                                .map(|p| (p.key.clone(), p.value.clone()))
+                                .collect()
+                        })
+                        .boxed()
+                    })
+                    .await?;
+                }
+                QueuedMutation::LandStack(req) => {
+                    self.with_retry(|this| {
+                        this.land_stack_attempt(req.bookmark.clone(), req.head, req.base, {
+                            req.pushvars
+                                .iter()
+                                .map(|p| (p.key.clone(), p.value.clone()))
+                                .collect()
+                        })
+                        .boxed()
+                    })
+                    .await?;
+                }
+            }
+        }
+    }
+
     fn repo_name(&self) -> &str {
         &self.config().repo_name
     }
@@ -248,6 +482,18 @@ impl Client {
 
         let config = self.config();
 
+        // curl treats a missing cert/key file as just another `CURLE_SSL_CERTPROBLEM`, which
+        // `cert_error_kind` can't reliably tell apart from a rejected-but-present cert. Since
+        // we can check this cheaply ourselves, do so up front for a precise error.
+        for path in [&config.http_config.cert_path, &config.http_config.key_path]
+            .into_iter()
+            .flatten()
+        {
+            if !path.exists() {
+                return Err(SaplingRemoteApiError::Cert(CertErrorKind::Missing));
+            }
+        }
+
         for (k, v) in &config.headers {
             req.set_header(k, v);
         }
@@ -277,6 +523,56 @@ impl Client {
         Ok(req)
     }
 
+    /// Like [`Client::configure_request`], but lets `edenapi.encoding-<endpoint>` override the
+    /// global `edenapi.encoding` for this request's content-encoding negotiation. Different
+    /// endpoints compress very differently (e.g. tree data vs. small JSON-ish metadata), so a
+    /// single global choice is often wrong for at least one of them.
+    fn configure_request_for(
+        &self,
+        req: Request,
+        endpoint: &str,
+    ) -> Result<Request, SaplingRemoteApiError> {
+        let mut req = self.configure_request(req)?;
+        if let Some(encoding) = self.config().encoding_overrides.get(endpoint) {
+            req.set_accept_encoding([encoding.clone()]);
+        }
+        Ok(req)
+    }
+
+    /// Feeds the outcome of a batched fetch back into the given adaptive batcher: an immediate
+    /// error shrinks future batches right away, otherwise the returned response's `stats` future
+    /// is wrapped so that, once the caller awaits it (after the transfer actually completes), the
+    /// batcher observes the real latency/error and adjusts the batch size accordingly.
+    fn with_adaptive_batch_feedback<T: Send + 'static>(
+        &self,
+        result: Result<Response<T>, SaplingRemoteApiError>,
+        kind: AdaptiveBatchKind,
+    ) -> Result<Response<T>, SaplingRemoteApiError> {
+        let response = match result {
+            Err(e) => {
+                kind.batcher(self).record_error();
+                return Err(e);
+            }
+            Ok(response) => response,
+        };
+
+        let this = self.clone();
+        let stats = response.stats;
+        let wrapped_stats: StatsFuture = Box::pin(async move {
+            let result = stats.await;
+            match &result {
+                Ok(stats) => kind.batcher(&this).record_latency(stats.time),
+                Err(_) => kind.batcher(&this).record_error(),
+            }
+            result
+        });
+
+        Ok(Response {
+            entries: response.entries,
+            stats: wrapped_stats,
+        })
+    }
+
     /// Prepare a collection of POST requests for the given keys.
     /// The keys will be grouped into batches of the specified size and
     /// passed to the `make_req` callback, which should insert them into
@@ -284,6 +580,7 @@ impl Client {
     fn prepare_requests<T, K, F, R, G>(
         &self,
         url: &Url,
+        endpoint: &str,
         keys: K,
         batch_size: Option<usize>,
         min_batch_size: Option<usize>,
@@ -301,7 +598,7 @@ impl Client {
             .map(|keys| {
                 let url = mutate_url(url, &keys);
                 let req = make_req(keys).to_wire();
-                self.configure_request(self.inner.client.post(url))?
+                self.configure_request_for(self.http_client().post(url), endpoint)?
                     .cbor(&req)
                     .map_err(SaplingRemoteApiError::RequestSerializationFailed)
             })
@@ -320,7 +617,7 @@ impl Client {
         &self,
         requests: Vec<Request>,
     ) -> Result<Response<T>, SaplingRemoteApiError> {
-        let (responses, stats) = self.inner.client.send_async(requests)?;
+        let (responses, stats) = self.http_client().send_async(requests)?;
 
         // Transform each response `Future` (which resolves when all of the HTTP
         // headers for that response have been received) into a `Stream` that
@@ -412,6 +709,21 @@ impl Client {
             .await
     }
 
+    /// Like [`Client::fetch_vec_with_retry`], but records per-endpoint metrics and allows a
+    /// `edenapi.maxretries-<endpoint>` override.
+    async fn fetch_vec_with_retry_for<T>(
+        &self,
+        endpoint: &'static str,
+        requests: Vec<Request>,
+    ) -> Result<Vec<T>, SaplingRemoteApiError>
+    where
+        <T as ToWire>::Wire: Send + DeserializeOwned + 'static,
+        T: ToWire + Send + 'static,
+    {
+        self.with_retry_for(endpoint, |this| this.fetch_vec::<T>(requests.clone()).boxed())
+            .await
+    }
+
     /// Similar to `fetch_vec`. But with retries and a custom progress bar (position drops on a retry).
     async fn fetch_vec_with_retry_and_prog<T>(
         &self,
@@ -487,6 +799,8 @@ impl Client {
             return Ok(Response::empty());
         }
 
+        self.fail_fast_if_offline("trees")?;
+
         let url = self.build_url(paths::TREES)?;
 
         let mut attrs = attributes.clone().unwrap_or_default();
@@ -500,11 +814,17 @@ impl Client {
 
         let try_route_consistently = self.config().try_route_consistently;
         let min_batch_size: Option<usize> = self.config().min_batch_size;
+        let batch_size = if self.config().adaptive_batching {
+            Some(self.inner.tree_batcher.batch_size())
+        } else {
+            self.config().max_trees_per_batch
+        };
 
         let requests = self.prepare_requests(
             &url,
+            "trees",
             keys,
-            self.config().max_trees_per_batch,
+            batch_size,
             min_batch_size,
             |keys| {
                 let req = TreeRequest {
@@ -523,7 +843,12 @@ impl Client {
             },
         )?;
 
-        self.fetch::<Result<TreeEntry, SaplingRemoteApiServerError>>(requests)
+        let result = self.fetch::<Result<TreeEntry, SaplingRemoteApiServerError>>(requests);
+        if self.config().adaptive_batching {
+            self.with_adaptive_batch_feedback(result, AdaptiveBatchKind::Trees)
+        } else {
+            result
+        }
     }
 
     pub(crate) async fn fetch_files_attrs(
@@ -539,16 +864,24 @@ impl Client {
             return Ok(Response::empty());
         }
 
+        self.fail_fast_if_offline("files")?;
+
         let guards = vec![FILES_ATTRS_INFLIGHT.entrance_guard(reqs.len())];
 
         let url = self.build_url(paths::FILES2)?;
         let try_route_consistently = self.config().try_route_consistently;
         let min_batch_size: Option<usize> = self.config().min_batch_size;
+        let batch_size = if self.config().adaptive_batching {
+            Some(self.inner.file_batcher.batch_size())
+        } else {
+            self.config().max_files_per_batch
+        };
 
         let requests = self.prepare_requests(
             &url,
+            "files",
             reqs,
-            self.config().max_files_per_batch,
+            batch_size,
             min_batch_size,
             |reqs| {
                 let req = FileRequest { reqs };
@@ -567,7 +900,12 @@ impl Client {
             },
         )?;
 
-        self.fetch_guard::<FileResponse>(requests, guards)
+        let result = self.fetch_guard::<FileResponse>(requests, guards);
+        if self.config().adaptive_batching {
+            self.with_adaptive_batch_feedback(result, AdaptiveBatchKind::Files)
+        } else {
+            result
+        }
     }
 
     /// Upload a single file
@@ -606,7 +944,7 @@ impl Client {
         tracing::info!("{}", &msg);
 
         self.fetch_single::<UploadToken>({
-            self.configure_request(self.inner.client.put(url.clone()))?
+            self.configure_request(self.http_client().put(url.clone()))?
                 .body(raw_content.to_vec())
         })
         .await
@@ -637,7 +975,7 @@ impl Client {
         // Currently, server sends the "upload_changesets" response once it is fully completed,
         // disable min speed transfer check to avoid premature termination of requests.
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .min_transfer_speed(None)
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
@@ -657,7 +995,7 @@ impl Client {
         self.log_request(&commit_revlog_data_req, "commit_revlog_data");
 
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&commit_revlog_data_req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -679,7 +1017,7 @@ impl Client {
         let req = UploadBonsaiChangesetRequest { changeset }.to_wire();
 
         let request = self
-            .configure_request(self.inner.client.post(url.clone()))?
+            .configure_request(self.http_client().post(url.clone()))?
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -699,7 +1037,7 @@ impl Client {
         }
         .to_wire();
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -718,7 +1056,7 @@ impl Client {
         let url = self.build_url(paths::FETCH_SNAPSHOT)?;
         let req = request.to_wire();
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -734,7 +1072,7 @@ impl Client {
         let url = self.build_url(paths::ALTER_SNAPSHOT)?;
         let req = request.to_wire();
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -743,7 +1081,7 @@ impl Client {
 
     async fn clone_data_attempt(&self) -> Result<CloneData<HgId>, SaplingRemoteApiError> {
         let url = self.build_url(paths::CLONE_DATA)?;
-        let req = self.configure_request(self.inner.client.post(url))?;
+        let req = self.configure_request(self.http_client().post(url))?;
         self.fetch_single::<CloneData<HgId>>(req).await
     }
 
@@ -753,7 +1091,7 @@ impl Client {
     ) -> Result<CloneData<HgId>, SaplingRemoteApiError> {
         let url = self.build_url(paths::PULL_LAZY)?;
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
         self.fetch_single::<CloneData<HgId>>(req).await
@@ -765,7 +1103,7 @@ impl Client {
     ) -> Result<CloneData<HgId>, SaplingRemoteApiError> {
         let url = self.build_url(paths::PULL_FAST_FORWARD)?;
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
         self.fetch_single::<CloneData<HgId>>(req).await
@@ -782,6 +1120,8 @@ impl Client {
             return Ok(Response::empty());
         }
 
+        self.fail_fast_if_offline("history")?;
+
         let url = self.build_url(paths::HISTORY)?;
 
         let try_route_consistently = self.config().try_route_consistently;
@@ -789,6 +1129,7 @@ impl Client {
 
         let requests = self.prepare_requests(
             &url,
+            "history",
             keys,
             self.config().max_history_per_batch,
             min_batch_size,
@@ -830,6 +1171,7 @@ impl Client {
         let url = self.build_url(paths::BLAME)?;
         let requests = self.prepare_requests(
             &url,
+            "blame",
             files,
             Some(MAX_CONCURRENT_BLAMES_PER_REQUEST),
             None,
@@ -868,13 +1210,40 @@ impl Client {
         };
 
         let requests = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
         self.fetch::<SuffixQueryResponse>(vec![requests])
     }
 
+    async fn path_history_attempt(
+        &self,
+        commit: CommitId,
+        path: RepoPathBuf,
+        limit: Option<u32>,
+        cursor: Option<HgId>,
+    ) -> Result<PathHistoryResponse, SaplingRemoteApiError> {
+        tracing::info!("Requesting history of {} at {}", path, commit);
+
+        self.fail_fast_if_offline("path_history")?;
+
+        let url = self.build_url(paths::PATH_HISTORY)?;
+        let req = PathHistoryRequest {
+            path,
+            commit,
+            limit,
+            cursor,
+        };
+
+        let request = self
+            .configure_request(self.http_client().post(url))?
+            .cbor(&req.to_wire())
+            .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
+
+        self.fetch_single::<PathHistoryResponse>(request).await
+    }
+
     async fn commit_translate_id_attempt(
         &self,
         commits: Vec<CommitId>,
@@ -890,6 +1259,7 @@ impl Client {
         let url = self.build_url(paths::COMMIT_TRANSLATE_ID)?;
         let requests = self.prepare_requests(
             &url,
+            "commit_translate_id",
             commits,
             self.config().max_commit_translate_id_per_batch,
             None,
@@ -908,6 +1278,14 @@ impl Client {
         self.fetch::<CommitTranslateIdResponse>(requests)
     }
 
+    /// Fetches the full bytes of `token`'s content. The server always returns the whole file
+    /// from the start (it does not honor `Range`), so a retried download must discard whatever
+    /// was previously buffered and re-fetch from scratch rather than appending to it.
+    ///
+    /// Resumable, partial-range downloads (picking back up mid-file after a disconnect) were
+    /// attempted here and reverted: doing that safely needs `edenapi_service` to actually honor
+    /// `Range` on this endpoint, which it does not today. Resumable download is considered
+    /// infeasible until that server-side support exists, not merely deferred.
     async fn download_file_attempt(
         &self,
         token: UploadToken,
@@ -917,7 +1295,7 @@ impl Client {
         let metadata = token.data.metadata.clone();
         let req = token.to_wire();
         let request = self
-            .configure_request(self.inner.client.post(url.clone()))?
+            .configure_request(self.http_client().post(url.clone()))?
             .cbor(&req)
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -946,7 +1324,6 @@ impl Client {
         pushvars: HashMap<String, String>,
     ) -> Result<SetBookmarkResponse, SaplingRemoteApiError> {
         tracing::info!("Set bookmark '{}' from {:?} to {:?}", &bookmark, from, to);
-        let url = self.build_url(paths::SET_BOOKMARK)?;
         let set_bookmark_req = SetBookmarkRequest {
             bookmark,
             to,
@@ -956,9 +1333,24 @@ impl Client {
                 .map(|(k, v)| PushVar { key: k, value: v })
                 .collect(),
         };
+
+        if self.config().offline {
+            let bookmark = set_bookmark_req.bookmark.clone();
+            self.inner
+                .queued_mutations
+                .lock()
+                .unwrap()
+                .push_back(QueuedMutation::SetBookmark(set_bookmark_req));
+            return Err(SaplingRemoteApiError::Offline(format!(
+                "set_bookmark({})",
+                bookmark
+            )));
+        }
+
+        let url = self.build_url(paths::SET_BOOKMARK)?;
         self.log_request(&set_bookmark_req, "set_bookmark");
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .min_transfer_speed(None)
             .cbor(&set_bookmark_req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
@@ -981,8 +1373,6 @@ impl Client {
             base,
             &bookmark
         );
-        let url = self.build_url(paths::LAND_STACK)?;
-
         let land_stack_req = LandStackRequest {
             bookmark,
             head,
@@ -992,17 +1382,45 @@ impl Client {
                 .map(|(k, v)| PushVar { key: k, value: v })
                 .collect(),
         };
+
+        if self.config().offline {
+            let bookmark = land_stack_req.bookmark.clone();
+            self.inner
+                .queued_mutations
+                .lock()
+                .unwrap()
+                .push_back(QueuedMutation::LandStack(land_stack_req));
+            return Err(SaplingRemoteApiError::Offline(format!(
+                "land_stack({})",
+                bookmark
+            )));
+        }
+
+        let url = self.build_url(paths::LAND_STACK)?;
         self.log_request(&land_stack_req, "land");
 
-        // Currently, server sends the land_stack response once it is fully completed,
-        // disable min speed transfer check to avoid premature termination of requests.
+        // The server streams zero or more `LandStackProgress` items (e.g. validating, running
+        // hooks) ahead of the final `LandStackResponse`, so disable the min
+        // speed transfer check to avoid premature termination while a land is in flight.
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .min_transfer_speed(None)
             .cbor(&land_stack_req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
-        self.fetch_single::<LandStackResponse>(req).await
+        let mut entries = self.fetch::<LandStackResponseItem>(vec![req])?.entries;
+        while let Some(item) = entries.try_next().await? {
+            match item {
+                LandStackResponseItem::Progress(progress) => {
+                    tracing::debug!("land_stack progress: {:?}", progress);
+                }
+                LandStackResponseItem::Done(response) => return Ok(response),
+            }
+        }
+
+        Err(SaplingRemoteApiError::Other(format_err!(
+            "server closed the land_stack response stream without a final result"
+        )))
     }
 
     async fn upload_filenodes_batch_attempt(
@@ -1018,6 +1436,7 @@ impl Client {
         let url = self.build_url(paths::UPLOAD_FILENODES)?;
         let requests = self.prepare_requests(
             &url,
+            "upload_filenodes",
             items,
             Some(MAX_CONCURRENT_UPLOAD_FILENODES_PER_REQUEST),
             None,
@@ -1045,6 +1464,7 @@ impl Client {
         let url = self.build_url(paths::UPLOAD_TREES)?;
         let requests = self.prepare_requests(
             &url,
+            "upload_trees",
             items,
             Some(MAX_CONCURRENT_UPLOAD_TREES_PER_REQUEST),
             None,
@@ -1065,7 +1485,20 @@ impl Client {
         func: impl Fn(&'t Self) -> BoxFuture<'t, Result<T, SaplingRemoteApiError>>,
     ) -> Result<T, SaplingRemoteApiError> {
         let retry_count = self.inner.config.max_retry_per_request;
-        with_retry(retry_count, || func(self)).await
+        with_retry(retry_count, || {
+            let fut = func(self);
+            async move {
+                let result = fut.await;
+                if let Err(ref error) = result {
+                    if error.cert_error_kind().is_some() {
+                        self.reload_tls_if_changed();
+                    }
+                }
+                result
+            }
+            .boxed()
+        })
+        .await
     }
 
     async fn cloud_workspace_attempt(
@@ -1080,7 +1513,7 @@ impl Client {
             reponame: reponame.to_string(),
         };
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&workspace_req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1103,7 +1536,7 @@ impl Client {
             reponame: reponame.to_string(),
         };
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&workspace_req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1121,7 +1554,7 @@ impl Client {
         );
         let url = self.build_url(paths::CLOUD_REFERENCES)?;
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&data.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1139,7 +1572,7 @@ impl Client {
         );
         let url = self.build_url(paths::CLOUD_UPDATE_REFERENCES)?;
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&data.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1157,7 +1590,7 @@ impl Client {
         );
         let url = self.build_url(paths::CLOUD_SMARTLOG)?;
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&data.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1175,7 +1608,7 @@ impl Client {
         );
         let url = self.build_url(paths::CLOUD_SHARE_WORKSPACE)?;
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&data.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1194,7 +1627,7 @@ impl Client {
         );
         let url = self.build_url(paths::CLOUD_UPDATE_ARCHIVE)?;
         let request = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&data.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
@@ -1215,7 +1648,7 @@ impl SaplingRemoteApi for Client {
 
                 tracing::info!("Sending health check request: {}", &url);
 
-                let req = client.configure_request(client.inner.client.get(url))?;
+                let req = client.configure_request(client.http_client().get(url))?;
                 let res = raise_for_status(req.send_async().await?).await?;
 
                 Ok(ResponseMeta::from(&res))
@@ -1230,7 +1663,7 @@ impl SaplingRemoteApi for Client {
             async {
                 tracing::info!("Requesting capabilities for repo {}", &client.repo_name());
                 let url = client.build_url("capabilities")?;
-                let req = client.configure_request(client.inner.client.get(url))?;
+                let req = client.configure_request(client.http_client().get(url))?;
                 let res = raise_for_status(req.send_async().await?).await?;
                 let body: Vec<u8> = res.into_body().decoded().try_concat().await?;
                 let caps = serde_json::from_slice(&body)
@@ -1269,7 +1702,9 @@ impl SaplingRemoteApi for Client {
         keys: Vec<Key>,
         length: Option<u32>,
     ) -> Result<Response<HistoryEntry>, SaplingRemoteApiError> {
-        self.with_retry(|this| this.history_attempt(keys.clone(), length.clone()).boxed())
+        self.with_retry_for("history", |this| {
+            this.history_attempt(keys.clone(), length.clone()).boxed()
+        })
             .await
     }
 
@@ -1314,6 +1749,7 @@ impl SaplingRemoteApi for Client {
             .collect::<Result<Vec<CommitHashLookupRequest>, _>>()?;
         let requests = self.prepare_requests(
             &url,
+            "commit_hash_lookup",
             prefixes,
             Some(MAX_CONCURRENT_HASH_LOOKUPS_PER_REQUEST),
             None,
@@ -1333,13 +1769,41 @@ impl SaplingRemoteApi for Client {
         let bookmark_req = BookmarkRequest { bookmarks };
         self.log_request(&bookmark_req, "bookmarks");
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .cbor(&bookmark_req.to_wire())
             .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
 
         self.fetch_vec_with_retry::<BookmarkEntry>(vec![req]).await
     }
 
+    async fn bookmarks_subscribe(
+        &self,
+        bookmarks: Vec<BookmarkEntry>,
+        timeout_ms: Option<u64>,
+    ) -> Result<Vec<BookmarkEntry>, SaplingRemoteApiError> {
+        tracing::info!("Long-polling for changes to {} bookmarks", bookmarks.len());
+        let url = self.build_url(paths::BOOKMARKS_SUBSCRIBE)?;
+        let subscribe_req = BookmarksSubscribeRequest {
+            bookmarks,
+            timeout_ms,
+        };
+        self.log_request(&subscribe_req, "bookmarks_subscribe");
+        let mut req = self
+            .configure_request(self.http_client().post(url))?
+            .cbor(&subscribe_req.to_wire())
+            .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
+
+        // Give the connection enough slack to outlive the server's long-poll wait, or
+        // else the client's own timeout can fire while the server is still blocked
+        // waiting for a change.
+        if let Some(timeout_ms) = timeout_ms {
+            req.set_timeout(Duration::from_millis(timeout_ms) + Duration::from_secs(30));
+        }
+
+        self.fetch_vec_with_retry_for::<BookmarkEntry>("bookmarks_subscribe", vec![req])
+            .await
+    }
+
     async fn set_bookmark(
         &self,
         bookmark: String,
@@ -1433,6 +1897,7 @@ impl SaplingRemoteApi for Client {
 
         let formatted = self.prepare_requests(
             &url,
+            "commit_location_to_hash",
             requests,
             self.config().max_location_to_hash_per_batch,
             None,
@@ -1466,6 +1931,7 @@ impl SaplingRemoteApi for Client {
 
         let formatted = self.prepare_requests(
             &url,
+            "commit_hash_to_location",
             hgids,
             self.config().max_location_to_hash_per_batch,
             None,
@@ -1545,7 +2011,7 @@ impl SaplingRemoteApi for Client {
         // Since we have a special progress bar and response is small, let's disable compression of
         // response's body.
         let req = self
-            .configure_request(self.inner.client.post(url))?
+            .configure_request(self.http_client().post(url))?
             .accept_encoding([Encoding::Identity])
             .min_transfer_speed(None)
             .cbor(&wire_graph_req)
@@ -1567,17 +2033,25 @@ impl SaplingRemoteApi for Client {
             common.len(),
         );
         let url = self.build_url(paths::COMMIT_GRAPH_SEGMENTS)?;
-        let graph_req = CommitGraphSegmentsRequest { heads, common };
-        self.log_request(&graph_req, "commit_graph_segments");
-        let wire_graph_req = graph_req.to_wire();
 
-        let req = self
-            .configure_request(self.inner.client.post(url))?
-            .min_transfer_speed(None)
-            .cbor(&wire_graph_req)
-            .map_err(SaplingRemoteApiError::RequestSerializationFailed)?;
+        // Lazy clones can pass a huge number of heads when pulling a large number of bookmarks
+        // incrementally; keep a single request from growing unbounded by chunking the heads.
+        let requests = split_into_batches(heads, self.config().max_commit_graph_segments_per_batch, None)
+            .into_iter()
+            .map(|heads| {
+                let graph_req = CommitGraphSegmentsRequest {
+                    heads,
+                    common: common.clone(),
+                };
+                self.log_request(&graph_req, "commit_graph_segments");
+                self.configure_request(self.http_client().post(url.clone()))?
+                    .min_transfer_speed(None)
+                    .cbor(&graph_req.to_wire())
+                    .map_err(SaplingRemoteApiError::RequestSerializationFailed)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
-        self.fetch_vec_with_retry::<CommitGraphSegmentsEntry>(vec![req])
+        self.fetch_vec_with_retry::<CommitGraphSegmentsEntry>(requests)
             .await
     }
 
@@ -1596,6 +2070,7 @@ impl SaplingRemoteApi for Client {
         let url = self.build_url(paths::LOOKUP)?;
         let requests = self.prepare_requests(
             &url,
+            "lookup",
             items,
             Some(MAX_CONCURRENT_LOOKUPS_PER_REQUEST),
             None,
@@ -1612,7 +2087,8 @@ impl SaplingRemoteApi for Client {
             |url, _keys| url.clone(),
         )?;
 
-        self.fetch_vec_with_retry::<LookupResponse>(requests).await
+        self.fetch_vec_with_retry_for::<LookupResponse>("lookup", requests)
+            .await
     }
 
     async fn process_files_upload(
@@ -1650,6 +2126,19 @@ impl SaplingRemoteApi for Client {
         );
         tracing::info!("{}", &msg);
 
+        let skipped_bytes: usize = data
+            .iter()
+            .filter(|(id, _content)| {
+                uploaded_ids.contains(&IndexableId {
+                    id: AnyId::AnyFileContentId(id.clone()),
+                    bubble_id,
+                })
+            })
+            .map(|(_id, content)| content.len())
+            .sum();
+        UPLOAD_DEDUP_FILES_SKIPPED.add(uploaded_tokens.len());
+        UPLOAD_DEDUP_BYTES_SKIPPED.add(skipped_bytes);
+
         // Upload the rest of the contents in parallel
         let new_tokens = stream::iter(
             data.into_iter()
@@ -1771,6 +2260,7 @@ impl SaplingRemoteApi for Client {
         let url = self.build_url(paths::COMMIT_MUTATIONS)?;
         let requests = self.prepare_requests(
             &url,
+            "commit_mutations",
             commits,
             self.config().max_commit_mutations_per_batch,
             None,
@@ -1887,6 +2377,27 @@ impl SaplingRemoteApi for Client {
         })
         .await
     }
+
+    async fn path_history(
+        &self,
+        commit: CommitId,
+        path: RepoPathBuf,
+        limit: Option<u32>,
+        cursor: Option<HgId>,
+    ) -> Result<PathHistoryResponse, SaplingRemoteApiError> {
+        self.with_retry(|this| {
+            this.path_history_attempt(commit.clone(), path.clone(), limit, cursor)
+                .boxed()
+        })
+        .await
+    }
+}
+
+/// Last-modified time of `path`, or `None` if there's no path to check or it can't be stat'd
+/// (e.g. the cert file is missing, which is itself reported separately via
+/// [`SaplingRemoteApiError::Cert`]).
+fn mtime_of(path: Option<&std::path::Path>) -> Option<SystemTime> {
+    std::fs::metadata(path?).and_then(|m| m.modified()).ok()
 }
 
 /// Split up a collection of keys into batches of at most `batch_size`.
@@ -1978,7 +2489,10 @@ async fn with_retry<'t, T>(
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
+    use edenapi_trait::SaplingRemoteApi;
+    use types::HgId;
 
+    use super::QueuedMutation;
     use crate::builder::HttpClientBuilder;
     use crate::client::split_into_batches;
 
@@ -2027,4 +2541,44 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_queued_mutations_replay_in_request_order() -> Result<()> {
+        let client = HttpClientBuilder::new()
+            .repo_name("repo")
+            .server_url("https://example.com".parse()?)
+            .offline(true)
+            .build()?;
+
+        // Each call fails fast (the client is offline) but still enqueues its request for
+        // later replay, in the order the calls were made.
+        client
+            .set_bookmark("first".to_string(), None, None, Default::default())
+            .await
+            .unwrap_err();
+        client
+            .land_stack(
+                "second".to_string(),
+                *HgId::null_id(),
+                *HgId::null_id(),
+                Default::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(client.queued_mutation_count(), 2);
+
+        // Draining the queue must yield "first" before "second": replay is supposed to
+        // preserve request order, not reverse it.
+        let mut bookmarks = Vec::new();
+        while let Some(mutation) = client.inner.queued_mutations.lock().unwrap().pop_front() {
+            bookmarks.push(match mutation {
+                QueuedMutation::SetBookmark(req) => req.bookmark,
+                QueuedMutation::LandStack(req) => req.bookmark,
+            });
+        }
+        assert_eq!(bookmarks, vec!["first".to_string(), "second".to_string()]);
+
+        Ok(())
+    }
 }
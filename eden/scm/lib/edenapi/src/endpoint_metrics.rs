@@ -0,0 +1,127 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+//! Structured per-endpoint metrics for the EdenAPI client: attempt/retry counts, bytes
+//! transferred, and a latency distribution good enough to read off approximate percentiles.
+//! A single global retry/timeout policy misbehaves for both tiny lookups and huge history
+//! fetches, so retry overrides are also configured per endpoint (see `Config::retry_overrides`).
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Number of recent latency samples kept per endpoint for percentile estimation.
+const MAX_LATENCY_SAMPLES: usize = 256;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct EndpointMetricsSnapshot {
+    pub attempts: usize,
+    pub retries: usize,
+    pub bytes: usize,
+    pub p50: Option<Duration>,
+    pub p90: Option<Duration>,
+    pub p99: Option<Duration>,
+}
+
+#[derive(Default)]
+pub(crate) struct EndpointMetrics {
+    attempts: AtomicUsize,
+    retries: AtomicUsize,
+    bytes: AtomicUsize,
+    latencies: Mutex<VecDeque<Duration>>,
+}
+
+impl EndpointMetrics {
+    pub(crate) fn record_attempt(&self) {
+        self.attempts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_retry(&self) {
+        self.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_bytes(&self, n: usize) {
+        self.bytes.fetch_add(n, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_latency(&self, latency: Duration) {
+        let mut latencies = self.latencies.lock().unwrap();
+        if latencies.len() == MAX_LATENCY_SAMPLES {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency);
+    }
+
+    /// Approximate percentile (0.0..=1.0) over the retained latency samples.
+    fn percentile(&self, p: f64) -> Option<Duration> {
+        let mut samples: Vec<Duration> = self.latencies.lock().unwrap().iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        // Nearest-rank: the smallest sample with at least `p` of all samples at or below it.
+        let idx = ((samples.len() as f64 * p).ceil() as usize).saturating_sub(1);
+        samples.get(idx).copied()
+    }
+
+    pub(crate) fn snapshot(&self) -> EndpointMetricsSnapshot {
+        EndpointMetricsSnapshot {
+            attempts: self.attempts.load(Ordering::Relaxed),
+            retries: self.retries.load(Ordering::Relaxed),
+            bytes: self.bytes.load(Ordering::Relaxed),
+            p50: self.percentile(0.50),
+            p90: self.percentile(0.90),
+            p99: self.percentile(0.99),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_attempts_and_retries() {
+        let metrics = EndpointMetrics::default();
+        metrics.record_attempt();
+        metrics.record_attempt();
+        metrics.record_retry();
+        let snap = metrics.snapshot();
+        assert_eq!(snap.attempts, 2);
+        assert_eq!(snap.retries, 1);
+    }
+
+    #[test]
+    fn test_bytes_accumulate() {
+        let metrics = EndpointMetrics::default();
+        metrics.record_bytes(100);
+        metrics.record_bytes(50);
+        assert_eq!(metrics.snapshot().bytes, 150);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let metrics = EndpointMetrics::default();
+        for ms in 1..=100 {
+            metrics.record_latency(Duration::from_millis(ms));
+        }
+        let snap = metrics.snapshot();
+        assert_eq!(snap.p50, Some(Duration::from_millis(50)));
+        assert_eq!(snap.p99, Some(Duration::from_millis(99)));
+    }
+
+    #[test]
+    fn test_latency_samples_bounded() {
+        let metrics = EndpointMetrics::default();
+        for ms in 0..(MAX_LATENCY_SAMPLES * 2) {
+            metrics.record_latency(Duration::from_millis(ms as u64));
+        }
+        assert_eq!(metrics.latencies.lock().unwrap().len(), MAX_LATENCY_SAMPLES);
+    }
+}
@@ -5,8 +5,10 @@
  * GNU General Public License version 2.
  */
 
+mod adaptive_batch;
 mod builder;
 mod client;
+mod endpoint_metrics;
 mod response;
 mod retryable;
 
@@ -24,6 +26,8 @@ pub use crate::api::SaplingRemoteApi;
 pub use crate::builder::Builder;
 pub use crate::builder::HttpClientBuilder;
 pub use crate::client::Client;
+pub use crate::endpoint_metrics::EndpointMetricsSnapshot;
+pub use crate::errors::CertErrorKind;
 pub use crate::errors::ConfigError;
 pub use crate::errors::SaplingRemoteApiError;
 pub use crate::response::BlockingResponse;
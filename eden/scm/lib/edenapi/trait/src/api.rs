@@ -36,6 +36,7 @@ use edenapi_types::CommitTranslateIdResponse;
 use edenapi_types::EphemeralPrepareResponse;
 use edenapi_types::FetchSnapshotRequest;
 use edenapi_types::FetchSnapshotResponse;
+use edenapi_types::FileAttributes;
 use edenapi_types::FileResponse;
 use edenapi_types::FileSpec;
 use edenapi_types::GetReferencesParams;
@@ -45,6 +46,7 @@ use edenapi_types::HgMutationEntryContent;
 use edenapi_types::HistoryEntry;
 use edenapi_types::LandStackResponse;
 use edenapi_types::LookupResponse;
+use edenapi_types::PathHistoryResponse;
 use edenapi_types::ReferencesDataResponse;
 use edenapi_types::SaplingRemoteApiServerError;
 use edenapi_types::SetBookmarkResponse;
@@ -64,6 +66,7 @@ use edenapi_types::WorkspacesDataResponse;
 use minibytes::Bytes;
 use types::HgId;
 use types::Key;
+use types::RepoPathBuf;
 
 use crate::errors::SaplingRemoteApiError;
 use crate::response::Response;
@@ -107,6 +110,26 @@ pub trait SaplingRemoteApi: Send + Sync + 'static {
         Err(SaplingRemoteApiError::NotSupported)
     }
 
+    /// Fetch just the aux data (size, content hashes) for a batch of files, without
+    /// downloading their content. Thin wrapper around [`files_attrs`] for callers that
+    /// only need metadata, e.g. to populate a size/hash index.
+    async fn file_aux_data(
+        &self,
+        keys: Vec<Key>,
+    ) -> Result<Response<FileResponse>, SaplingRemoteApiError> {
+        let reqs = keys
+            .into_iter()
+            .map(|key| FileSpec {
+                key,
+                attrs: FileAttributes {
+                    content: false,
+                    aux_data: true,
+                },
+            })
+            .collect();
+        self.files_attrs(reqs).await
+    }
+
     async fn trees(
         &self,
         keys: Vec<Key>,
@@ -223,6 +246,21 @@ pub trait SaplingRemoteApi: Send + Sync + 'static {
         Err(SaplingRemoteApiError::NotSupported)
     }
 
+    /// Long-poll for changes to a set of bookmarks. `bookmarks` carries the client's
+    /// last-known value for each bookmark it cares about (as returned by a previous call
+    /// to `bookmarks` or `bookmarks_subscribe`); the call returns as soon as at least one
+    /// no longer matches, or once `timeout_ms` elapses, in which case the result is empty.
+    /// Intended for automation (e.g. `pull --rebase`) that wants to notice bookmark
+    /// movement promptly without polling `bookmarks` aggressively.
+    async fn bookmarks_subscribe(
+        &self,
+        bookmarks: Vec<BookmarkEntry>,
+        timeout_ms: Option<u64>,
+    ) -> Result<Vec<BookmarkEntry>, SaplingRemoteApiError> {
+        let _ = (bookmarks, timeout_ms);
+        Err(SaplingRemoteApiError::NotSupported)
+    }
+
     /// Create, delete, or move a bookmark
     ///
     /// Both `from` and `to` can be None, but not both:
@@ -449,4 +487,18 @@ pub trait SaplingRemoteApi: Send + Sync + 'static {
         let _ = (commit, suffixes, prefixes);
         Err(SaplingRemoteApiError::NotSupported)
     }
+
+    /// Fetch a page of the commits that modified `path`, following renames using the
+    /// server's mutable rename data. Pass the `next` cursor from a response back in as
+    /// `cursor` to fetch the following page; `None` means there are no more pages.
+    async fn path_history(
+        &self,
+        commit: CommitId,
+        path: RepoPathBuf,
+        limit: Option<u32>,
+        cursor: Option<HgId>,
+    ) -> Result<PathHistoryResponse, SaplingRemoteApiError> {
+        let _ = (commit, path, limit, cursor);
+        Err(SaplingRemoteApiError::NotSupported)
+    }
 }
@@ -46,8 +46,27 @@ pub enum SaplingRemoteApiError {
     Other(#[from] anyhow::Error),
     #[error("not supported by the server")]
     NotSupported,
+    #[error("client is in offline mode; no cached data available for '{0}'")]
+    Offline(String),
     #[error(transparent)]
     MissingCerts(#[from] auth::MissingCerts),
+    #[error("TLS client certificate {0}")]
+    Cert(CertErrorKind),
+}
+
+/// Classifies a TLS client certificate problem so callers (and `eden doctor`) can tell a
+/// user "your cert expired, run `hg cloud auth`" apart from "the server doesn't trust your
+/// CA", instead of surfacing an opaque curl error code.
+#[derive(Debug, Error, Copy, Clone, Eq, PartialEq)]
+pub enum CertErrorKind {
+    #[error("is missing from disk")]
+    Missing,
+    #[error("has expired")]
+    Expired,
+    #[error("was rejected by the server")]
+    Rejected,
+    #[error("could not be validated")]
+    Unknown,
 }
 
 #[derive(Debug, Error)]
@@ -76,12 +95,49 @@ impl SaplingRemoteApiError {
         }
     }
 
+    /// If this error (or the `Http`/`Tls` error it wraps) indicates a problem with the
+    /// client's TLS certificate, classify it. Used both to decide whether retrying makes
+    /// sense and to give the user an actionable message instead of a raw curl error code.
+    pub fn cert_error_kind(&self) -> Option<CertErrorKind> {
+        use SaplingRemoteApiError::*;
+        match self {
+            Cert(kind) => Some(*kind),
+            Http(HttpClientError::Tls(TlsError { kind, source })) => {
+                use TlsErrorKind::*;
+                Some(match kind {
+                    CaCert | CaCertBadFile | CrlBadFile => CertErrorKind::Rejected,
+                    CertProblem | InvalidCertStatus | IssuerError => {
+                        // libcurl/OpenSSL don't give us a structured "expired" code; the
+                        // human-readable detail is the only place it shows up.
+                        let expired = source
+                            .extra_description()
+                            .is_some_and(|d| d.to_lowercase().contains("expired"));
+                        if expired {
+                            CertErrorKind::Expired
+                        } else {
+                            CertErrorKind::Rejected
+                        }
+                    }
+                    _ => return None,
+                })
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_retryable(&self) -> bool {
         use http_client::HttpClientError::*;
         use SaplingRemoteApiError::*;
         match self {
+            Cert(kind) => *kind != CertErrorKind::Rejected,
             Http(client_error) => match client_error {
-                Tls(TlsError { kind, .. }) => kind == &TlsErrorKind::RecvError,
+                Tls(TlsError { kind, .. }) => {
+                    kind == &TlsErrorKind::RecvError
+                        || matches!(
+                            self.cert_error_kind(),
+                            Some(CertErrorKind::Expired) | Some(CertErrorKind::Missing)
+                        )
+                }
                 _ => true,
             },
             HttpError { status, .. } => {
@@ -137,7 +193,9 @@ impl SaplingRemoteApiError {
             | InvalidUrl(_)
             | WireToApiConversionFailed(_)
             | NotSupported
-            | MissingCerts(_) => false,
+            | MissingCerts(_)
+            | Cert(_)
+            | Offline(_) => false,
         }
     }
 
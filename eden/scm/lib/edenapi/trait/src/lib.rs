@@ -14,6 +14,7 @@ pub use configmodel;
 pub use edenapi_types as types;
 
 pub use crate::api::SaplingRemoteApi;
+pub use crate::errors::CertErrorKind;
 pub use crate::errors::ConfigError;
 pub use crate::errors::SaplingRemoteApiError;
 pub use crate::response::Entries;
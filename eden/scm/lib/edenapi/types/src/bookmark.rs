@@ -58,3 +58,21 @@ pub struct SetBookmarkResponse {
     #[no_default]
     pub data: Result<(), ServerError>,
 }
+
+/// Long-poll for changes to a set of bookmarks. The client sends its last-known value for
+/// each bookmark it cares about; the server holds the request open until at least one of
+/// them no longer matches, then returns the bookmarks that changed (or, if `timeout_ms`
+/// elapses first, an empty list). This lets callers like `pull --rebase` automation notice
+/// bookmark movement promptly without polling `bookmarks` aggressively.
+#[auto_wire]
+#[derive(Clone, Default, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub struct BookmarksSubscribeRequest {
+    #[id(0)]
+    pub bookmarks: Vec<BookmarkEntry>,
+
+    /// How long the server may hold the request open waiting for a change, in
+    /// milliseconds. The server clamps this to its own maximum.
+    #[id(1)]
+    pub timeout_ms: Option<u64>,
+}
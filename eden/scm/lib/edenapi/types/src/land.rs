@@ -63,3 +63,44 @@ pub struct LandStackResponse {
     #[no_default]
     pub data: Result<LandStackData, ServerError>,
 }
+
+/// A progress update emitted while a stack land is in flight. The server streams zero or
+/// more of these ahead of the final [`LandStackResponse`] so that a large land doesn't look
+/// hung to the client and retries stay server-side.
+#[auto_wire]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub enum LandStackProgress {
+    /// Resolving the head/base commits and validating the bookmark.
+    #[id(1)]
+    Validating,
+    /// Running landing hooks against the stack.
+    #[id(2)]
+    RunningHooks,
+    /// Attempting the pushrebase; `retry_count` counts prior conflicts that were retried.
+    #[id(3)]
+    Rebasing { retry_count: u32 },
+}
+
+impl Default for LandStackProgress {
+    fn default() -> Self {
+        Self::Validating
+    }
+}
+
+/// One item of the `/land` response stream: either a progress update or the final result.
+#[auto_wire]
+#[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub enum LandStackResponseItem {
+    #[id(1)]
+    Progress(LandStackProgress),
+    #[id(2)]
+    Done(LandStackResponse),
+}
+
+impl Default for LandStackResponseItem {
+    fn default() -> Self {
+        Self::Progress(LandStackProgress::default())
+    }
+}
@@ -38,6 +38,7 @@ pub mod file;
 pub mod history;
 pub mod land;
 pub mod metadata;
+pub mod path_history;
 pub mod segments;
 pub mod suffix_query;
 pub mod token;
@@ -71,6 +72,7 @@ pub use crate::blame::BlameRequest;
 pub use crate::blame::BlameResult;
 pub use crate::bookmark::BookmarkEntry;
 pub use crate::bookmark::BookmarkRequest;
+pub use crate::bookmark::BookmarksSubscribeRequest;
 pub use crate::bookmark::SetBookmarkRequest;
 pub use crate::bookmark::SetBookmarkResponse;
 pub use crate::cloud::CloudShareWorkspaceRequest;
@@ -151,8 +153,10 @@ pub use crate::history::HistoryResponse;
 pub use crate::history::HistoryResponseChunk;
 pub use crate::history::WireHistoryEntry;
 pub use crate::land::LandStackData;
+pub use crate::land::LandStackProgress;
 pub use crate::land::LandStackRequest;
 pub use crate::land::LandStackResponse;
+pub use crate::land::LandStackResponseItem;
 pub use crate::land::PushVar;
 pub use crate::metadata::AnyFileContentId;
 pub use crate::metadata::Blake3;
@@ -164,6 +168,9 @@ pub use crate::metadata::FsnodeId;
 pub use crate::metadata::Sha1;
 pub use crate::metadata::Sha256;
 pub use crate::segments::CommitGraphSegments;
+pub use crate::path_history::PathHistoryRequest;
+pub use crate::path_history::PathHistoryResponse;
+pub use crate::path_history::PathHistoryResponseChunk;
 pub use crate::suffix_query::SuffixQueryRequest;
 pub use crate::suffix_query::SuffixQueryResponse;
 pub use crate::token::FileContentTokenMetadata;
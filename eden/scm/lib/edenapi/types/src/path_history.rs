@@ -0,0 +1,60 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+#[cfg(any(test, feature = "for-tests"))]
+use quickcheck_arbitrary_derive::Arbitrary;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+use type_macros::auto_wire;
+use types::HgId;
+use types::RepoPathBuf;
+
+use crate::CommitId;
+use crate::ServerError;
+
+/// Request for the commits that modified `path`, following renames using the server's
+/// mutable rename data so the client doesn't have to emulate fastlog/linkrev tracing
+/// itself over many round trips. Paginated via `cursor`, which is the last entry
+/// returned by a previous response.
+#[auto_wire]
+#[derive(Clone, Default, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub struct PathHistoryRequest {
+    #[id(0)]
+    pub path: RepoPathBuf,
+
+    #[id(1)]
+    pub commit: CommitId,
+
+    #[id(2)]
+    pub limit: Option<u32>,
+
+    #[id(3)]
+    pub cursor: Option<HgId>,
+}
+
+#[auto_wire]
+#[derive(Clone, Default, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub struct PathHistoryResponseChunk {
+    #[id(0)]
+    pub entries: Vec<HgId>,
+
+    /// Cursor to pass as `PathHistoryRequest::cursor` to fetch the next page, or
+    /// `None` if this was the last page of history for the path.
+    #[id(1)]
+    pub next: Option<HgId>,
+}
+
+#[auto_wire]
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "for-tests"), derive(Arbitrary))]
+pub struct PathHistoryResponse {
+    #[id(0)]
+    #[no_default]
+    pub data: Result<PathHistoryResponseChunk, ServerError>,
+}
@@ -58,6 +58,7 @@ pub mod file;
 pub mod history;
 pub mod land;
 pub mod metadata;
+pub mod path_history;
 pub mod pull;
 pub mod suffix_query;
 #[cfg(test)]
@@ -92,6 +93,7 @@ pub use crate::wire::anyid::WireLookupResult;
 pub use crate::wire::batch::WireBatch;
 pub use crate::wire::bookmark::WireBookmarkEntry;
 pub use crate::wire::bookmark::WireBookmarkRequest;
+pub use crate::wire::bookmark::WireBookmarksSubscribeRequest;
 pub use crate::wire::bookmark::WireSetBookmarkRequest;
 pub use crate::wire::clone::WireCloneData;
 pub use crate::wire::clone::WireIdMapEntry;
@@ -124,8 +126,10 @@ pub use crate::wire::file::WireUploadTokensResponse;
 pub use crate::wire::history::WireHistoryRequest;
 pub use crate::wire::history::WireHistoryResponseChunk;
 pub use crate::wire::history::WireWireHistoryEntry;
+pub use crate::wire::land::WireLandStackProgress;
 pub use crate::wire::land::WireLandStackRequest;
 pub use crate::wire::land::WireLandStackResponse;
+pub use crate::wire::land::WireLandStackResponseItem;
 pub use crate::wire::land::WirePushVar;
 pub use crate::wire::metadata::WireAnyFileContentId;
 pub use crate::wire::metadata::WireBlake3;
@@ -135,6 +139,9 @@ pub use crate::wire::metadata::WireFileType;
 pub use crate::wire::metadata::WireSha1;
 pub use crate::wire::metadata::WireSha256;
 pub use crate::wire::metadata::WireTreeAuxData;
+pub use crate::wire::path_history::WirePathHistoryRequest;
+pub use crate::wire::path_history::WirePathHistoryResponse;
+pub use crate::wire::path_history::WirePathHistoryResponseChunk;
 pub use crate::wire::suffix_query::WireSuffixQueryRequest;
 pub use crate::wire::suffix_query::WireSuffixQueryResponse;
 pub use crate::wire::token::WireUploadToken;
@@ -7,6 +7,7 @@
 
 pub use crate::bookmark::WireBookmarkEntry;
 pub use crate::bookmark::WireBookmarkRequest;
+pub use crate::bookmark::WireBookmarksSubscribeRequest;
 pub use crate::bookmark::WireSetBookmarkRequest;
 
 #[cfg(test)]
@@ -17,6 +18,7 @@ mod tests {
     auto_wire_tests!(
         WireBookmarkRequest,
         WireBookmarkEntry,
-        WireSetBookmarkRequest
+        WireSetBookmarkRequest,
+        WireBookmarksSubscribeRequest,
     );
 }
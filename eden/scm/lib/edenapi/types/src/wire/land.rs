@@ -5,8 +5,10 @@
  * GNU General Public License version 2.
  */
 
+pub use crate::land::WireLandStackProgress;
 pub use crate::land::WireLandStackRequest;
 pub use crate::land::WireLandStackResponse;
+pub use crate::land::WireLandStackResponseItem;
 pub use crate::land::WirePushVar;
 
 #[cfg(test)]
@@ -14,5 +16,11 @@ mod tests {
     use super::*;
     use crate::wire::tests::auto_wire_tests;
 
-    auto_wire_tests!(WirePushVar, WireLandStackRequest, WireLandStackResponse,);
+    auto_wire_tests!(
+        WirePushVar,
+        WireLandStackRequest,
+        WireLandStackResponse,
+        WireLandStackProgress,
+        WireLandStackResponseItem,
+    );
 }
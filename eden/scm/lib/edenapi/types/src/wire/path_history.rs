@@ -0,0 +1,22 @@
+/*
+ * Copyright (c) Meta Platforms, Inc. and affiliates.
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2.
+ */
+
+pub use crate::path_history::WirePathHistoryRequest;
+pub use crate::path_history::WirePathHistoryResponse;
+pub use crate::path_history::WirePathHistoryResponseChunk;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire::tests::auto_wire_tests;
+
+    auto_wire_tests!(
+        WirePathHistoryRequest,
+        WirePathHistoryResponseChunk,
+        WirePathHistoryResponse,
+    );
+}
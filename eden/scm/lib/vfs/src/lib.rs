@@ -14,5 +14,10 @@ pub use util::lock::PathLock;
 pub use crate::async_vfs::AsyncVfsWriter;
 pub use crate::pathauditor::AuditError;
 pub use crate::pathauditor::PathAuditor;
+pub use crate::vfs::FsyncPolicy;
+pub use crate::vfs::MountType;
+pub use crate::vfs::RemoveCheckedError;
+pub use crate::vfs::RemoveContentHint;
 pub use crate::vfs::UpdateFlag;
+pub use crate::vfs::VirtualFileHandler;
 pub use crate::vfs::VFS;
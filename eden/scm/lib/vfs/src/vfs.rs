@@ -25,6 +25,8 @@ use std::os::unix::fs::PermissionsExt;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::SystemTime;
 
 use anyhow::bail;
 use anyhow::ensure;
@@ -34,10 +36,38 @@ use fsinfo::fstype;
 use fsinfo::FsType;
 use minibytes::Bytes;
 use types::RepoPath;
+use types::RepoPathBuf;
 use util::path::remove_file;
 
 use crate::pathauditor::PathAuditor;
 
+/// What the caller expects the on-disk content of a file to be, used by
+/// [`VFS::remove_checked`] to avoid deleting a file that doesn't match.
+#[derive(Clone, Debug)]
+pub enum RemoveContentHint {
+    /// Cheap check: the file's size and mtime, as previously observed.
+    SizeMtime(u64, SystemTime),
+    /// Strict check: the sha1 of the file's content.
+    ContentHash([u8; 20]),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RemoveCheckedError {
+    #[error(
+        "refusing to remove \"{0}\": on-disk content doesn't match the expected content hint"
+    )]
+    ContentMismatch(RepoPathBuf),
+    #[error(
+        "refusing to remove \"{path}\": on-disk entry is actually named \"{actual_name}\", a case-insensitive alias of a different tracked file"
+    )]
+    CaseCollision {
+        path: RepoPathBuf,
+        actual_name: String,
+    },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
 #[derive(Clone)]
 pub struct VFS {
     inner: Arc<Inner>,
@@ -49,6 +79,25 @@ struct Inner {
     supports_symlinks: bool,
     supports_executables: bool,
     case_sensitive: bool,
+    mount: MountType,
+    fsync_policy: FsyncPolicy,
+    pending_fsync: Mutex<Vec<PathBuf>>,
+}
+
+/// Controls when the [`VFS`] calls `fsync` on files it writes.
+///
+/// This lets callers trade durability for speed: laptop users doing lots of small working copy
+/// mutations may prefer `None`, while server-side automation that can't tolerate losing writes on
+/// a crash may require `PerFile`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FsyncPolicy {
+    /// Never fsync; rely on the OS to flush writes eventually.
+    #[default]
+    None,
+    /// Defer fsync of every written file until [`VFS::flush`] is called.
+    Batched,
+    /// fsync each file immediately after it is written.
+    PerFile,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -58,8 +107,57 @@ pub enum UpdateFlag {
     Executable,
 }
 
+/// Selects how a [`VFS`] satisfies reads/writes for a given path.
+///
+/// `OnDisk` is the traditional behavior: every operation goes straight to the
+/// local filesystem under `root`. `Virtualized` lets a subset of paths
+/// (anything accepted by the handler) be served by a callback instead, so
+/// working copy code can run unmodified against a virtualized checkout (e.g.
+/// EdenFS thrift calls) without needing to special-case the backing store.
+#[derive(Clone)]
+pub enum MountType {
+    OnDisk,
+    Virtualized(Arc<dyn VirtualFileHandler>),
+}
+
+impl std::fmt::Debug for MountType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MountType::OnDisk => write!(f, "OnDisk"),
+            MountType::Virtualized(_) => write!(f, "Virtualized"),
+        }
+    }
+}
+
+/// Callback implemented by VFS consumers that want to intercept reads/writes
+/// of certain path prefixes (e.g. forwarding them to EdenFS via thrift)
+/// instead of letting the VFS touch the on-disk file directly.
+///
+/// Returning `None` from `read`/`write` means "not handled", causing the VFS
+/// to fall back to its normal on-disk behavior for that path.
+pub trait VirtualFileHandler: Send + Sync {
+    /// Returns the file content if `path` is served virtually.
+    fn read(&self, path: &RepoPath) -> Result<Option<Bytes>>;
+
+    /// Writes `data` for `path` if it is served virtually, returning the
+    /// number of bytes "written".
+    fn write(&self, path: &RepoPath, data: &[u8], flag: UpdateFlag) -> Result<Option<usize>>;
+}
+
 impl VFS {
     pub fn new(root: PathBuf) -> Result<Self> {
+        Self::new_with_mount(root, MountType::OnDisk)
+    }
+
+    pub fn new_with_mount(root: PathBuf, mount: MountType) -> Result<Self> {
+        Self::new_with_config(root, mount, FsyncPolicy::default())
+    }
+
+    pub fn new_with_fsync_policy(root: PathBuf, fsync_policy: FsyncPolicy) -> Result<Self> {
+        Self::new_with_config(root, MountType::OnDisk, fsync_policy)
+    }
+
+    pub fn new_with_config(root: PathBuf, mount: MountType, fsync_policy: FsyncPolicy) -> Result<Self> {
         let auditor = PathAuditor::new(&root);
         let fs_type =
             fstype(&root).with_context(|| format!("can't construct a VFS for {:?}", root))?;
@@ -74,10 +172,30 @@ impl VFS {
                 supports_symlinks,
                 supports_executables,
                 case_sensitive,
+                mount,
+                fsync_policy,
+                pending_fsync: Mutex::new(Vec::new()),
             }),
         })
     }
 
+    pub fn fsync_policy(&self) -> FsyncPolicy {
+        self.inner.fsync_policy
+    }
+
+    /// Fsync barrier: blocks until every write made under `FsyncPolicy::Batched` since the last
+    /// `flush()` (or construction) has been fsync-ed. A no-op under `None`/`PerFile`.
+    pub fn flush(&self) -> Result<()> {
+        let paths = std::mem::take(&mut *self.inner.pending_fsync.lock().unwrap());
+        for path in paths {
+            if let Ok(file) = File::open(&path) {
+                file.sync_all()
+                    .with_context(|| format!("can't fsync {:?}", path))?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn root(&self) -> &Path {
         &self.inner.root
     }
@@ -191,9 +309,31 @@ impl VFS {
 
         f.write_all(content)
             .with_context(|| format!("can't write to {:?}", filepath))?;
+
+        self.apply_fsync_policy(&f, filepath)?;
+
         Ok(content.len())
     }
 
+    /// Fsyncs or defers fsync of a just-written file according to `self.inner.fsync_policy`.
+    fn apply_fsync_policy(&self, file: &File, filepath: &Path) -> Result<()> {
+        match self.inner.fsync_policy {
+            FsyncPolicy::None => {}
+            FsyncPolicy::PerFile => {
+                file.sync_all()
+                    .with_context(|| format!("can't fsync {:?}", filepath))?;
+            }
+            FsyncPolicy::Batched => {
+                self.inner
+                    .pending_fsync
+                    .lock()
+                    .unwrap()
+                    .push(filepath.to_path_buf());
+            }
+        }
+        Ok(())
+    }
+
     #[cfg(unix)]
     fn update_mode(mode: u32, exec: bool) -> u32 {
         if exec {
@@ -256,6 +396,15 @@ impl VFS {
         let link_dest = Path::new(std::str::from_utf8(content)?);
 
         self.symlink(filepath, link_dest)?;
+
+        // A symlink can't usefully be opened and fsynced itself (opening its path follows
+        // it to the target), so fsync the containing directory instead: that's what
+        // actually makes the new directory entry durable.
+        if let Some(dir) = filepath.parent() {
+            let dir_file = File::open(dir).with_context(|| format!("can't open {:?}", dir))?;
+            self.apply_fsync_policy(&dir_file, dir)?;
+        }
+
         Ok(filepath.as_os_str().len())
     }
 
@@ -275,6 +424,12 @@ impl VFS {
     ///
     /// Return an error if fails to overwrite after clearing conflicts, or if clear conflicts fail
     pub fn write(&self, path: &RepoPath, data: &[u8], flag: UpdateFlag) -> Result<usize> {
+        if let MountType::Virtualized(handler) = &self.inner.mount {
+            if let Some(written) = handler.write(path, data, flag)? {
+                return Ok(written);
+            }
+        }
+
         // Fast path: let's try to open the file directly, we'll handle the failure only if this fails.
         match self.write_inner(path, data, flag) {
             Ok(size) => Ok(size),
@@ -334,6 +489,12 @@ impl VFS {
 
     // Reads file content
     pub fn read(&self, path: &RepoPath) -> Result<Bytes> {
+        if let MountType::Virtualized(handler) = &self.inner.mount {
+            if let Some(content) = handler.read(path)? {
+                return Ok(content);
+            }
+        }
+
         Ok(self.read_with_metadata(path)?.0)
     }
 
@@ -379,6 +540,47 @@ impl VFS {
         Ok(())
     }
 
+    /// Remove the file at `path`, but first verify that the file actually on disk matches
+    /// `expected`, refusing to delete it otherwise.
+    ///
+    /// On case-insensitive filesystems this also guards against case aliasing: if `path` is
+    /// `Foo` but the directory entry that would be removed is actually `foo` (a differently
+    /// tracked file sharing the same on-disk slot), this returns
+    /// [`RemoveCheckedError::CaseCollision`] instead of deleting it.
+    pub fn remove_checked(
+        &self,
+        path: &RepoPath,
+        expected: &RemoveContentHint,
+    ) -> Result<(), RemoveCheckedError> {
+        let filepath = self.inner.auditor.audit(path)?;
+
+        let metadata = match symlink_metadata(&filepath) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(RemoveCheckedError::Other(err.into())),
+        };
+
+        if !self.inner.case_sensitive {
+            if let Some(actual) = actual_on_disk_name(&filepath)? {
+                let expected_name = path.components().last().map(|c| c.as_str()).unwrap_or("");
+                if actual != expected_name {
+                    return Err(RemoveCheckedError::CaseCollision {
+                        path: path.to_owned(),
+                        actual_name: actual,
+                    });
+                }
+            }
+        }
+
+        if !content_matches(&filepath, &metadata, expected)
+            .with_context(|| format!("can't verify content of {:?}", filepath))?
+        {
+            return Err(RemoveCheckedError::ContentMismatch(path.to_owned()));
+        }
+
+        self.remove(path).map_err(RemoveCheckedError::Other)
+    }
+
     pub fn supports_symlinks(&self) -> bool {
         self.inner.supports_symlinks
     }
@@ -505,6 +707,52 @@ fn case_sensitive(root: &Path, fs_type: &FsType) -> Result<bool> {
     detect_case_sensitive(root)
 }
 
+/// Verifies that `filepath`'s on-disk content matches `expected`.
+fn content_matches(filepath: &Path, metadata: &Metadata, expected: &RemoveContentHint) -> Result<bool> {
+    match expected {
+        RemoveContentHint::SizeMtime(size, mtime) => {
+            Ok(metadata.len() == *size && metadata.modified()? == *mtime)
+        }
+        RemoveContentHint::ContentHash(expected_sha1) => {
+            use sha1::Digest;
+            use sha1::Sha1;
+
+            let content = fs::read(filepath)?;
+            let digest: [u8; 20] = Sha1::digest(&content).into();
+            Ok(&digest == expected_sha1)
+        }
+    }
+}
+
+/// Returns the actual name of the first entry in `filepath`'s parent directory whose name
+/// matches `filepath`'s file name ignoring ASCII case (an exact match included), or `None`.
+fn actual_on_disk_name(filepath: &Path) -> Result<Option<String>> {
+    let (Some(parent), Some(file_name)) = (filepath.parent(), filepath.file_name()) else {
+        return Ok(None);
+    };
+    let file_name = match file_name.to_str() {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    let entries = match fs::read_dir(parent) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    for entry in entries {
+        let entry = entry?;
+        if let Some(name) = entry.file_name().to_str() {
+            if name.eq_ignore_ascii_case(file_name) {
+                return Ok(Some(name.to_owned()));
+            }
+        }
+    }
+
+    Ok(None)
+}
+
 fn detect_case_sensitive(root: &Path) -> Result<bool> {
     let original_lstat = root.symlink_metadata()?;
     let root_str = root.to_str().expect("Can't convert root path to string");
@@ -534,6 +782,92 @@ fn metadata_eq(m1: &Metadata, m2: &Metadata) -> Result<bool> {
 mod tests {
     use super::*;
 
+    struct FixedHandler;
+
+    impl VirtualFileHandler for FixedHandler {
+        fn read(&self, path: &RepoPath) -> Result<Option<Bytes>> {
+            if path.as_str() == "virtual" {
+                Ok(Some(Bytes::from(b"eden".to_vec())))
+            } else {
+                Ok(None)
+            }
+        }
+
+        fn write(&self, path: &RepoPath, _data: &[u8], _flag: UpdateFlag) -> Result<Option<usize>> {
+            if path.as_str() == "virtual" {
+                Ok(Some(4))
+            } else {
+                Ok(None)
+            }
+        }
+    }
+
+    #[test]
+    fn test_virtualized_mount_passthrough() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vfs =
+            VFS::new_with_mount(tmp.path().to_path_buf(), MountType::Virtualized(Arc::new(FixedHandler)))
+                .unwrap();
+
+        let virtual_path = RepoPath::from_str("virtual").unwrap();
+        assert_eq!(vfs.read(virtual_path).unwrap(), Bytes::from(b"eden".to_vec()));
+        assert_eq!(
+            vfs.write(virtual_path, b"ignored", UpdateFlag::Regular)
+                .unwrap(),
+            4
+        );
+
+        // Paths the handler doesn't claim fall back to on-disk behavior.
+        let disk_path = RepoPath::from_str("real").unwrap();
+        vfs.write(disk_path, b"abc", UpdateFlag::Regular).unwrap();
+        assert_eq!(vfs.read(disk_path).unwrap(), Bytes::from(b"abc".to_vec()));
+    }
+
+    #[test]
+    fn test_fsync_policy_batched_flush() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vfs =
+            VFS::new_with_fsync_policy(tmp.path().to_path_buf(), FsyncPolicy::Batched).unwrap();
+        let path = RepoPath::from_str("a").unwrap();
+
+        vfs.write(path, b"abc", UpdateFlag::Regular).unwrap();
+        assert_eq!(vfs.inner.pending_fsync.lock().unwrap().len(), 1);
+
+        vfs.flush().unwrap();
+        assert!(vfs.inner.pending_fsync.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_remove_checked_content_mismatch() {
+        let tmp = tempfile::tempdir().unwrap();
+        let vfs = VFS::new(tmp.path().to_path_buf()).unwrap();
+        let path = RepoPath::from_str("a").unwrap();
+        vfs.write(path, b"abc", UpdateFlag::Regular).unwrap();
+
+        let wrong_hash = RemoveContentHint::ContentHash([0u8; 20]);
+        assert!(matches!(
+            vfs.remove_checked(path, &wrong_hash),
+            Err(RemoveCheckedError::ContentMismatch(_))
+        ));
+        assert!(vfs.is_file(path).unwrap());
+    }
+
+    #[test]
+    fn test_remove_checked_matching_hash() {
+        use sha1::Digest;
+        use sha1::Sha1;
+
+        let tmp = tempfile::tempdir().unwrap();
+        let vfs = VFS::new(tmp.path().to_path_buf()).unwrap();
+        let path = RepoPath::from_str("a").unwrap();
+        vfs.write(path, b"abc", UpdateFlag::Regular).unwrap();
+
+        let digest: [u8; 20] = Sha1::digest(b"abc").into();
+        vfs.remove_checked(path, &RemoveContentHint::ContentHash(digest))
+            .unwrap();
+        assert!(!vfs.is_file(path).unwrap());
+    }
+
     #[test]
     fn test_detect_case_sensitive() {
         let tmp = tempfile::tempdir().unwrap();